@@ -1,14 +1,22 @@
+use crate::error::ParseExpressionError;
+use crate::span::Span;
 use crate::token::Token;
 
 pub trait Node {
     fn token_literal(&self) -> String;
+    fn span(&self) -> Span;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Statement {
     Let(LetStatement),
     Return(ReturnStatement),
     Expression(ExpressionStatement),
+    Assign(AssignStatement),
+    While(WhileStatement),
+    Break(BreakStatement),
+    Continue(ContinueStatement),
+    For(ForStatement),
 }
 
 impl Node for Statement {
@@ -17,6 +25,24 @@ impl Node for Statement {
             Statement::Let(statement) => statement.token_literal(),
             Statement::Return(statement) => statement.token_literal(),
             Statement::Expression(statement) => statement.token_literal(),
+            Statement::Assign(statement) => statement.token_literal(),
+            Statement::While(statement) => statement.token_literal(),
+            Statement::Break(statement) => statement.token_literal(),
+            Statement::Continue(statement) => statement.token_literal(),
+            Statement::For(statement) => statement.token_literal(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            Statement::Let(statement) => statement.span(),
+            Statement::Return(statement) => statement.span(),
+            Statement::Expression(statement) => statement.span(),
+            Statement::Assign(statement) => statement.span(),
+            Statement::While(statement) => statement.span(),
+            Statement::Break(statement) => statement.span(),
+            Statement::Continue(statement) => statement.span(),
+            Statement::For(statement) => statement.span(),
         }
     }
 }
@@ -27,23 +53,32 @@ impl std::fmt::Display for Statement {
             Statement::Let(statement) => write!(f, "{}", statement),
             Statement::Return(statement) => write!(f, "{}", statement),
             Statement::Expression(statement) => write!(f, "{}", statement),
+            Statement::Assign(statement) => write!(f, "{}", statement),
+            Statement::While(statement) => write!(f, "{}", statement),
+            Statement::Break(statement) => write!(f, "{}", statement),
+            Statement::Continue(statement) => write!(f, "{}", statement),
+            Statement::For(statement) => write!(f, "{}", statement),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LetStatement {
     pub token: Box<Token>,
     pub identifier: Identifier,
     pub expression: Box<Expression>,
+    // the `///` comments in front of the statement.
+    pub doc: Option<String>,
 }
 
 impl LetStatement {
     pub fn new(token: Box<Token>, identifier: Identifier, expression: Box<Expression>) -> Self {
+        let doc = token.doc_comment();
         Self {
             token,
             identifier,
             expression,
+            doc,
         }
     }
 }
@@ -52,6 +87,10 @@ impl Node for LetStatement {
     fn token_literal(&self) -> String {
         self.token.literal.clone()
     }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.expression.span())
+    }
 }
 
 impl std::fmt::Display for LetStatement {
@@ -66,7 +105,7 @@ impl std::fmt::Display for LetStatement {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReturnStatement {
     pub token: Box<Token>,
     pub return_value: Box<Expression>,
@@ -85,6 +124,10 @@ impl Node for ReturnStatement {
     fn token_literal(&self) -> String {
         self.token.literal.clone()
     }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.return_value.span())
+    }
 }
 
 impl std::fmt::Display for ReturnStatement {
@@ -93,7 +136,7 @@ impl std::fmt::Display for ReturnStatement {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExpressionStatement {
     pub expression: Box<Expression>,
 }
@@ -108,6 +151,10 @@ impl Node for ExpressionStatement {
     fn token_literal(&self) -> String {
         self.expression.token_literal()
     }
+
+    fn span(&self) -> Span {
+        self.expression.span()
+    }
 }
 
 impl std::fmt::Display for ExpressionStatement {
@@ -116,13 +163,228 @@ impl std::fmt::Display for ExpressionStatement {
     }
 }
 
-#[derive(Debug)]
+// AssignStatement rebinds an existing variable or element, unlike LetStatement which
+// introduces a new binding.
+#[derive(Debug, Clone)]
+pub struct AssignStatement {
+    pub token: Box<Token>,
+    pub target: AssignTarget,
+    pub operator: String,
+    pub value: Box<Expression>,
+}
+
+impl AssignStatement {
+    pub fn new(token: Box<Token>, target: AssignTarget, value: Box<Expression>) -> Self {
+        let operator = token.literal.clone();
+        Self {
+            token,
+            target,
+            operator,
+            value,
+        }
+    }
+}
+
+impl Node for AssignStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.target.span().merge(self.value.span())
+    }
+}
+
+impl std::fmt::Display for AssignStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {};", self.target, self.operator, self.value)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum AssignTarget {
+    Identifier(Identifier),
+    Index(IndexExpression),
+}
+
+impl AssignTarget {
+    pub fn span(&self) -> Span {
+        match self {
+            AssignTarget::Identifier(identifier) => identifier.span(),
+            AssignTarget::Index(index) => index.span(),
+        }
+    }
+}
+
+impl std::fmt::Display for AssignTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AssignTarget::Identifier(identifier) => write!(f, "{}", identifier),
+            AssignTarget::Index(index) => write!(f, "{}[{}]", index.left, index.index),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WhileStatement {
+    pub token: Box<Token>,
+    pub condition: Box<Expression>,
+    pub body: BlockStatement,
+}
+
+impl WhileStatement {
+    pub fn new(token: Box<Token>, condition: Box<Expression>, body: BlockStatement) -> Self {
+        Self {
+            token,
+            condition,
+            body,
+        }
+    }
+}
+
+impl Node for WhileStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.body.span())
+    }
+}
+
+impl std::fmt::Display for WhileStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // prefix and infix expressions are already wrapped in parentheses.
+        let condition = match *self.condition {
+            Expression::Prefix(_) | Expression::Infix(_) => format!("{}", self.condition),
+            _ => format!("({})", self.condition),
+        };
+        write!(f, "{} {} {}", self.token_literal(), condition, self.body)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BreakStatement {
+    pub token: Box<Token>,
+}
+
+impl BreakStatement {
+    pub fn new(token: Box<Token>) -> Self {
+        Self { token }
+    }
+}
+
+impl Node for BreakStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span
+    }
+}
+
+impl std::fmt::Display for BreakStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{};", self.token_literal())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContinueStatement {
+    pub token: Box<Token>,
+}
+
+impl ContinueStatement {
+    pub fn new(token: Box<Token>) -> Self {
+        Self { token }
+    }
+}
+
+impl Node for ContinueStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span
+    }
+}
+
+impl std::fmt::Display for ContinueStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{};", self.token_literal())
+    }
+}
+
+// ForStatement binds one variable to each element of the iterable, or two to
+// each key and value as in `for (k, v in hash) { ... }`.
+#[derive(Debug, Clone)]
+pub struct ForStatement {
+    pub token: Box<Token>,
+    pub key: Identifier,
+    pub value: Option<Identifier>,
+    pub iterable: Box<Expression>,
+    pub body: BlockStatement,
+}
+
+impl ForStatement {
+    pub fn new(
+        token: Box<Token>,
+        key: Identifier,
+        value: Option<Identifier>,
+        iterable: Box<Expression>,
+        body: BlockStatement,
+    ) -> Self {
+        Self {
+            token,
+            key,
+            value,
+            iterable,
+            body,
+        }
+    }
+}
+
+impl Node for ForStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.body.span())
+    }
+}
+
+impl std::fmt::Display for ForStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} ({}", self.token_literal(), self.key)?;
+        if let Some(ref value) = self.value {
+            write!(f, ", {}", value)?;
+        }
+        write!(f, " in {}) {}", self.iterable, self.body)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum Expression {
     Identifier(Identifier),
     Integer(IntegerLiteral),
+    Float(FloatLiteral),
     Prefix(PrefixExpression),
     Infix(InfixExpression),
     Boolean(Boolean),
+    If(IfExpression),
+    Function(FunctionLiteral),
+    Call(CallExpression),
+    String(StringLiteral),
+    StringInterpolation(StringInterpolation),
+    Array(ArrayLiteral),
+    Index(IndexExpression),
+    Hash(HashLiteral),
+    Range(RangeExpression),
+    Match(MatchExpression),
+    Macro(MacroLiteral),
 }
 
 impl Node for Expression {
@@ -130,9 +392,43 @@ impl Node for Expression {
         match self {
             Expression::Identifier(identifier) => identifier.token_literal(),
             Expression::Integer(integer_literal) => integer_literal.token_literal(),
+            Expression::Float(float_literal) => float_literal.token_literal(),
             Expression::Prefix(prefix) => prefix.token_literal(),
             Expression::Infix(infix) => infix.token_literal(),
             Expression::Boolean(boolean) => boolean.token_literal(),
+            Expression::If(if_expression) => if_expression.token_literal(),
+            Expression::Function(function) => function.token_literal(),
+            Expression::Call(call) => call.token_literal(),
+            Expression::String(string) => string.token_literal(),
+            Expression::StringInterpolation(interpolation) => interpolation.token_literal(),
+            Expression::Array(array) => array.token_literal(),
+            Expression::Index(index) => index.token_literal(),
+            Expression::Hash(hash) => hash.token_literal(),
+            Expression::Range(range) => range.token_literal(),
+            Expression::Match(match_expression) => match_expression.token_literal(),
+            Expression::Macro(macro_literal) => macro_literal.token_literal(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            Expression::Identifier(identifier) => identifier.span(),
+            Expression::Integer(integer_literal) => integer_literal.span(),
+            Expression::Float(float_literal) => float_literal.span(),
+            Expression::Prefix(prefix) => prefix.span(),
+            Expression::Infix(infix) => infix.span(),
+            Expression::Boolean(boolean) => boolean.span(),
+            Expression::If(if_expression) => if_expression.span(),
+            Expression::Function(function) => function.span(),
+            Expression::Call(call) => call.span(),
+            Expression::String(string) => string.span(),
+            Expression::StringInterpolation(interpolation) => interpolation.span(),
+            Expression::Array(array) => array.span(),
+            Expression::Index(index) => index.span(),
+            Expression::Hash(hash) => hash.span(),
+            Expression::Range(range) => range.span(),
+            Expression::Match(match_expression) => match_expression.span(),
+            Expression::Macro(macro_literal) => macro_literal.span(),
         }
     }
 }
@@ -142,14 +438,26 @@ impl std::fmt::Display for Expression {
         match self {
             Expression::Identifier(identifier) => write!(f, "{}", identifier),
             Expression::Integer(integer_literal) => write!(f, "{}", integer_literal),
+            Expression::Float(float_literal) => write!(f, "{}", float_literal),
             Expression::Prefix(prefix) => write!(f, "{}", prefix),
             Expression::Infix(infix) => write!(f, "{}", infix),
             Expression::Boolean(boolean) => write!(f, "{}", boolean),
+            Expression::If(if_expression) => write!(f, "{}", if_expression),
+            Expression::Function(function) => write!(f, "{}", function),
+            Expression::Call(call) => write!(f, "{}", call),
+            Expression::String(string) => write!(f, "{}", string),
+            Expression::StringInterpolation(interpolation) => write!(f, "{}", interpolation),
+            Expression::Array(array) => write!(f, "{}", array),
+            Expression::Index(index) => write!(f, "{}", index),
+            Expression::Hash(hash) => write!(f, "{}", hash),
+            Expression::Range(range) => write!(f, "{}", range),
+            Expression::Match(match_expression) => write!(f, "{}", match_expression),
+            Expression::Macro(macro_literal) => write!(f, "{}", macro_literal),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Identifier {
     pub token: Box<Token>,
     pub value: String,
@@ -166,6 +474,10 @@ impl Node for Identifier {
     fn token_literal(&self) -> String {
         self.token.literal.clone()
     }
+
+    fn span(&self) -> Span {
+        self.token.span
+    }
 }
 
 impl std::fmt::Display for Identifier {
@@ -174,19 +486,28 @@ impl std::fmt::Display for Identifier {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct IntegerLiteral {
     pub token: Box<Token>,
     pub value: i64,
 }
 
 impl IntegerLiteral {
-    pub fn new(token: Box<Token>) -> Self {
-        let value = token
-            .literal
-            .parse::<i64>()
-            .unwrap_or_else(|e| panic!("could not parse {} as integer", e));
-        Self { token, value }
+    pub fn new(token: Box<Token>) -> Result<Self, ParseExpressionError> {
+        let literal = token.literal.as_str();
+        let (radix, digits) = match literal.get(..2) {
+            Some("0x") | Some("0X") => (16, &literal[2..]),
+            Some("0o") | Some("0O") => (8, &literal[2..]),
+            Some("0b") | Some("0B") => (2, &literal[2..]),
+            _ => (10, literal),
+        };
+        if !is_digit_group(digits, radix) {
+            return Err(ParseExpressionError::MalformedNumber(token));
+        }
+        match i64::from_str_radix(&digits.replace('_', ""), radix) {
+            Ok(value) => Ok(Self { token, value }),
+            Err(_) => Err(ParseExpressionError::NumberOutOfRange(token)),
+        }
     }
 }
 
@@ -194,6 +515,10 @@ impl Node for IntegerLiteral {
     fn token_literal(&self) -> String {
         self.token.literal.clone()
     }
+
+    fn span(&self) -> Span {
+        self.token.span
+    }
 }
 
 impl std::fmt::Display for IntegerLiteral {
@@ -202,7 +527,57 @@ impl std::fmt::Display for IntegerLiteral {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub struct FloatLiteral {
+    pub token: Box<Token>,
+    pub value: f64,
+}
+
+impl FloatLiteral {
+    pub fn new(token: Box<Token>) -> Result<Self, ParseExpressionError> {
+        let literal = token.literal.as_str();
+        let (mantissa, exponent) = match literal.find(&['e', 'E'][..]) {
+            Some(index) => (&literal[..index], Some(&literal[index + 1..])),
+            None => (literal, None),
+        };
+        let (integer, fraction) = match mantissa.find('.') {
+            Some(index) => (&mantissa[..index], Some(&mantissa[index + 1..])),
+            None => (mantissa, None),
+        };
+        let exponent =
+            exponent.map(|exponent| exponent.strip_prefix(&['+', '-'][..]).unwrap_or(exponent));
+        if !is_digit_group(integer, 10)
+            || !fraction.is_none_or(|fraction| is_digit_group(fraction, 10))
+            || !exponent.is_none_or(|exponent| is_digit_group(exponent, 10))
+        {
+            return Err(ParseExpressionError::MalformedNumber(token));
+        }
+        match literal.replace('_', "").parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(Self { token, value }),
+            Ok(_) => Err(ParseExpressionError::NumberOutOfRange(token)),
+            Err(_) => Err(ParseExpressionError::MalformedNumber(token)),
+        }
+    }
+}
+
+impl Node for FloatLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span
+    }
+}
+
+impl std::fmt::Display for FloatLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // Debug keeps the fraction or exponent, so the output still lexes as a Float.
+        write!(f, "{:?}", self.value)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct PrefixExpression {
     pub token: Box<Token>,
     pub operator: String,
@@ -224,6 +599,10 @@ impl Node for PrefixExpression {
     fn token_literal(&self) -> String {
         self.token.literal.clone()
     }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.right.span())
+    }
 }
 
 impl std::fmt::Display for PrefixExpression {
@@ -232,7 +611,7 @@ impl std::fmt::Display for PrefixExpression {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct InfixExpression {
     pub token: Box<Token>,
     pub left: Box<Expression>,
@@ -256,6 +635,10 @@ impl Node for InfixExpression {
     fn token_literal(&self) -> String {
         self.token.literal.clone()
     }
+
+    fn span(&self) -> Span {
+        self.left.span().merge(self.right.span())
+    }
 }
 
 impl std::fmt::Display for InfixExpression {
@@ -264,7 +647,7 @@ impl std::fmt::Display for InfixExpression {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Boolean {
     pub token: Box<Token>,
     pub value: bool,
@@ -286,12 +669,738 @@ impl Node for Boolean {
     fn token_literal(&self) -> String {
         self.token.literal.clone()
     }
+
+    fn span(&self) -> Span {
+        self.token.span
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IfExpression {
+    pub token: Box<Token>,
+    pub condition: Box<Expression>,
+    pub consequence: BlockStatement,
+    pub alternative: Option<BlockStatement>,
+}
+
+impl IfExpression {
+    pub fn new(
+        token: Box<Token>,
+        condition: Box<Expression>,
+        consequence: BlockStatement,
+        alternative: Option<BlockStatement>,
+    ) -> Self {
+        Self {
+            token,
+            condition,
+            consequence,
+            alternative,
+        }
+    }
+}
+
+impl Node for IfExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        let end = self
+            .alternative
+            .as_ref()
+            .unwrap_or(&self.consequence)
+            .span();
+        self.token.span.merge(end)
+    }
 }
 
-pub type Program = Vec<Statement>;
+impl std::fmt::Display for IfExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // prefix and infix expressions are already wrapped in parentheses.
+        let condition = match *self.condition {
+            Expression::Prefix(_) | Expression::Infix(_) => format!("{}", self.condition),
+            _ => format!("({})", self.condition),
+        };
+        write!(
+            f,
+            "{} {} {}",
+            self.token_literal(),
+            condition,
+            self.consequence
+        )?;
+        if let Some(ref alternative) = self.alternative {
+            write!(f, " else {}", alternative)?;
+        }
+        Ok(())
+    }
+}
 
-pub fn string(program: &[Statement]) -> String {
-    program
-        .iter()
-        .fold(String::new(), |string, ast| format!("{}{}", string, ast))
+#[derive(Debug, Clone)]
+pub struct BlockStatement {
+    pub token: Box<Token>,
+    pub statements: Program,
+    pub closing_token: Box<Token>,
+}
+
+impl BlockStatement {
+    pub fn new(token: Box<Token>, statements: Program, closing_token: Box<Token>) -> Self {
+        Self {
+            token,
+            statements,
+            closing_token,
+        }
+    }
+}
+
+impl Node for BlockStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.closing_token.span)
+    }
+}
+
+impl std::fmt::Display for BlockStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{{")?;
+        for statement in self.statements.iter() {
+            write!(f, " {}", statement)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionLiteral {
+    pub token: Box<Token>,
+    pub parameters: Vec<Identifier>,
+    pub body: BlockStatement,
+}
+
+impl FunctionLiteral {
+    pub fn new(token: Box<Token>, parameters: Vec<Identifier>, body: BlockStatement) -> Self {
+        Self {
+            token,
+            parameters,
+            body,
+        }
+    }
+}
+
+impl Node for FunctionLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.body.span())
+    }
+}
+
+impl std::fmt::Display for FunctionLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({}) {}",
+            self.token_literal(),
+            self.parameters
+                .iter()
+                .map(|parameter| parameter.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.body
+        )
+    }
+}
+
+// MacroLiteral is written like a function literal, but its body runs on the
+// unevaluated arguments of each call before the program is evaluated.
+#[derive(Debug, Clone)]
+pub struct MacroLiteral {
+    pub token: Box<Token>,
+    pub parameters: Vec<Identifier>,
+    pub body: BlockStatement,
+}
+
+impl MacroLiteral {
+    pub fn new(token: Box<Token>, parameters: Vec<Identifier>, body: BlockStatement) -> Self {
+        Self {
+            token,
+            parameters,
+            body,
+        }
+    }
+}
+
+impl Node for MacroLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.body.span())
+    }
+}
+
+impl std::fmt::Display for MacroLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({}) {}",
+            self.token_literal(),
+            self.parameters
+                .iter()
+                .map(|parameter| parameter.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.body
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CallExpression {
+    pub token: Box<Token>,
+    pub function: Box<Expression>,
+    pub arguments: Vec<Expression>,
+    pub closing_token: Box<Token>,
+}
+
+impl CallExpression {
+    pub fn new(
+        token: Box<Token>,
+        function: Box<Expression>,
+        arguments: Vec<Expression>,
+        closing_token: Box<Token>,
+    ) -> Self {
+        Self {
+            token,
+            function,
+            arguments,
+            closing_token,
+        }
+    }
+}
+
+impl Node for CallExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.function.span().merge(self.closing_token.span)
+    }
+}
+
+impl std::fmt::Display for CallExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({})",
+            self.function,
+            self.arguments
+                .iter()
+                .map(|argument| argument.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+pub type Program = Vec<Statement>;
+
+pub fn string(program: &[Statement]) -> String {
+    program
+        .iter()
+        .fold(String::new(), |string, ast| format!("{}{}", string, ast))
+}
+
+#[derive(Debug, Clone)]
+pub struct StringLiteral {
+    pub token: Box<Token>,
+    pub value: String,
+}
+
+impl StringLiteral {
+    pub fn new(token: Box<Token>) -> Self {
+        let value = token.literal.clone();
+        Self { token, value }
+    }
+}
+
+impl Node for StringLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span
+    }
+}
+
+impl std::fmt::Display for StringLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "\"{}\"", escape(&self.value))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum StringPart {
+    Text(String),
+    Expression(Expression),
+}
+
+impl std::fmt::Display for StringPart {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StringPart::Text(text) => write!(f, "{}", escape(text)),
+            StringPart::Expression(expression) => write!(f, "${{{}}}", expression),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StringInterpolation {
+    pub token: Box<Token>,
+    pub parts: Vec<StringPart>,
+    pub closing_token: Box<Token>,
+}
+
+impl StringInterpolation {
+    pub fn new(token: Box<Token>, parts: Vec<StringPart>, closing_token: Box<Token>) -> Self {
+        Self {
+            token,
+            parts,
+            closing_token,
+        }
+    }
+}
+
+impl Node for StringInterpolation {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.closing_token.span)
+    }
+}
+
+impl std::fmt::Display for StringInterpolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "\"")?;
+        for part in self.parts.iter() {
+            write!(f, "{}", part)?;
+        }
+        write!(f, "\"")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArrayLiteral {
+    pub token: Box<Token>,
+    pub elements: Vec<Expression>,
+    pub closing_token: Box<Token>,
+}
+
+impl ArrayLiteral {
+    pub fn new(token: Box<Token>, elements: Vec<Expression>, closing_token: Box<Token>) -> Self {
+        Self {
+            token,
+            elements,
+            closing_token,
+        }
+    }
+}
+
+impl Node for ArrayLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.closing_token.span)
+    }
+}
+
+impl std::fmt::Display for ArrayLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}]",
+            self.elements
+                .iter()
+                .map(|element| element.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexExpression {
+    pub token: Box<Token>,
+    pub left: Box<Expression>,
+    pub index: Box<Expression>,
+    pub closing_token: Box<Token>,
+}
+
+impl IndexExpression {
+    pub fn new(
+        token: Box<Token>,
+        left: Box<Expression>,
+        index: Box<Expression>,
+        closing_token: Box<Token>,
+    ) -> Self {
+        Self {
+            token,
+            left,
+            index,
+            closing_token,
+        }
+    }
+}
+
+impl Node for IndexExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.left.span().merge(self.closing_token.span)
+    }
+}
+
+impl std::fmt::Display for IndexExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}[{}])", self.left, self.index)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HashLiteral {
+    pub token: Box<Token>,
+    pub pairs: Vec<(Expression, Expression)>,
+    pub closing_token: Box<Token>,
+}
+
+impl HashLiteral {
+    pub fn new(
+        token: Box<Token>,
+        pairs: Vec<(Expression, Expression)>,
+        closing_token: Box<Token>,
+    ) -> Self {
+        Self {
+            token,
+            pairs,
+            closing_token,
+        }
+    }
+}
+
+impl Node for HashLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.closing_token.span)
+    }
+}
+
+impl std::fmt::Display for HashLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{{{}}}",
+            self.pairs
+                .iter()
+                .map(|(key, value)| format!("{}: {}", key, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+// RangeExpression is the half-open range `start..end`.
+#[derive(Debug, Clone)]
+pub struct RangeExpression {
+    pub token: Box<Token>,
+    pub start: Box<Expression>,
+    pub end: Box<Expression>,
+}
+
+impl RangeExpression {
+    pub fn new(token: Box<Token>, start: Box<Expression>, end: Box<Expression>) -> Self {
+        Self { token, start, end }
+    }
+}
+
+impl Node for RangeExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.start.span().merge(self.end.span())
+    }
+}
+
+impl std::fmt::Display for RangeExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}..{})", self.start, self.end)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchExpression {
+    pub token: Box<Token>,
+    pub subject: Box<Expression>,
+    pub arms: Vec<MatchArm>,
+    pub closing_token: Box<Token>,
+}
+
+impl MatchExpression {
+    pub fn new(
+        token: Box<Token>,
+        subject: Box<Expression>,
+        arms: Vec<MatchArm>,
+        closing_token: Box<Token>,
+    ) -> Self {
+        Self {
+            token,
+            subject,
+            arms,
+            closing_token,
+        }
+    }
+}
+
+impl Node for MatchExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.closing_token.span)
+    }
+}
+
+impl std::fmt::Display for MatchExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // prefix and infix expressions are already wrapped in parentheses.
+        let subject = match *self.subject {
+            Expression::Prefix(_) | Expression::Infix(_) => format!("{}", self.subject),
+            _ => format!("({})", self.subject),
+        };
+        write!(
+            f,
+            "{} {} {{ {} }}",
+            self.token_literal(),
+            subject,
+            self.arms
+                .iter()
+                .map(|arm| arm.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Box<Expression>>,
+    pub body: Box<Expression>,
+}
+
+impl MatchArm {
+    pub fn new(pattern: Pattern, guard: Option<Box<Expression>>, body: Box<Expression>) -> Self {
+        Self {
+            pattern,
+            guard,
+            body,
+        }
+    }
+}
+
+impl std::fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.pattern)?;
+        if let Some(ref guard) = self.guard {
+            write!(f, " if {}", guard)?;
+        }
+        write!(f, " => {}", self.body)
+    }
+}
+
+// Pattern is the left hand side of a match arm. literals are compared with `==`,
+// identifiers bind whatever they meet and `_` matches anything without binding.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Wildcard(Box<Token>),
+    Identifier(Identifier),
+    Literal(Box<Expression>),
+    Array(ArrayPattern),
+    Hash(HashPattern),
+}
+
+impl Node for Pattern {
+    fn token_literal(&self) -> String {
+        match self {
+            Pattern::Wildcard(token) => token.literal.clone(),
+            Pattern::Identifier(identifier) => identifier.token_literal(),
+            Pattern::Literal(literal) => literal.token_literal(),
+            Pattern::Array(array) => array.token_literal(),
+            Pattern::Hash(hash) => hash.token_literal(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            Pattern::Wildcard(token) => token.span,
+            Pattern::Identifier(identifier) => identifier.span(),
+            Pattern::Literal(literal) => literal.span(),
+            Pattern::Array(array) => array.span(),
+            Pattern::Hash(hash) => hash.span(),
+        }
+    }
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Pattern::Wildcard(token) => write!(f, "{}", token.literal),
+            Pattern::Identifier(identifier) => write!(f, "{}", identifier),
+            Pattern::Literal(literal) => write!(f, "{}", literal),
+            Pattern::Array(array) => write!(f, "{}", array),
+            Pattern::Hash(hash) => write!(f, "{}", hash),
+        }
+    }
+}
+
+// ArrayPattern matches arrays of exactly as many elements, or of at least as many
+// when a `...rest` binding collects the remaining ones.
+#[derive(Debug, Clone)]
+pub struct ArrayPattern {
+    pub token: Box<Token>,
+    pub elements: Vec<Pattern>,
+    pub rest: Option<Identifier>,
+    pub closing_token: Box<Token>,
+}
+
+impl ArrayPattern {
+    pub fn new(
+        token: Box<Token>,
+        elements: Vec<Pattern>,
+        rest: Option<Identifier>,
+        closing_token: Box<Token>,
+    ) -> Self {
+        Self {
+            token,
+            elements,
+            rest,
+            closing_token,
+        }
+    }
+}
+
+impl Node for ArrayPattern {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.closing_token.span)
+    }
+}
+
+impl std::fmt::Display for ArrayPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut elements = self
+            .elements
+            .iter()
+            .map(|element| element.to_string())
+            .collect::<Vec<_>>();
+        if let Some(ref rest) = self.rest {
+            elements.push(format!("...{}", rest));
+        }
+        write!(f, "[{}]", elements.join(", "))
+    }
+}
+
+// HashPattern matches hashes which hold every listed key, ignoring any others.
+#[derive(Debug, Clone)]
+pub struct HashPattern {
+    pub token: Box<Token>,
+    pub pairs: Vec<(Expression, Pattern)>,
+    pub closing_token: Box<Token>,
+}
+
+impl HashPattern {
+    pub fn new(
+        token: Box<Token>,
+        pairs: Vec<(Expression, Pattern)>,
+        closing_token: Box<Token>,
+    ) -> Self {
+        Self {
+            token,
+            pairs,
+            closing_token,
+        }
+    }
+}
+
+impl Node for HashPattern {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.token.span.merge(self.closing_token.span)
+    }
+}
+
+impl std::fmt::Display for HashPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{{{}}}",
+            self.pairs
+                .iter()
+                .map(|(key, value)| format!("{}: {}", key, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+// escapes a string value so that it lexes back to the same value.
+fn escape(value: &str) -> String {
+    let mut escaped = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\0' => escaped.push_str("\\0"),
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '$' if chars.peek() == Some(&'{') => escaped.push_str("\\$"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+// a group of digits may use `_` as a separator between digits, but must not start
+// or end with one.
+fn is_digit_group(digits: &str, radix: u32) -> bool {
+    !digits.is_empty()
+        && !digits.starts_with('_')
+        && !digits.ends_with('_')
+        && digits.chars().all(|ch| ch == '_' || ch.is_digit(radix))
 }
@@ -0,0 +1,95 @@
+use crate::error::RuntimeErrorKind;
+use crate::object::{Builtin, BuiltinFunction, Object};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+lazy_static! {
+    pub static ref BUILTINS: HashMap<&'static str, BuiltinFunction> = {
+        let builtins: [(&'static str, BuiltinFunction); 6] = [
+            ("len", len),
+            ("puts", puts),
+            ("first", first),
+            ("last", last),
+            ("rest", rest),
+            ("push", push),
+        ];
+        builtins.iter().cloned().collect()
+    };
+}
+
+pub fn look_up(name: &str) -> Option<Object> {
+    BUILTINS
+        .get(name)
+        .map(|&function| Object::Builtin(Builtin::new(name, function)))
+}
+
+fn len(arguments: &[Object]) -> Result<Object, RuntimeErrorKind> {
+    check_arguments_count(arguments, 1)?;
+    match arguments[0] {
+        Object::Array(ref elements) => Ok(Object::Integer(elements.len() as i64)),
+        Object::String(ref value) => Ok(Object::Integer(value.chars().count() as i64)),
+        Object::Hash(ref hash) => Ok(Object::Integer(hash.len() as i64)),
+        ref other => Err(RuntimeErrorKind::UnsupportedArgument(
+            "len",
+            other.type_name(),
+        )),
+    }
+}
+
+fn puts(arguments: &[Object]) -> Result<Object, RuntimeErrorKind> {
+    arguments
+        .iter()
+        .for_each(|argument| println!("{}", argument));
+    Ok(Object::Null)
+}
+
+fn first(arguments: &[Object]) -> Result<Object, RuntimeErrorKind> {
+    check_arguments_count(arguments, 1)?;
+    let elements = expect_array("first", &arguments[0])?;
+    Ok(elements.first().cloned().unwrap_or(Object::Null))
+}
+
+fn last(arguments: &[Object]) -> Result<Object, RuntimeErrorKind> {
+    check_arguments_count(arguments, 1)?;
+    let elements = expect_array("last", &arguments[0])?;
+    Ok(elements.last().cloned().unwrap_or(Object::Null))
+}
+
+fn rest(arguments: &[Object]) -> Result<Object, RuntimeErrorKind> {
+    check_arguments_count(arguments, 1)?;
+    let elements = expect_array("rest", &arguments[0])?;
+    if elements.is_empty() {
+        return Ok(Object::Null);
+    }
+    Ok(Object::Array(elements[1..].to_vec()))
+}
+
+fn push(arguments: &[Object]) -> Result<Object, RuntimeErrorKind> {
+    check_arguments_count(arguments, 2)?;
+    let mut elements = expect_array("push", &arguments[0])?.clone();
+    elements.push(arguments[1].clone());
+    Ok(Object::Array(elements))
+}
+
+fn check_arguments_count(arguments: &[Object], want: usize) -> Result<(), RuntimeErrorKind> {
+    if arguments.len() != want {
+        return Err(RuntimeErrorKind::WrongNumberOfArguments(
+            want,
+            arguments.len(),
+        ));
+    }
+    Ok(())
+}
+
+fn expect_array<'a>(
+    name: &'static str,
+    argument: &'a Object,
+) -> Result<&'a Vec<Object>, RuntimeErrorKind> {
+    match argument {
+        Object::Array(elements) => Ok(elements),
+        other => Err(RuntimeErrorKind::UnsupportedArgument(
+            name,
+            other.type_name(),
+        )),
+    }
+}
@@ -0,0 +1,171 @@
+use crate::error::RuntimeErrorKind;
+use crate::object::{Hash, Key, Object};
+use std::collections::HashMap;
+
+pub trait IntoObject {
+    fn into_object(self) -> Object;
+}
+
+pub trait FromObject: Sized {
+    fn from_object(object: Object) -> Result<Self, RuntimeErrorKind>;
+}
+
+impl IntoObject for Object {
+    fn into_object(self) -> Object {
+        self
+    }
+}
+
+impl FromObject for Object {
+    fn from_object(object: Object) -> Result<Self, RuntimeErrorKind> {
+        Ok(object)
+    }
+}
+
+impl IntoObject for () {
+    fn into_object(self) -> Object {
+        Object::Null
+    }
+}
+
+impl FromObject for () {
+    fn from_object(object: Object) -> Result<Self, RuntimeErrorKind> {
+        match object {
+            Object::Null => Ok(()),
+            other => Err(unexpected_type("Null", &other)),
+        }
+    }
+}
+
+impl IntoObject for i64 {
+    fn into_object(self) -> Object {
+        Object::Integer(self)
+    }
+}
+
+impl FromObject for i64 {
+    fn from_object(object: Object) -> Result<Self, RuntimeErrorKind> {
+        match object {
+            Object::Integer(value) => Ok(value),
+            other => Err(unexpected_type("Integer", &other)),
+        }
+    }
+}
+
+impl IntoObject for f64 {
+    fn into_object(self) -> Object {
+        Object::Float(self)
+    }
+}
+
+// integers are promoted the same way as in mixed arithmetic.
+impl FromObject for f64 {
+    fn from_object(object: Object) -> Result<Self, RuntimeErrorKind> {
+        match object {
+            Object::Float(value) => Ok(value),
+            Object::Integer(value) => Ok(value as f64),
+            other => Err(unexpected_type("Float", &other)),
+        }
+    }
+}
+
+impl IntoObject for bool {
+    fn into_object(self) -> Object {
+        Object::Boolean(self)
+    }
+}
+
+impl FromObject for bool {
+    fn from_object(object: Object) -> Result<Self, RuntimeErrorKind> {
+        match object {
+            Object::Boolean(value) => Ok(value),
+            other => Err(unexpected_type("Boolean", &other)),
+        }
+    }
+}
+
+impl IntoObject for String {
+    fn into_object(self) -> Object {
+        Object::String(self)
+    }
+}
+
+impl IntoObject for &str {
+    fn into_object(self) -> Object {
+        Object::String(self.to_string())
+    }
+}
+
+impl FromObject for String {
+    fn from_object(object: Object) -> Result<Self, RuntimeErrorKind> {
+        match object {
+            Object::String(value) => Ok(value),
+            other => Err(unexpected_type("String", &other)),
+        }
+    }
+}
+
+// None maps to null, and null maps back to None.
+impl<T: IntoObject> IntoObject for Option<T> {
+    fn into_object(self) -> Object {
+        self.map_or(Object::Null, IntoObject::into_object)
+    }
+}
+
+impl<T: FromObject> FromObject for Option<T> {
+    fn from_object(object: Object) -> Result<Self, RuntimeErrorKind> {
+        match object {
+            Object::Null => Ok(None),
+            other => T::from_object(other).map(Some),
+        }
+    }
+}
+
+impl<T: IntoObject> IntoObject for Vec<T> {
+    fn into_object(self) -> Object {
+        Object::Array(self.into_iter().map(IntoObject::into_object).collect())
+    }
+}
+
+impl<T: FromObject> FromObject for Vec<T> {
+    fn from_object(object: Object) -> Result<Self, RuntimeErrorKind> {
+        match object {
+            Object::Array(elements) => elements.into_iter().map(T::from_object).collect(),
+            other => Err(unexpected_type("Array", &other)),
+        }
+    }
+}
+
+// a HashMap has no order of its own, so its keys are sorted to keep the
+// resulting hash reproducible.
+impl<T: IntoObject> IntoObject for HashMap<String, T> {
+    fn into_object(self) -> Object {
+        let mut pairs = self.into_iter().collect::<Vec<_>>();
+        pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut hash = Hash::new();
+        for (key, value) in pairs {
+            hash.insert(Key::String(key), value.into_object());
+        }
+        Object::Hash(hash)
+    }
+}
+
+impl<T: FromObject> FromObject for HashMap<String, T> {
+    fn from_object(object: Object) -> Result<Self, RuntimeErrorKind> {
+        match object {
+            Object::Hash(hash) => hash
+                .into_iter()
+                .map(|(key, value)| match key {
+                    Key::String(key) => Ok((key, T::from_object(value)?)),
+                    other => Err(unexpected_type("String", &Object::from(other))),
+                })
+                .collect(),
+            other => Err(unexpected_type("Hash", &other)),
+        }
+    }
+}
+
+fn unexpected_type(want: &'static str, got: &Object) -> RuntimeErrorKind {
+    RuntimeErrorKind::UnexpectedType(want, got.type_name())
+}
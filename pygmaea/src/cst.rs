@@ -0,0 +1,413 @@
+use crate::ast::*;
+use crate::error::{LexError, ParseError};
+use crate::lexer::{Lexer, TokenSource};
+use crate::parser::Parser;
+use crate::span::Span;
+use crate::token::Token;
+use crate::token_type::TokenType;
+use crate::visit::{self, Visitor};
+
+// a concrete syntax tree holds every token of the source, trivia included, so that
+// printing it gives back the source byte for byte. its nodes mirror the nodes of the
+// AST, and tokens no AST node covers, such as semicolons, sit in the enclosing node.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum SyntaxKind {
+    Program,
+    LetStatement,
+    ReturnStatement,
+    ExpressionStatement,
+    AssignStatement,
+    WhileStatement,
+    BreakStatement,
+    ContinueStatement,
+    ForStatement,
+    BlockStatement,
+    Identifier,
+    IntegerLiteral,
+    FloatLiteral,
+    Boolean,
+    StringLiteral,
+    PrefixExpression,
+    InfixExpression,
+    IfExpression,
+    FunctionLiteral,
+    MacroLiteral,
+    CallExpression,
+    StringInterpolation,
+    ArrayLiteral,
+    IndexExpression,
+    HashLiteral,
+    RangeExpression,
+    MatchExpression,
+    MatchArm,
+    WildcardPattern,
+    LiteralPattern,
+    ArrayPattern,
+    HashPattern,
+}
+
+impl std::fmt::Display for SyntaxKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement>,
+}
+
+#[derive(Debug, Clone)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+// SyntaxToken keeps the source text of a token next to the token itself, as the
+// literal of a string token is its unescaped value.
+#[derive(Debug, Clone)]
+pub struct SyntaxToken {
+    pub token: Token,
+    pub text: String,
+}
+
+impl SyntaxNode {
+    pub fn new(kind: SyntaxKind, children: Vec<SyntaxElement>) -> Self {
+        Self { kind, children }
+    }
+
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = vec![];
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a SyntaxToken>) {
+        for child in self.children.iter() {
+            match child {
+                SyntaxElement::Node(node) => node.collect_tokens(tokens),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+    }
+
+    // lower parses the tokens of the tree back into an AST, so that edits made to the
+    // tokens show up in the program.
+    pub fn lower(&self) -> Result<Program, Vec<ParseError>> {
+        let tokens = self
+            .tokens()
+            .into_iter()
+            .map(|token| token.token.clone())
+            .collect();
+        let mut parser = Parser::new(TokenStream::new(tokens));
+        let program = parser.parse_program();
+        if parser.errors.is_empty() {
+            Ok(program)
+        } else {
+            Err(parser.errors)
+        }
+    }
+}
+
+impl std::fmt::Display for SyntaxNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for child in self.children.iter() {
+            match child {
+                SyntaxElement::Node(node) => write!(f, "{}", node)?,
+                SyntaxElement::Token(token) => write!(f, "{}", token)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for SyntaxToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for trivia in self.token.leading_trivia.iter() {
+            write!(f, "{}", trivia.text)?;
+        }
+        write!(f, "{}", self.text)?;
+        for trivia in self.token.trailing_trivia.iter() {
+            write!(f, "{}", trivia.text)?;
+        }
+        Ok(())
+    }
+}
+
+// parse builds the concrete syntax tree of input along with the errors found while
+// parsing it. the tree is complete even when there are errors, as tokens that do not
+// make up any node are kept in the enclosing one.
+pub fn parse(input: &str) -> (SyntaxNode, Vec<ParseError>) {
+    let mut lexer = Lexer::lossless(input.to_string());
+    let mut tokens = vec![];
+    loop {
+        let token = lexer.next_token();
+        let eof = token.token_type.is_eof();
+        tokens.push((token, lexer.take_errors()));
+        if eof {
+            break;
+        }
+    }
+
+    let mut parser = Parser::new(TokenStream::with_errors(tokens.clone()));
+    let program = parser.parse_program();
+
+    let mut collector = NodeCollector::default();
+    collector.visit_program(&program);
+    // children come after their parents and in source order within them, and a stable
+    // sort keeps it that way for a parent which starts where its first child does.
+    collector.nodes.sort_by_key(|(_, span)| span.start.offset);
+
+    let tokens = tokens
+        .into_iter()
+        .map(|(token, _)| SyntaxToken {
+            text: input[token.span.start.offset..token.span.end.offset].to_string(),
+            token,
+        })
+        .collect();
+    (build(tokens, collector.nodes), parser.errors)
+}
+
+// build nests the tokens under the nodes whose spans contain them.
+fn build(tokens: Vec<SyntaxToken>, nodes: Vec<(SyntaxKind, Span)>) -> SyntaxNode {
+    let mut nodes = nodes.into_iter().peekable();
+    let mut stack = vec![(SyntaxKind::Program, usize::MAX, vec![])];
+    for token in tokens {
+        let start = token.token.span.start.offset;
+        while stack.len() > 1 && stack.last().is_some_and(|(_, end, _)| *end <= start) {
+            close(&mut stack);
+        }
+        while let Some((kind, span)) = nodes.next_if(|(_, span)| span.start.offset <= start) {
+            if span.end.offset > start {
+                stack.push((kind, span.end.offset, vec![]));
+            }
+        }
+        if let Some((_, _, children)) = stack.last_mut() {
+            children.push(SyntaxElement::Token(token));
+        }
+    }
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+    let (kind, _, children) = stack.pop().unwrap();
+    SyntaxNode::new(kind, children)
+}
+
+fn close(stack: &mut Vec<(SyntaxKind, usize, Vec<SyntaxElement>)>) {
+    if let Some((kind, _, children)) = stack.pop() {
+        if let Some((_, _, parent)) = stack.last_mut() {
+            parent.push(SyntaxElement::Node(SyntaxNode::new(kind, children)));
+        }
+    }
+}
+
+// TokenStream replays tokens to a Parser, repeating the last one once they run out as
+// the lexer does with EOF. the errors recorded with a token are reported along with it,
+// so that the parser sees them in the same order as when it drives the lexer.
+pub struct TokenStream {
+    tokens: std::vec::IntoIter<(Token, Vec<LexError>)>,
+    last: Token,
+    errors: Vec<LexError>,
+}
+
+impl TokenStream {
+    pub fn new(tokens: Vec<Token>) -> Self {
+        Self::with_errors(tokens.into_iter().map(|token| (token, vec![])).collect())
+    }
+
+    pub fn with_errors(tokens: Vec<(Token, Vec<LexError>)>) -> Self {
+        let end = tokens
+            .last()
+            .map_or_else(Span::default, |(token, _)| token.span);
+        Self {
+            tokens: tokens.into_iter(),
+            last: Token::with_span(TokenType::EOF, "".to_string(), Span::new(end.end, end.end)),
+            errors: vec![],
+        }
+    }
+}
+
+impl TokenSource for TokenStream {
+    fn next_token(&mut self) -> Token {
+        match self.tokens.next() {
+            Some((token, mut errors)) => {
+                self.errors.append(&mut errors);
+                if token.token_type.is_eof() {
+                    self.last = token.clone();
+                }
+                token
+            }
+            None => self.last.clone(),
+        }
+    }
+
+    fn take_errors(&mut self) -> Vec<LexError> {
+        std::mem::take(&mut self.errors)
+    }
+}
+
+#[derive(Default)]
+struct NodeCollector {
+    nodes: Vec<(SyntaxKind, Span)>,
+}
+
+impl NodeCollector {
+    fn push(&mut self, kind: SyntaxKind, span: Span) {
+        self.nodes.push((kind, span));
+    }
+}
+
+impl Visitor for NodeCollector {
+    fn visit_let_statement(&mut self, statement: &LetStatement) {
+        self.push(SyntaxKind::LetStatement, statement.span());
+        visit::walk_let_statement(self, statement);
+    }
+
+    fn visit_return_statement(&mut self, statement: &ReturnStatement) {
+        self.push(SyntaxKind::ReturnStatement, statement.span());
+        visit::walk_return_statement(self, statement);
+    }
+
+    fn visit_expression_statement(&mut self, statement: &ExpressionStatement) {
+        self.push(SyntaxKind::ExpressionStatement, statement.span());
+        visit::walk_expression_statement(self, statement);
+    }
+
+    fn visit_assign_statement(&mut self, statement: &AssignStatement) {
+        self.push(SyntaxKind::AssignStatement, statement.span());
+        visit::walk_assign_statement(self, statement);
+    }
+
+    fn visit_while_statement(&mut self, statement: &WhileStatement) {
+        self.push(SyntaxKind::WhileStatement, statement.span());
+        visit::walk_while_statement(self, statement);
+    }
+
+    fn visit_for_statement(&mut self, statement: &ForStatement) {
+        self.push(SyntaxKind::ForStatement, statement.span());
+        visit::walk_for_statement(self, statement);
+    }
+
+    fn visit_break_statement(&mut self, statement: &BreakStatement) {
+        self.push(SyntaxKind::BreakStatement, statement.span());
+    }
+
+    fn visit_continue_statement(&mut self, statement: &ContinueStatement) {
+        self.push(SyntaxKind::ContinueStatement, statement.span());
+    }
+
+    fn visit_block_statement(&mut self, block: &BlockStatement) {
+        self.push(SyntaxKind::BlockStatement, block.span());
+        visit::walk_block_statement(self, block);
+    }
+
+    fn visit_identifier(&mut self, identifier: &Identifier) {
+        self.push(SyntaxKind::Identifier, identifier.span());
+    }
+
+    fn visit_integer_literal(&mut self, integer_literal: &IntegerLiteral) {
+        self.push(SyntaxKind::IntegerLiteral, integer_literal.span());
+    }
+
+    fn visit_float_literal(&mut self, float_literal: &FloatLiteral) {
+        self.push(SyntaxKind::FloatLiteral, float_literal.span());
+    }
+
+    fn visit_boolean(&mut self, boolean: &Boolean) {
+        self.push(SyntaxKind::Boolean, boolean.span());
+    }
+
+    fn visit_string_literal(&mut self, string: &StringLiteral) {
+        self.push(SyntaxKind::StringLiteral, string.span());
+    }
+
+    fn visit_prefix_expression(&mut self, prefix: &PrefixExpression) {
+        self.push(SyntaxKind::PrefixExpression, prefix.span());
+        visit::walk_prefix_expression(self, prefix);
+    }
+
+    fn visit_infix_expression(&mut self, infix: &InfixExpression) {
+        self.push(SyntaxKind::InfixExpression, infix.span());
+        visit::walk_infix_expression(self, infix);
+    }
+
+    fn visit_if_expression(&mut self, if_expression: &IfExpression) {
+        self.push(SyntaxKind::IfExpression, if_expression.span());
+        visit::walk_if_expression(self, if_expression);
+    }
+
+    fn visit_function_literal(&mut self, function: &FunctionLiteral) {
+        self.push(SyntaxKind::FunctionLiteral, function.span());
+        visit::walk_function_literal(self, function);
+    }
+
+    fn visit_macro_literal(&mut self, macro_literal: &MacroLiteral) {
+        self.push(SyntaxKind::MacroLiteral, macro_literal.span());
+        visit::walk_macro_literal(self, macro_literal);
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression) {
+        self.push(SyntaxKind::CallExpression, call.span());
+        visit::walk_call_expression(self, call);
+    }
+
+    fn visit_string_interpolation(&mut self, interpolation: &StringInterpolation) {
+        self.push(SyntaxKind::StringInterpolation, interpolation.span());
+        visit::walk_string_interpolation(self, interpolation);
+    }
+
+    fn visit_array_literal(&mut self, array: &ArrayLiteral) {
+        self.push(SyntaxKind::ArrayLiteral, array.span());
+        visit::walk_array_literal(self, array);
+    }
+
+    fn visit_index_expression(&mut self, index: &IndexExpression) {
+        self.push(SyntaxKind::IndexExpression, index.span());
+        visit::walk_index_expression(self, index);
+    }
+
+    fn visit_hash_literal(&mut self, hash: &HashLiteral) {
+        self.push(SyntaxKind::HashLiteral, hash.span());
+        visit::walk_hash_literal(self, hash);
+    }
+
+    fn visit_range_expression(&mut self, range: &RangeExpression) {
+        self.push(SyntaxKind::RangeExpression, range.span());
+        visit::walk_range_expression(self, range);
+    }
+
+    fn visit_match_expression(&mut self, match_expression: &MatchExpression) {
+        self.push(SyntaxKind::MatchExpression, match_expression.span());
+        visit::walk_match_expression(self, match_expression);
+    }
+
+    fn visit_match_arm(&mut self, arm: &MatchArm) {
+        self.push(
+            SyntaxKind::MatchArm,
+            arm.pattern.span().merge(arm.body.span()),
+        );
+        visit::walk_match_arm(self, arm);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Wildcard(token) => self.push(SyntaxKind::WildcardPattern, token.span),
+            Pattern::Literal(literal) => self.push(SyntaxKind::LiteralPattern, literal.span()),
+            // identifiers are pushed by visit_identifier, arrays and hashes by their own
+            // visit methods.
+            Pattern::Identifier(_) | Pattern::Array(_) | Pattern::Hash(_) => {}
+        }
+        visit::walk_pattern(self, pattern);
+    }
+
+    fn visit_array_pattern(&mut self, array: &ArrayPattern) {
+        self.push(SyntaxKind::ArrayPattern, array.span());
+        visit::walk_array_pattern(self, array);
+    }
+
+    fn visit_hash_pattern(&mut self, hash: &HashPattern) {
+        self.push(SyntaxKind::HashPattern, hash.span());
+        visit::walk_hash_pattern(self, hash);
+    }
+}
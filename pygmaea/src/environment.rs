@@ -0,0 +1,158 @@
+use crate::object::Object;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Default)]
+struct Scope {
+    store: HashMap<String, Object>,
+    outer: Option<Environment>,
+}
+
+// Environment is a shared handle to a scope, so that function objects can keep
+// the scope they were defined in alive after the defining call has returned.
+// a function bound in the scope it captured forms a reference cycle with it, so a
+// scope has to be released once it is done with, or it is never freed. a cycle
+// through another scope, such as a closure over a match arm stored in the enclosing
+// call, is not detected and keeps both scopes alive.
+#[derive(Default, Clone)]
+pub struct Environment {
+    scope: Rc<RefCell<Scope>>,
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn new_enclosed(outer: &Environment) -> Self {
+        Self {
+            scope: Rc::new(RefCell::new(Scope {
+                store: HashMap::new(),
+                outer: Some(outer.clone()),
+            })),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Object> {
+        let scope = self.scope.borrow();
+        match scope.store.get(name) {
+            Some(value) => Some(value.clone()),
+            None => scope.outer.as_ref().and_then(|outer| outer.get(name)),
+        }
+    }
+
+    // assign rebinds name in the innermost scope which declares it, and reports
+    // whether such a scope was found.
+    pub fn assign(&mut self, name: &str, value: Object) -> bool {
+        let outer = {
+            let mut scope = self.scope.borrow_mut();
+            if let Some(slot) = scope.store.get_mut(name) {
+                *slot = value;
+                return true;
+            }
+            scope.outer.clone()
+        };
+        match outer {
+            Some(mut outer) => outer.assign(name, value),
+            None => false,
+        }
+    }
+
+    pub fn set(&mut self, name: String, value: Object) -> Object {
+        self.scope.borrow_mut().store.insert(name, value.clone());
+        value
+    }
+
+    // release gives up this handle to the scope, and drops the bindings of the scope
+    // when every other handle to it is held by those bindings, as then nothing can
+    // look them up any more. the scopes captured by the dropped bindings are released
+    // in turn.
+    pub fn release(self) {
+        release_all(vec![self]);
+    }
+
+    // clear drops the bindings of the scope regardless of who else holds it, and
+    // releases the scopes they captured.
+    pub fn clear(self) {
+        let pending = self.take_bindings();
+        release_all(pending);
+    }
+
+    fn is_unreachable(&self) -> bool {
+        let held_by_bindings: usize = self
+            .scope
+            .borrow()
+            .store
+            .values()
+            .map(|value| self.handles_in(value))
+            .sum();
+        Rc::strong_count(&self.scope) == held_by_bindings + 1
+    }
+
+    fn handles_in(&self, value: &Object) -> usize {
+        match value {
+            Object::Function(function) if Rc::ptr_eq(&function.env.scope, &self.scope) => 1,
+            Object::Macro(definition) if Rc::ptr_eq(&definition.env.scope, &self.scope) => 1,
+            Object::Array(elements) => elements.iter().map(|value| self.handles_in(value)).sum(),
+            Object::Hash(hash) => hash.iter().map(|(_, value)| self.handles_in(value)).sum(),
+            _ => 0,
+        }
+    }
+
+    // take_bindings empties the scope and returns the handles to other scopes which it
+    // held, so that the caller can release them.
+    fn take_bindings(&self) -> Vec<Environment> {
+        let (store, outer) = {
+            let mut scope = self.scope.borrow_mut();
+            (std::mem::take(&mut scope.store), scope.outer.take())
+        };
+        let mut handles = outer.into_iter().collect();
+        for (_, value) in store.into_iter() {
+            collect_handles(value, &mut handles);
+        }
+        handles
+    }
+}
+
+// release_all works through a list instead of recursing, as the scopes released by
+// one release may reach arbitrarily far.
+fn release_all(mut pending: Vec<Environment>) {
+    while let Some(env) = pending.pop() {
+        if env.is_unreachable() {
+            pending.extend(env.take_bindings());
+        }
+    }
+}
+
+fn collect_handles(value: Object, handles: &mut Vec<Environment>) {
+    match value {
+        Object::Function(function) => handles.push(function.env),
+        Object::Macro(definition) => handles.push(definition.env),
+        Object::Array(elements) => {
+            for element in elements.into_iter() {
+                collect_handles(element, handles);
+            }
+        }
+        Object::Hash(hash) => {
+            for (_, value) in hash.into_iter() {
+                collect_handles(value, handles);
+            }
+        }
+        _ => {}
+    }
+}
+
+// the store may hold functions that captured this very environment, so only
+// names are printed to keep Debug from recursing forever.
+impl std::fmt::Debug for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let scope = self.scope.borrow();
+        let mut names = scope.store.keys().collect::<Vec<_>>();
+        names.sort();
+        f.debug_struct("Environment")
+            .field("names", &names)
+            .field("outer", &scope.outer)
+            .finish()
+    }
+}
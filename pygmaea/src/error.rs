@@ -1,55 +1,79 @@
+use crate::span::{Position, Span};
 use crate::token::Token;
 use crate::token_type::TokenType;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum ParseError {
-    NoneToken,
-    PeekToken(TokenType, Option<Box<Token>>),
-    Statement(ParseStatementError),
+    NoneToken(Span),
+    PeekToken(TokenType, Box<Token>),
     Expression(ParseExpressionError),
-}
-
-#[derive(Debug)]
-pub enum ParseStatementError {
-    Let,
-    Return,
-    Expression,
+    Lex(LexError),
+    InvalidAssignmentTarget(Span),
+    OutsideLoop(Box<Token>),
+    InvalidPattern(Box<Token>),
+    NestingDepthExceeded(Span, usize),
 }
 
 #[derive(Debug)]
 pub enum ParseExpressionError {
     NoPrefix(Box<Token>),
-    Prefix,
-    Infix,
-    Boolean,
+    UnclosedParen(Box<Token>, Box<Token>),
+    UnclosedBracket(Box<Token>, Box<Token>),
+    MalformedNumber(Box<Token>),
+    NumberOutOfRange(Box<Token>),
+}
+
+impl ParseError {
+    pub fn span(&self) -> Span {
+        match self {
+            ParseError::NoneToken(span) => *span,
+            ParseError::PeekToken(_, actual) => actual.span,
+            ParseError::Expression(e) => e.span(),
+            ParseError::Lex(e) => e.span(),
+            ParseError::InvalidAssignmentTarget(span) => *span,
+            ParseError::OutsideLoop(token) => token.span,
+            ParseError::InvalidPattern(token) => token.span,
+            ParseError::NestingDepthExceeded(span, _) => *span,
+        }
+    }
 }
 
 impl std::error::Error for ParseError {}
 impl std::fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: ", self.span())?;
         match self {
-            ParseError::NoneToken => write!(f, "expected token to be exist. got None instead."),
-            ParseError::PeekToken(expect, Some(actual)) => write!(
+            ParseError::NoneToken(_) => {
+                write!(f, "expected token to be exist. got None instead.")
+            }
+            ParseError::PeekToken(expect, actual) => write!(
                 f,
                 "expected next token to be {}, got {} instead.",
                 expect, actual.token_type
             ),
             ParseError::Expression(e) => write!(f, "{}", e),
-            _ => write!(
-                f,
-                "occur something error that does not to be catched any patterns."
-            ),
+            ParseError::Lex(e) => write!(f, "{}", e),
+            ParseError::InvalidAssignmentTarget(_) => write!(f, "invalid assignment target."),
+            ParseError::OutsideLoop(token) => write!(f, "{} outside of a loop.", token.literal),
+            ParseError::InvalidPattern(token) => {
+                write!(f, "no pattern parse for {}.", token.token_type)
+            }
+            ParseError::NestingDepthExceeded(_, max) => {
+                write!(f, "nesting depth exceeded. max={}.", max)
+            }
         }
     }
 }
 
-impl std::error::Error for ParseStatementError {}
-impl std::fmt::Display for ParseStatementError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl ParseExpressionError {
+    pub fn span(&self) -> Span {
         match self {
-            ParseStatementError::Let => write!(f, "failed to parse LetStatement."),
-            ParseStatementError::Return => write!(f, "failed to parse ReturnStatement."),
-            ParseStatementError::Expression => write!(f, "failed to parse ExpressionStatement."),
+            ParseExpressionError::NoPrefix(token) => token.span,
+            ParseExpressionError::UnclosedParen(_, actual) => actual.span,
+            ParseExpressionError::UnclosedBracket(_, actual) => actual.span,
+            ParseExpressionError::MalformedNumber(token) => token.span,
+            ParseExpressionError::NumberOutOfRange(token) => token.span,
         }
     }
 }
@@ -61,9 +85,283 @@ impl std::fmt::Display for ParseExpressionError {
             ParseExpressionError::NoPrefix(token) => {
                 write!(f, "no prefix parse for {}.", token.token_type)
             }
-            ParseExpressionError::Prefix => write!(f, "failed to parse prefix."),
-            ParseExpressionError::Infix => write!(f, "failed to parse infix expression."),
-            ParseExpressionError::Boolean => write!(f, "failed to parse boolean."),
+            ParseExpressionError::UnclosedParen(paren, actual) => write!(
+                f,
+                "unclosed {} opened at {}. expected RParen, got {} instead.",
+                paren.token_type, paren.span, actual.token_type
+            ),
+            ParseExpressionError::UnclosedBracket(bracket, actual) => write!(
+                f,
+                "unclosed {} opened at {}. expected RBracket, got {} instead.",
+                bracket.token_type, bracket.span, actual.token_type
+            ),
+            ParseExpressionError::MalformedNumber(token) => {
+                write!(f, "malformed number literal {}.", token.literal)
+            }
+            ParseExpressionError::NumberOutOfRange(token) => {
+                write!(f, "number literal {} is out of range.", token.literal)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexError {
+    UnterminatedString(Span),
+    UnterminatedComment(Span),
+    InvalidEscape(String, Span),
+}
+
+impl LexError {
+    pub fn span(&self) -> Span {
+        match self {
+            LexError::UnterminatedString(span) => *span,
+            LexError::UnterminatedComment(span) => *span,
+            LexError::InvalidEscape(_, span) => *span,
+        }
+    }
+}
+
+impl std::error::Error for LexError {}
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LexError::UnterminatedString(_) => write!(f, "unterminated string."),
+            LexError::UnterminatedComment(_) => write!(f, "unterminated block comment."),
+            LexError::InvalidEscape(escape, _) => {
+                write!(f, "invalid escape sequence {}.", escape)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeserializeError {
+    UnexpectedCharacter(char, Position),
+    UnexpectedEnd(Position),
+    InvalidNumber(String, Position),
+    InvalidEscape(String, Position),
+    NestingDepthExceeded(Position, usize),
+    UnknownKind(String),
+    UnknownTokenType(String),
+    MissingField(&'static str, String),
+    UnexpectedValue(&'static str, String),
+}
+
+impl std::error::Error for DeserializeError {}
+impl std::fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DeserializeError::UnexpectedCharacter(ch, position) => {
+                write!(f, "{}: unexpected character {:?}.", position, ch)
+            }
+            DeserializeError::UnexpectedEnd(position) => {
+                write!(f, "{}: unexpected end of input.", position)
+            }
+            DeserializeError::InvalidNumber(number, position) => {
+                write!(f, "{}: invalid number {}.", position, number)
+            }
+            DeserializeError::InvalidEscape(escape, position) => {
+                write!(f, "{}: invalid escape sequence {}.", position, escape)
+            }
+            DeserializeError::NestingDepthExceeded(position, max) => {
+                write!(f, "{}: nesting depth exceeded. max={}.", position, max)
+            }
+            DeserializeError::UnknownKind(kind) => write!(f, "unknown node kind: {}.", kind),
+            DeserializeError::UnknownTokenType(token_type) => {
+                write!(f, "unknown token type: {}.", token_type)
+            }
+            DeserializeError::MissingField(field, kind) => {
+                write!(f, "missing field {} in {}.", field, kind)
+            }
+            DeserializeError::UnexpectedValue(want, got) => {
+                write!(f, "expected {}, got {}.", want, got)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RuntimeError {
+    pub kind: RuntimeErrorKind,
+    pub span: Span,
+    pub stack: Vec<Frame>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuntimeErrorKind {
+    TypeMismatch(&'static str, String, &'static str),
+    UnknownPrefixOperator(String, &'static str),
+    UnknownInfixOperator(&'static str, String, &'static str),
+    IdentifierNotFound(String),
+    UndeclaredAssignment(String),
+    NotAFunction(&'static str),
+    WrongNumberOfArguments(usize, usize),
+    UnsupportedArgument(&'static str, &'static str),
+    UnexpectedType(&'static str, &'static str),
+    IndexOutOfBounds(i64, usize),
+    IndexNotSupported(&'static str, &'static str),
+    UnhashableKey(&'static str),
+    IndexAssignmentNotSupported(&'static str),
+    NotIterable(&'static str),
+    UnmatchedValue(String),
+    UnquotableValue(&'static str),
+    MacroResultNotQuote(&'static str),
+    OutsideLoop(&'static str),
+    Custom(String),
+    DivisionByZero,
+    IntegerOverflow,
+    FloatOverflow,
+    StepLimitExceeded(usize),
+    CallDepthExceeded(usize),
+    ObjectLimitExceeded(usize),
+    DeadlineExceeded(Duration),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub function: String,
+    pub span: Span,
+}
+
+impl RuntimeError {
+    pub fn new(kind: RuntimeErrorKind, span: Span) -> Self {
+        Self {
+            kind,
+            span,
+            stack: vec![],
+        }
+    }
+
+    pub fn message(&self) -> String {
+        self.kind.to_string()
+    }
+
+    pub fn push_frame(&mut self, frame: Frame) {
+        self.stack.push(frame);
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+impl std::fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.span, self.kind)?;
+        for frame in self.stack.iter() {
+            write!(f, "\n    at {}", frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for RuntimeErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RuntimeErrorKind::TypeMismatch(left, operator, right) => {
+                write!(f, "type mismatch: {} {} {}.", left, operator, right)
+            }
+            RuntimeErrorKind::UnknownPrefixOperator(operator, right) => {
+                write!(f, "unknown operator: {}{}.", operator, right)
+            }
+            RuntimeErrorKind::UnknownInfixOperator(left, operator, right) => {
+                write!(f, "unknown operator: {} {} {}.", left, operator, right)
+            }
+            RuntimeErrorKind::IdentifierNotFound(name) => {
+                write!(f, "identifier not found: {}.", name)
+            }
+            RuntimeErrorKind::UndeclaredAssignment(name) => {
+                write!(f, "assignment to undeclared identifier: {}.", name)
+            }
+            RuntimeErrorKind::NotAFunction(type_name) => {
+                write!(f, "not a function: {}.", type_name)
+            }
+            RuntimeErrorKind::WrongNumberOfArguments(want, got) => {
+                write!(f, "wrong number of arguments. want={}, got={}.", want, got)
+            }
+            RuntimeErrorKind::UnsupportedArgument(name, type_name) => write!(
+                f,
+                "argument to `{}` not supported, got {}.",
+                name, type_name
+            ),
+            RuntimeErrorKind::UnexpectedType(want, got) => {
+                write!(f, "expected {}, got {}.", want, got)
+            }
+            RuntimeErrorKind::IndexOutOfBounds(index, length) => write!(
+                f,
+                "index out of bounds: index={}, length={}.",
+                index, length
+            ),
+            RuntimeErrorKind::IndexNotSupported(left, index) => {
+                write!(f, "index operator not supported: {}[{}].", left, index)
+            }
+            RuntimeErrorKind::UnhashableKey(type_name) => {
+                write!(f, "unusable as hash key: {}.", type_name)
+            }
+            RuntimeErrorKind::IndexAssignmentNotSupported(type_name) => {
+                write!(f, "index assignment not supported: {}.", type_name)
+            }
+            RuntimeErrorKind::NotIterable(type_name) => {
+                write!(f, "not iterable: {}.", type_name)
+            }
+            RuntimeErrorKind::UnmatchedValue(value) => write!(f, "unmatched value: {}.", value),
+            RuntimeErrorKind::UnquotableValue(type_name) => {
+                write!(f, "unable to unquote {}.", type_name)
+            }
+            RuntimeErrorKind::MacroResultNotQuote(type_name) => {
+                write!(f, "macro must return a quote, got {}.", type_name)
+            }
+            RuntimeErrorKind::OutsideLoop(keyword) => write!(f, "{} outside of a loop.", keyword),
+            RuntimeErrorKind::Custom(message) => write!(f, "{}", message),
+            RuntimeErrorKind::DivisionByZero => write!(f, "division by zero."),
+            RuntimeErrorKind::IntegerOverflow => write!(f, "integer overflow."),
+            RuntimeErrorKind::FloatOverflow => write!(f, "float overflow."),
+            RuntimeErrorKind::StepLimitExceeded(max) => {
+                write!(f, "step limit exceeded. max={}.", max)
+            }
+            RuntimeErrorKind::CallDepthExceeded(max) => {
+                write!(f, "call depth exceeded. max={}.", max)
+            }
+            RuntimeErrorKind::ObjectLimitExceeded(max) => {
+                write!(f, "object limit exceeded. max={}.", max)
+            }
+            RuntimeErrorKind::DeadlineExceeded(timeout) => {
+                write!(f, "deadline exceeded. timeout={:?}.", timeout)
+            }
+        }
+    }
+}
+
+impl Frame {
+    pub fn new(function: String, span: Span) -> Self {
+        Self { function, span }
+    }
+}
+
+impl std::fmt::Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} ({})", self.function, self.span)
+    }
+}
+
+#[derive(Debug)]
+pub enum InterpreterError {
+    Parse(Vec<ParseError>),
+    Runtime(Box<RuntimeError>),
+}
+
+impl std::error::Error for InterpreterError {}
+impl std::fmt::Display for InterpreterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            InterpreterError::Parse(errors) => write!(
+                f,
+                "{}",
+                errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ),
+            InterpreterError::Runtime(error) => write!(f, "{}", error),
         }
     }
 }
@@ -0,0 +1,777 @@
+use crate::ast::*;
+use crate::builtins;
+use crate::environment::Environment;
+use crate::error::{Frame, RuntimeError, RuntimeErrorKind};
+use crate::limits::{Budget, Limits};
+use crate::modify::modify_expression;
+use crate::object::{Function, Hash, HashKey, Iterable, Macro, Object};
+use crate::span::Span;
+
+// Unwind is whatever stops an evaluation before it yields a value. it travels on the
+// error side of EvalResult, so that every expression passes it on with `?` rather
+// than mistaking it for an operand. a return unwinds up to the call it belongs to,
+// a break or continue up to the innermost loop.
+#[derive(Debug)]
+pub(crate) enum Unwind {
+    Error(Box<RuntimeError>),
+    Return(Object),
+    Break(Span),
+    Continue(Span),
+}
+
+pub(crate) type EvalResult = Result<Object, Unwind>;
+
+pub fn eval(program: &[Statement], env: &mut Environment) -> Object {
+    eval_with_limits(program, env, Limits::default())
+}
+
+pub fn eval_with_limits(program: &[Statement], env: &mut Environment, limits: Limits) -> Object {
+    let mut budget = Budget::new(limits);
+    finish(eval_program(program, env, &mut budget)).unwrap_or_else(Object::Error)
+}
+
+// finish stops the unwinding at a function boundary or at the top level: a return
+// yields its value, and a break or continue that has not met its loop is an error.
+// the parser rules those out, but an AST built by other means may still hold them.
+pub(crate) fn finish(result: EvalResult) -> Result<Object, Box<RuntimeError>> {
+    let (keyword, span) = match result {
+        Ok(object) | Err(Unwind::Return(object)) => return Ok(object),
+        Err(Unwind::Error(error)) => return Err(error),
+        Err(Unwind::Break(span)) => ("break", span),
+        Err(Unwind::Continue(span)) => ("continue", span),
+    };
+    Err(Box::new(RuntimeError::new(
+        RuntimeErrorKind::OutsideLoop(keyword),
+        span,
+    )))
+}
+
+fn eval_program(program: &[Statement], env: &mut Environment, budget: &mut Budget) -> EvalResult {
+    let mut result = Object::Null;
+    for statement in program.iter() {
+        result = eval_statement(statement, env, budget)?;
+    }
+    Ok(result)
+}
+
+pub(crate) fn eval_block_statement(
+    block: &BlockStatement,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    let mut result = Object::Null;
+    for statement in block.statements.iter() {
+        result = eval_statement(statement, env, budget)?;
+    }
+    Ok(result)
+}
+
+fn eval_statement(statement: &Statement, env: &mut Environment, budget: &mut Budget) -> EvalResult {
+    budget
+        .step()
+        .map_err(|kind| runtime_error(kind, statement.span()))?;
+    match statement {
+        Statement::Let(statement) => {
+            let value = eval_expression(&statement.expression, env, budget)?;
+            env.set(statement.identifier.value.clone(), value);
+            Ok(Object::Null)
+        }
+        Statement::Return(statement) => {
+            let value = eval_expression(&statement.return_value, env, budget)?;
+            Err(Unwind::Return(value))
+        }
+        Statement::Expression(statement) => eval_expression(&statement.expression, env, budget),
+        Statement::Assign(statement) => eval_assign_statement(statement, env, budget),
+        Statement::While(statement) => eval_while_statement(statement, env, budget),
+        Statement::For(statement) => eval_for_statement(statement, env, budget),
+        Statement::Break(statement) => Err(Unwind::Break(statement.span())),
+        Statement::Continue(statement) => Err(Unwind::Continue(statement.span())),
+    }
+}
+
+fn eval_while_statement(
+    statement: &WhileStatement,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    while eval_expression(&statement.condition, env, budget)?.is_truthy() {
+        match eval_block_statement(&statement.body, env, budget) {
+            Ok(_) | Err(Unwind::Continue(_)) => {}
+            Err(Unwind::Break(_)) => break,
+            Err(unwind) => return Err(unwind),
+        }
+    }
+    Ok(Object::Null)
+}
+
+// the loop variables are bound in the enclosing scope, just like a `let` inside the
+// body would be.
+fn eval_for_statement(
+    statement: &ForStatement,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    let iterator = eval_expression(&statement.iterable, env, budget)?
+        .iterate()
+        .map_err(|kind| runtime_error(kind, statement.iterable.span()))?;
+    let keyed = iterator.is_keyed();
+    for (key, value) in iterator {
+        budget
+            .step()
+            .map_err(|kind| runtime_error(kind, statement.span()))?;
+        match statement.value {
+            Some(ref value_identifier) => {
+                env.set(statement.key.value.clone(), key);
+                env.set(value_identifier.value.clone(), value);
+            }
+            None if keyed => {
+                env.set(statement.key.value.clone(), key);
+            }
+            None => {
+                env.set(statement.key.value.clone(), value);
+            }
+        }
+        match eval_block_statement(&statement.body, env, budget) {
+            Ok(_) | Err(Unwind::Continue(_)) => {}
+            Err(Unwind::Break(_)) => break,
+            Err(unwind) => return Err(unwind),
+        }
+    }
+    Ok(Object::Null)
+}
+
+fn eval_assign_statement(
+    statement: &AssignStatement,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    let (identifier, indices) = match statement.target {
+        AssignTarget::Identifier(ref identifier) => (identifier, vec![]),
+        AssignTarget::Index(ref index) => {
+            let mut indices = vec![eval_expression(&index.index, env, budget)?];
+            let mut left = &*index.left;
+            while let Expression::Index(ref index) = left {
+                indices.push(eval_expression(&index.index, env, budget)?);
+                left = &index.left;
+            }
+            indices.reverse();
+            match left {
+                Expression::Identifier(identifier) => (identifier, indices),
+                other => {
+                    return Err(runtime_error(
+                        RuntimeErrorKind::IndexAssignmentNotSupported("Expression"),
+                        other.span(),
+                    ))
+                }
+            }
+        }
+    };
+    // the value may itself assign to the root, so the root is read only once it is known.
+    let mut value = eval_expression(&statement.value, env, budget)?;
+    let root = env.get(&identifier.value).ok_or_else(|| {
+        runtime_error(
+            RuntimeErrorKind::UndeclaredAssignment(identifier.value.clone()),
+            identifier.span(),
+        )
+    })?;
+
+    if statement.operator != "=" {
+        let current = indices
+            .iter()
+            .try_fold(root.clone(), |object, index| {
+                eval_index_expression(object, index.clone())
+            })
+            .map_err(|kind| runtime_error(kind, statement.target.span()))?;
+        let operator = statement.operator.trim_end_matches('=');
+        value = eval_infix_expression(operator, current, value)
+            .map_err(|kind| runtime_error(kind, statement.span()))?;
+    }
+
+    let root = set_element(root, &indices, value)
+        .map_err(|kind| runtime_error(kind, statement.target.span()))?;
+    env.assign(&identifier.value, root);
+    Ok(Object::Null)
+}
+
+// returns container with the element at the path of indices replaced by value.
+// objects are values, so the updated container is written back by the caller.
+fn set_element(
+    container: Object,
+    indices: &[Object],
+    value: Object,
+) -> Result<Object, RuntimeErrorKind> {
+    let (index, rest) = match indices.split_first() {
+        Some(split) => split,
+        None => return Ok(value),
+    };
+    match (container, index) {
+        (Object::Array(mut elements), Object::Integer(position)) => {
+            let element = element_at(&elements, *position)?;
+            elements[*position as usize] = set_element(element, rest, value)?;
+            Ok(Object::Array(elements))
+        }
+        (Object::Array(_), index) => Err(RuntimeErrorKind::IndexNotSupported(
+            "Array",
+            index.type_name(),
+        )),
+        (Object::Hash(mut hash), key) => {
+            let key = key.hash_key()?;
+            let element = hash.get(&key).cloned().unwrap_or(Object::Null);
+            let element = set_element(element, rest, value)?;
+            hash.insert(key, element);
+            Ok(Object::Hash(hash))
+        }
+        (container, _) => Err(RuntimeErrorKind::IndexAssignmentNotSupported(
+            container.type_name(),
+        )),
+    }
+}
+
+fn eval_expression(
+    expression: &Expression,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    budget
+        .step()
+        .map_err(|kind| runtime_error(kind, expression.span()))?;
+    let object = match expression {
+        // looking a value up does not allocate a new object.
+        Expression::Identifier(identifier) => return eval_identifier(identifier, env),
+        Expression::Integer(integer_literal) => Ok(Object::Integer(integer_literal.value)),
+        Expression::Float(float_literal) => Ok(Object::Float(float_literal.value)),
+        Expression::Boolean(boolean) => Ok(Object::Boolean(boolean.value)),
+        Expression::Prefix(prefix) => eval_prefix(prefix, env, budget),
+        Expression::Infix(infix) if infix.operator == "&&" || infix.operator == "||" => {
+            eval_logical_expression(infix, env, budget)
+        }
+        Expression::Infix(infix) => eval_infix(infix, env, budget),
+        Expression::If(if_expression) => eval_if_expression(if_expression, env, budget),
+        Expression::Function(function) => Ok(Object::Function(Function::new(
+            function.parameters.clone(),
+            function.body.clone(),
+            env.clone(),
+        ))),
+        Expression::Macro(macro_literal) => Ok(Object::Macro(Macro::new(
+            macro_literal.parameters.clone(),
+            macro_literal.body.clone(),
+            env.clone(),
+        ))),
+        Expression::Call(call) if is_call_to(call, "quote") => eval_quote(call, env, budget),
+        Expression::Call(call) => eval_call_expression(call, env, budget),
+        Expression::String(string) => Ok(Object::String(string.value.clone())),
+        Expression::StringInterpolation(interpolation) => {
+            eval_string_interpolation(interpolation, env, budget)
+        }
+        Expression::Array(array) => eval_array_literal(array, env, budget),
+        Expression::Hash(hash) => eval_hash_literal(hash, env, budget),
+        Expression::Match(match_expression) => eval_match_expression(match_expression, env, budget),
+        Expression::Range(range) => eval_range_expression(range, env, budget),
+        Expression::Index(index) => eval_index(index, env, budget),
+    }?;
+    budget
+        .allocate()
+        .map_err(|kind| runtime_error(kind, expression.span()))?;
+    Ok(object)
+}
+
+// the cases of eval_expression which evaluate operands live in functions of
+// their own, as the frame of a single function holding all of them takes several
+// times the stack per level of nesting in a debug build.
+fn eval_prefix(
+    prefix: &PrefixExpression,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    let right = eval_expression(&prefix.right, env, budget)?;
+    eval_prefix_expression(&prefix.operator, right)
+        .map_err(|kind| runtime_error(kind, prefix.span()))
+}
+
+fn eval_infix(infix: &InfixExpression, env: &mut Environment, budget: &mut Budget) -> EvalResult {
+    let left = eval_expression(&infix.left, env, budget)?;
+    let right = eval_expression(&infix.right, env, budget)?;
+    eval_infix_expression(&infix.operator, left, right)
+        .map_err(|kind| runtime_error(kind, infix.span()))
+}
+
+fn eval_call_expression(
+    call: &CallExpression,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    let function = eval_expression(&call.function, env, budget)?;
+    let arguments = call
+        .arguments
+        .iter()
+        .map(|argument| eval_expression(argument, env, budget))
+        .collect::<Result<Vec<_>, _>>()?;
+    apply_function(function, arguments, call, budget)
+}
+
+fn eval_array_literal(
+    array: &ArrayLiteral,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    array
+        .elements
+        .iter()
+        .map(|element| eval_expression(element, env, budget))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Object::Array)
+}
+
+fn eval_range_expression(
+    range: &RangeExpression,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    let start = eval_range_bound(&range.start, env, budget)?;
+    let end = eval_range_bound(&range.end, env, budget)?;
+    Ok(Object::Range(start, end))
+}
+
+fn eval_index(index: &IndexExpression, env: &mut Environment, budget: &mut Budget) -> EvalResult {
+    let left = eval_expression(&index.left, env, budget)?;
+    let position = eval_expression(&index.index, env, budget)?;
+    eval_index_expression(left, position).map_err(|kind| runtime_error(kind, index.span()))
+}
+
+// quote returns its argument unevaluated as a Quote, except for the unquote calls
+// within it, whose arguments are evaluated and spliced back into the code.
+fn eval_quote(call: &CallExpression, env: &mut Environment, budget: &mut Budget) -> EvalResult {
+    let argument = single_argument(call)?;
+    let quoted = modify_expression(argument.clone(), &mut |expression| match expression {
+        Expression::Call(ref unquote) if is_call_to(unquote, "unquote") => {
+            let value = eval_expression(single_argument(unquote)?, env, budget)?;
+            value
+                .into_expression(unquote.span())
+                .map_err(|kind| runtime_error(kind, unquote.span()))
+        }
+        expression => Ok(expression),
+    })?;
+    Ok(Object::Quote(Box::new(quoted)))
+}
+
+fn single_argument(call: &CallExpression) -> Result<&Expression, Unwind> {
+    match call.arguments.as_slice() {
+        [argument] => Ok(argument),
+        arguments => Err(runtime_error(
+            RuntimeErrorKind::WrongNumberOfArguments(1, arguments.len()),
+            call.span(),
+        )),
+    }
+}
+
+fn is_call_to(call: &CallExpression, name: &str) -> bool {
+    match *call.function {
+        Expression::Identifier(ref identifier) => identifier.value == name,
+        _ => false,
+    }
+}
+
+fn eval_identifier(identifier: &Identifier, env: &Environment) -> EvalResult {
+    env.get(&identifier.value)
+        .or_else(|| builtins::look_up(&identifier.value))
+        .ok_or_else(|| {
+            runtime_error(
+                RuntimeErrorKind::IdentifierNotFound(identifier.value.clone()),
+                identifier.span(),
+            )
+        })
+}
+
+fn eval_prefix_expression(operator: &str, right: Object) -> Result<Object, RuntimeErrorKind> {
+    match (operator, right) {
+        ("!", right) => Ok(Object::Boolean(!right.is_truthy())),
+        ("-", Object::Integer(value)) => value
+            .checked_neg()
+            .map(Object::Integer)
+            .ok_or(RuntimeErrorKind::IntegerOverflow),
+        ("-", Object::Float(value)) => Ok(Object::Float(-value)),
+        (operator, right) => Err(RuntimeErrorKind::UnknownPrefixOperator(
+            operator.to_string(),
+            right.type_name(),
+        )),
+    }
+}
+
+fn eval_infix_expression(
+    operator: &str,
+    left: Object,
+    right: Object,
+) -> Result<Object, RuntimeErrorKind> {
+    match (left, right) {
+        (Object::Integer(left), Object::Integer(right)) => {
+            eval_integer_infix_expression(operator, left, right)
+        }
+        // an integer meeting a float is promoted, so mixed arithmetic always yields a float.
+        (Object::Integer(left), Object::Float(right)) => {
+            eval_float_infix_expression(operator, left as f64, right)
+        }
+        (Object::Float(left), Object::Integer(right)) => {
+            eval_float_infix_expression(operator, left, right as f64)
+        }
+        (Object::Float(left), Object::Float(right)) => {
+            eval_float_infix_expression(operator, left, right)
+        }
+        (Object::String(left), Object::String(right)) => {
+            eval_string_infix_expression(operator, left, right)
+        }
+        (Object::Boolean(left), Object::Boolean(right)) if operator == "==" => {
+            Ok(Object::Boolean(left == right))
+        }
+        (Object::Boolean(left), Object::Boolean(right)) if operator == "!=" => {
+            Ok(Object::Boolean(left != right))
+        }
+        (left, right) if left.type_name() != right.type_name() => {
+            Err(RuntimeErrorKind::TypeMismatch(
+                left.type_name(),
+                operator.to_string(),
+                right.type_name(),
+            ))
+        }
+        (left, right) => Err(RuntimeErrorKind::UnknownInfixOperator(
+            left.type_name(),
+            operator.to_string(),
+            right.type_name(),
+        )),
+    }
+}
+
+// `&&` and `||` evaluate their right operand only when the left one does not
+// decide the result already.
+fn eval_logical_expression(
+    infix: &InfixExpression,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    let left = eval_expression(&infix.left, env, budget)?.is_truthy();
+    if (infix.operator == "&&") != left {
+        return Ok(Object::Boolean(left));
+    }
+    let right = eval_expression(&infix.right, env, budget)?;
+    Ok(Object::Boolean(right.is_truthy()))
+}
+
+fn eval_integer_infix_expression(
+    operator: &str,
+    left: i64,
+    right: i64,
+) -> Result<Object, RuntimeErrorKind> {
+    let value = match operator {
+        "+" => left.checked_add(right),
+        "-" => left.checked_sub(right),
+        "*" => left.checked_mul(right),
+        "/" if right == 0 => return Err(RuntimeErrorKind::DivisionByZero),
+        "/" => left.checked_div(right),
+        "%" if right == 0 => return Err(RuntimeErrorKind::DivisionByZero),
+        "%" => left.checked_rem(right),
+        "<" => return Ok(Object::Boolean(left < right)),
+        ">" => return Ok(Object::Boolean(left > right)),
+        "<=" => return Ok(Object::Boolean(left <= right)),
+        ">=" => return Ok(Object::Boolean(left >= right)),
+        "==" => return Ok(Object::Boolean(left == right)),
+        "!=" => return Ok(Object::Boolean(left != right)),
+        _ => {
+            return Err(RuntimeErrorKind::UnknownInfixOperator(
+                "Integer",
+                operator.to_string(),
+                "Integer",
+            ))
+        }
+    };
+    value
+        .map(Object::Integer)
+        .ok_or(RuntimeErrorKind::IntegerOverflow)
+}
+
+fn eval_float_infix_expression(
+    operator: &str,
+    left: f64,
+    right: f64,
+) -> Result<Object, RuntimeErrorKind> {
+    let value = match operator {
+        "+" => left + right,
+        "-" => left - right,
+        "*" => left * right,
+        "/" if right == 0.0 => return Err(RuntimeErrorKind::DivisionByZero),
+        "/" => left / right,
+        "%" if right == 0.0 => return Err(RuntimeErrorKind::DivisionByZero),
+        "%" => left % right,
+        "<" => return Ok(Object::Boolean(left < right)),
+        ">" => return Ok(Object::Boolean(left > right)),
+        "<=" => return Ok(Object::Boolean(left <= right)),
+        ">=" => return Ok(Object::Boolean(left >= right)),
+        "==" => return Ok(Object::Boolean(left == right)),
+        "!=" => return Ok(Object::Boolean(left != right)),
+        _ => {
+            return Err(RuntimeErrorKind::UnknownInfixOperator(
+                "Float",
+                operator.to_string(),
+                "Float",
+            ))
+        }
+    };
+    if !value.is_finite() {
+        return Err(RuntimeErrorKind::FloatOverflow);
+    }
+    Ok(Object::Float(value))
+}
+
+fn eval_string_infix_expression(
+    operator: &str,
+    left: String,
+    right: String,
+) -> Result<Object, RuntimeErrorKind> {
+    match operator {
+        "+" => Ok(Object::String(left + &right)),
+        "<" => Ok(Object::Boolean(left < right)),
+        ">" => Ok(Object::Boolean(left > right)),
+        "<=" => Ok(Object::Boolean(left <= right)),
+        ">=" => Ok(Object::Boolean(left >= right)),
+        "==" => Ok(Object::Boolean(left == right)),
+        "!=" => Ok(Object::Boolean(left != right)),
+        _ => Err(RuntimeErrorKind::UnknownInfixOperator(
+            "String",
+            operator.to_string(),
+            "String",
+        )),
+    }
+}
+
+fn eval_string_interpolation(
+    interpolation: &StringInterpolation,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    let mut value = String::new();
+    for part in interpolation.parts.iter() {
+        match part {
+            StringPart::Text(text) => value.push_str(text),
+            StringPart::Expression(expression) => {
+                let object = eval_expression(expression, env, budget)?;
+                value.push_str(&object.to_string());
+            }
+        }
+    }
+    Ok(Object::String(value))
+}
+
+fn eval_index_expression(left: Object, index: Object) -> Result<Object, RuntimeErrorKind> {
+    match (left, index) {
+        (Object::Array(elements), Object::Integer(index)) => element_at(&elements, index),
+        (Object::String(value), Object::Integer(index)) => {
+            let chars = value.chars().map(|ch| ch.to_string()).map(Object::String);
+            element_at(&chars.collect::<Vec<_>>(), index)
+        }
+        (Object::Hash(hash), key) => {
+            Ok(hash.get(&key.hash_key()?).cloned().unwrap_or(Object::Null))
+        }
+        (left, index) => Err(RuntimeErrorKind::IndexNotSupported(
+            left.type_name(),
+            index.type_name(),
+        )),
+    }
+}
+
+fn eval_hash_literal(hash: &HashLiteral, env: &mut Environment, budget: &mut Budget) -> EvalResult {
+    let mut pairs = Hash::new();
+    for (key_expression, value_expression) in hash.pairs.iter() {
+        let key = eval_expression(key_expression, env, budget)?
+            .hash_key()
+            .map_err(|kind| runtime_error(kind, key_expression.span()))?;
+        let value = eval_expression(value_expression, env, budget)?;
+        pairs.insert(key, value);
+    }
+    Ok(Object::Hash(pairs))
+}
+
+// arms are tried in order. the bindings of a matching pattern live in a scope of
+// their own, which the guard and the body are evaluated in.
+fn eval_match_expression(
+    match_expression: &MatchExpression,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    let subject = eval_expression(&match_expression.subject, env, budget)?;
+    for arm in match_expression.arms.iter() {
+        let mut bindings = vec![];
+        if !match_pattern(&arm.pattern, &subject, &mut bindings, env, budget)? {
+            continue;
+        }
+        let mut arm_env = Environment::new_enclosed(env);
+        for (name, value) in bindings.into_iter() {
+            arm_env.set(name, value);
+        }
+        let guarded = match arm.guard {
+            Some(ref guard) => {
+                eval_expression(guard, &mut arm_env, budget).map(|guard| guard.is_truthy())
+            }
+            None => Ok(true),
+        };
+        let result = match guarded {
+            Ok(true) => Some(eval_expression(&arm.body, &mut arm_env, budget)),
+            Ok(false) => None,
+            Err(unwind) => Some(Err(unwind)),
+        };
+        arm_env.release();
+        if let Some(result) = result {
+            return result;
+        }
+    }
+    Err(runtime_error(
+        RuntimeErrorKind::UnmatchedValue(subject.to_string()),
+        match_expression.span(),
+    ))
+}
+
+// reports whether value matches pattern, collecting the bindings the pattern makes.
+fn match_pattern(
+    pattern: &Pattern,
+    value: &Object,
+    bindings: &mut Vec<(String, Object)>,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> Result<bool, Unwind> {
+    match (pattern, value) {
+        (Pattern::Wildcard(_), _) => Ok(true),
+        (Pattern::Identifier(identifier), value) => {
+            bindings.push((identifier.value.clone(), value.clone()));
+            Ok(true)
+        }
+        (Pattern::Literal(literal), value) => {
+            let literal = eval_expression(literal, env, budget)?;
+            Ok(match eval_infix_expression("==", literal, value.clone()) {
+                Ok(Object::Boolean(equal)) => equal,
+                _ => false,
+            })
+        }
+        (Pattern::Array(array), Object::Array(elements)) => {
+            let length_matches = match array.rest {
+                Some(_) => elements.len() >= array.elements.len(),
+                None => elements.len() == array.elements.len(),
+            };
+            if !length_matches {
+                return Ok(false);
+            }
+            for (pattern, element) in array.elements.iter().zip(elements.iter()) {
+                if !match_pattern(pattern, element, bindings, env, budget)? {
+                    return Ok(false);
+                }
+            }
+            if let Some(ref rest) = array.rest {
+                if rest.value != "_" {
+                    let rest_elements = elements[array.elements.len()..].to_vec();
+                    bindings.push((rest.value.clone(), Object::Array(rest_elements)));
+                }
+            }
+            Ok(true)
+        }
+        (Pattern::Hash(hash), Object::Hash(pairs)) => {
+            for (key_expression, pattern) in hash.pairs.iter() {
+                let key = eval_expression(key_expression, env, budget)?
+                    .hash_key()
+                    .map_err(|kind| runtime_error(kind, key_expression.span()))?;
+                match pairs.get(&key) {
+                    Some(value) if match_pattern(pattern, value, bindings, env, budget)? => {}
+                    _ => return Ok(false),
+                }
+            }
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+fn eval_range_bound(
+    bound: &Expression,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> Result<i64, Unwind> {
+    match eval_expression(bound, env, budget)? {
+        Object::Integer(value) => Ok(value),
+        other => Err(runtime_error(
+            RuntimeErrorKind::UnexpectedType("Integer", other.type_name()),
+            bound.span(),
+        )),
+    }
+}
+
+fn element_at(elements: &[Object], index: i64) -> Result<Object, RuntimeErrorKind> {
+    if index < 0 || index as usize >= elements.len() {
+        return Err(RuntimeErrorKind::IndexOutOfBounds(index, elements.len()));
+    }
+    Ok(elements[index as usize].clone())
+}
+
+fn eval_if_expression(
+    if_expression: &IfExpression,
+    env: &mut Environment,
+    budget: &mut Budget,
+) -> EvalResult {
+    let condition = eval_expression(&if_expression.condition, env, budget)?;
+
+    if condition.is_truthy() {
+        eval_block_statement(&if_expression.consequence, env, budget)
+    } else if let Some(ref alternative) = if_expression.alternative {
+        eval_block_statement(alternative, env, budget)
+    } else {
+        Ok(Object::Null)
+    }
+}
+
+fn apply_function(
+    function: Object,
+    arguments: Vec<Object>,
+    call: &CallExpression,
+    budget: &mut Budget,
+) -> EvalResult {
+    let function = match function {
+        Object::Function(function) => function,
+        Object::Builtin(builtin) => {
+            return (builtin.function)(&arguments).map_err(|kind| runtime_error(kind, call.span()))
+        }
+        other => {
+            return Err(runtime_error(
+                RuntimeErrorKind::NotAFunction(other.type_name()),
+                call.function.span(),
+            ))
+        }
+    };
+    if function.parameters.len() != arguments.len() {
+        return Err(runtime_error(
+            RuntimeErrorKind::WrongNumberOfArguments(function.parameters.len(), arguments.len()),
+            call.span(),
+        ));
+    }
+
+    let mut extended_env = Environment::new_enclosed(&function.env);
+    for (parameter, argument) in function.parameters.iter().zip(arguments) {
+        extended_env.set(parameter.value.clone(), argument);
+    }
+
+    budget
+        .enter_call()
+        .map_err(|kind| runtime_error(kind, call.span()))?;
+    let result = eval_block_statement(&function.body, &mut extended_env, budget);
+    budget.exit_call();
+    extended_env.release();
+
+    finish(result).map_err(|mut error| {
+        error.push_frame(Frame::new(callee_name(call), call.span()));
+        Unwind::Error(error)
+    })
+}
+
+fn callee_name(call: &CallExpression) -> String {
+    match *call.function {
+        Expression::Identifier(ref identifier) => identifier.value.clone(),
+        _ => "<anonymous>".to_string(),
+    }
+}
+
+fn runtime_error(kind: RuntimeErrorKind, span: Span) -> Unwind {
+    Unwind::Error(Box::new(RuntimeError::new(kind, span)))
+}
@@ -0,0 +1,462 @@
+use crate::ast::*;
+
+// Folder walks the AST by value and rebuilds it from what its methods return. like
+// Visitor, every method defaults to folding the children of its node.
+pub trait Folder {
+    fn fold_program(&mut self, program: Program) -> Program {
+        walk_program(self, program)
+    }
+
+    fn fold_statement(&mut self, statement: Statement) -> Statement {
+        walk_statement(self, statement)
+    }
+
+    fn fold_let_statement(&mut self, statement: LetStatement) -> LetStatement {
+        walk_let_statement(self, statement)
+    }
+
+    fn fold_return_statement(&mut self, statement: ReturnStatement) -> ReturnStatement {
+        walk_return_statement(self, statement)
+    }
+
+    fn fold_expression_statement(&mut self, statement: ExpressionStatement) -> ExpressionStatement {
+        walk_expression_statement(self, statement)
+    }
+
+    fn fold_assign_statement(&mut self, statement: AssignStatement) -> AssignStatement {
+        walk_assign_statement(self, statement)
+    }
+
+    fn fold_assign_target(&mut self, target: AssignTarget) -> AssignTarget {
+        walk_assign_target(self, target)
+    }
+
+    fn fold_while_statement(&mut self, statement: WhileStatement) -> WhileStatement {
+        walk_while_statement(self, statement)
+    }
+
+    fn fold_for_statement(&mut self, statement: ForStatement) -> ForStatement {
+        walk_for_statement(self, statement)
+    }
+
+    fn fold_break_statement(&mut self, statement: BreakStatement) -> BreakStatement {
+        statement
+    }
+
+    fn fold_continue_statement(&mut self, statement: ContinueStatement) -> ContinueStatement {
+        statement
+    }
+
+    fn fold_block_statement(&mut self, block: BlockStatement) -> BlockStatement {
+        walk_block_statement(self, block)
+    }
+
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        walk_expression(self, expression)
+    }
+
+    fn fold_identifier(&mut self, identifier: Identifier) -> Identifier {
+        identifier
+    }
+
+    fn fold_integer_literal(&mut self, integer_literal: IntegerLiteral) -> IntegerLiteral {
+        integer_literal
+    }
+
+    fn fold_float_literal(&mut self, float_literal: FloatLiteral) -> FloatLiteral {
+        float_literal
+    }
+
+    fn fold_boolean(&mut self, boolean: Boolean) -> Boolean {
+        boolean
+    }
+
+    fn fold_string_literal(&mut self, string: StringLiteral) -> StringLiteral {
+        string
+    }
+
+    fn fold_prefix_expression(&mut self, prefix: PrefixExpression) -> PrefixExpression {
+        walk_prefix_expression(self, prefix)
+    }
+
+    fn fold_infix_expression(&mut self, infix: InfixExpression) -> InfixExpression {
+        walk_infix_expression(self, infix)
+    }
+
+    fn fold_if_expression(&mut self, if_expression: IfExpression) -> IfExpression {
+        walk_if_expression(self, if_expression)
+    }
+
+    fn fold_function_literal(&mut self, function: FunctionLiteral) -> FunctionLiteral {
+        walk_function_literal(self, function)
+    }
+
+    fn fold_macro_literal(&mut self, macro_literal: MacroLiteral) -> MacroLiteral {
+        walk_macro_literal(self, macro_literal)
+    }
+
+    fn fold_call_expression(&mut self, call: CallExpression) -> CallExpression {
+        walk_call_expression(self, call)
+    }
+
+    fn fold_string_interpolation(
+        &mut self,
+        interpolation: StringInterpolation,
+    ) -> StringInterpolation {
+        walk_string_interpolation(self, interpolation)
+    }
+
+    fn fold_array_literal(&mut self, array: ArrayLiteral) -> ArrayLiteral {
+        walk_array_literal(self, array)
+    }
+
+    fn fold_index_expression(&mut self, index: IndexExpression) -> IndexExpression {
+        walk_index_expression(self, index)
+    }
+
+    fn fold_hash_literal(&mut self, hash: HashLiteral) -> HashLiteral {
+        walk_hash_literal(self, hash)
+    }
+
+    fn fold_range_expression(&mut self, range: RangeExpression) -> RangeExpression {
+        walk_range_expression(self, range)
+    }
+
+    fn fold_match_expression(&mut self, match_expression: MatchExpression) -> MatchExpression {
+        walk_match_expression(self, match_expression)
+    }
+
+    fn fold_match_arm(&mut self, arm: MatchArm) -> MatchArm {
+        walk_match_arm(self, arm)
+    }
+
+    fn fold_pattern(&mut self, pattern: Pattern) -> Pattern {
+        walk_pattern(self, pattern)
+    }
+
+    fn fold_array_pattern(&mut self, array: ArrayPattern) -> ArrayPattern {
+        walk_array_pattern(self, array)
+    }
+
+    fn fold_hash_pattern(&mut self, hash: HashPattern) -> HashPattern {
+        walk_hash_pattern(self, hash)
+    }
+}
+
+pub fn walk_program<F: Folder + ?Sized>(folder: &mut F, program: Program) -> Program {
+    program
+        .into_iter()
+        .map(|statement| folder.fold_statement(statement))
+        .collect()
+}
+
+pub fn walk_statement<F: Folder + ?Sized>(folder: &mut F, statement: Statement) -> Statement {
+    match statement {
+        Statement::Let(statement) => Statement::Let(folder.fold_let_statement(statement)),
+        Statement::Return(statement) => Statement::Return(folder.fold_return_statement(statement)),
+        Statement::Expression(statement) => {
+            Statement::Expression(folder.fold_expression_statement(statement))
+        }
+        Statement::Assign(statement) => Statement::Assign(folder.fold_assign_statement(statement)),
+        Statement::While(statement) => Statement::While(folder.fold_while_statement(statement)),
+        Statement::Break(statement) => Statement::Break(folder.fold_break_statement(statement)),
+        Statement::Continue(statement) => {
+            Statement::Continue(folder.fold_continue_statement(statement))
+        }
+        Statement::For(statement) => Statement::For(folder.fold_for_statement(statement)),
+    }
+}
+
+pub fn walk_let_statement<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut statement: LetStatement,
+) -> LetStatement {
+    statement.identifier = folder.fold_identifier(statement.identifier);
+    statement.expression = fold_boxed(folder, *statement.expression);
+    statement
+}
+
+pub fn walk_return_statement<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut statement: ReturnStatement,
+) -> ReturnStatement {
+    statement.return_value = fold_boxed(folder, *statement.return_value);
+    statement
+}
+
+pub fn walk_expression_statement<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut statement: ExpressionStatement,
+) -> ExpressionStatement {
+    statement.expression = fold_boxed(folder, *statement.expression);
+    statement
+}
+
+pub fn walk_assign_statement<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut statement: AssignStatement,
+) -> AssignStatement {
+    statement.target = folder.fold_assign_target(statement.target);
+    statement.value = fold_boxed(folder, *statement.value);
+    statement
+}
+
+pub fn walk_assign_target<F: Folder + ?Sized>(
+    folder: &mut F,
+    target: AssignTarget,
+) -> AssignTarget {
+    match target {
+        AssignTarget::Identifier(identifier) => {
+            AssignTarget::Identifier(folder.fold_identifier(identifier))
+        }
+        AssignTarget::Index(index) => AssignTarget::Index(folder.fold_index_expression(index)),
+    }
+}
+
+pub fn walk_while_statement<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut statement: WhileStatement,
+) -> WhileStatement {
+    statement.condition = fold_boxed(folder, *statement.condition);
+    statement.body = folder.fold_block_statement(statement.body);
+    statement
+}
+
+pub fn walk_for_statement<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut statement: ForStatement,
+) -> ForStatement {
+    statement.key = folder.fold_identifier(statement.key);
+    statement.value = statement.value.map(|value| folder.fold_identifier(value));
+    statement.iterable = fold_boxed(folder, *statement.iterable);
+    statement.body = folder.fold_block_statement(statement.body);
+    statement
+}
+
+pub fn walk_block_statement<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut block: BlockStatement,
+) -> BlockStatement {
+    block.statements = folder.fold_program(block.statements);
+    block
+}
+
+pub fn walk_expression<F: Folder + ?Sized>(folder: &mut F, expression: Expression) -> Expression {
+    match expression {
+        Expression::Identifier(identifier) => {
+            Expression::Identifier(folder.fold_identifier(identifier))
+        }
+        Expression::Integer(integer_literal) => {
+            Expression::Integer(folder.fold_integer_literal(integer_literal))
+        }
+        Expression::Float(float_literal) => {
+            Expression::Float(folder.fold_float_literal(float_literal))
+        }
+        Expression::Prefix(prefix) => Expression::Prefix(folder.fold_prefix_expression(prefix)),
+        Expression::Infix(infix) => Expression::Infix(folder.fold_infix_expression(infix)),
+        Expression::Boolean(boolean) => Expression::Boolean(folder.fold_boolean(boolean)),
+        Expression::If(if_expression) => Expression::If(folder.fold_if_expression(if_expression)),
+        Expression::Function(function) => {
+            Expression::Function(folder.fold_function_literal(function))
+        }
+        Expression::Call(call) => Expression::Call(folder.fold_call_expression(call)),
+        Expression::String(string) => Expression::String(folder.fold_string_literal(string)),
+        Expression::StringInterpolation(interpolation) => {
+            Expression::StringInterpolation(folder.fold_string_interpolation(interpolation))
+        }
+        Expression::Array(array) => Expression::Array(folder.fold_array_literal(array)),
+        Expression::Index(index) => Expression::Index(folder.fold_index_expression(index)),
+        Expression::Hash(hash) => Expression::Hash(folder.fold_hash_literal(hash)),
+        Expression::Range(range) => Expression::Range(folder.fold_range_expression(range)),
+        Expression::Match(match_expression) => {
+            Expression::Match(folder.fold_match_expression(match_expression))
+        }
+        Expression::Macro(macro_literal) => {
+            Expression::Macro(folder.fold_macro_literal(macro_literal))
+        }
+    }
+}
+
+pub fn walk_prefix_expression<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut prefix: PrefixExpression,
+) -> PrefixExpression {
+    prefix.right = fold_boxed(folder, *prefix.right);
+    prefix
+}
+
+pub fn walk_infix_expression<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut infix: InfixExpression,
+) -> InfixExpression {
+    infix.left = fold_boxed(folder, *infix.left);
+    infix.right = fold_boxed(folder, *infix.right);
+    infix
+}
+
+pub fn walk_if_expression<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut if_expression: IfExpression,
+) -> IfExpression {
+    if_expression.condition = fold_boxed(folder, *if_expression.condition);
+    if_expression.consequence = folder.fold_block_statement(if_expression.consequence);
+    if_expression.alternative = if_expression
+        .alternative
+        .map(|alternative| folder.fold_block_statement(alternative));
+    if_expression
+}
+
+pub fn walk_function_literal<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut function: FunctionLiteral,
+) -> FunctionLiteral {
+    function.parameters = fold_identifiers(folder, function.parameters);
+    function.body = folder.fold_block_statement(function.body);
+    function
+}
+
+pub fn walk_macro_literal<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut macro_literal: MacroLiteral,
+) -> MacroLiteral {
+    macro_literal.parameters = fold_identifiers(folder, macro_literal.parameters);
+    macro_literal.body = folder.fold_block_statement(macro_literal.body);
+    macro_literal
+}
+
+pub fn walk_call_expression<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut call: CallExpression,
+) -> CallExpression {
+    call.function = fold_boxed(folder, *call.function);
+    call.arguments = fold_expressions(folder, call.arguments);
+    call
+}
+
+pub fn walk_string_interpolation<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut interpolation: StringInterpolation,
+) -> StringInterpolation {
+    interpolation.parts = interpolation
+        .parts
+        .into_iter()
+        .map(|part| match part {
+            StringPart::Text(text) => StringPart::Text(text),
+            StringPart::Expression(expression) => {
+                StringPart::Expression(folder.fold_expression(expression))
+            }
+        })
+        .collect();
+    interpolation
+}
+
+pub fn walk_array_literal<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut array: ArrayLiteral,
+) -> ArrayLiteral {
+    array.elements = fold_expressions(folder, array.elements);
+    array
+}
+
+pub fn walk_index_expression<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut index: IndexExpression,
+) -> IndexExpression {
+    index.left = fold_boxed(folder, *index.left);
+    index.index = fold_boxed(folder, *index.index);
+    index
+}
+
+pub fn walk_hash_literal<F: Folder + ?Sized>(folder: &mut F, mut hash: HashLiteral) -> HashLiteral {
+    hash.pairs = hash
+        .pairs
+        .into_iter()
+        .map(|(key, value)| (folder.fold_expression(key), folder.fold_expression(value)))
+        .collect();
+    hash
+}
+
+pub fn walk_range_expression<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut range: RangeExpression,
+) -> RangeExpression {
+    range.start = fold_boxed(folder, *range.start);
+    range.end = fold_boxed(folder, *range.end);
+    range
+}
+
+pub fn walk_match_expression<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut match_expression: MatchExpression,
+) -> MatchExpression {
+    match_expression.subject = fold_boxed(folder, *match_expression.subject);
+    match_expression.arms = match_expression
+        .arms
+        .into_iter()
+        .map(|arm| folder.fold_match_arm(arm))
+        .collect();
+    match_expression
+}
+
+pub fn walk_match_arm<F: Folder + ?Sized>(folder: &mut F, mut arm: MatchArm) -> MatchArm {
+    arm.pattern = folder.fold_pattern(arm.pattern);
+    arm.guard = arm.guard.map(|guard| fold_boxed(folder, *guard));
+    arm.body = fold_boxed(folder, *arm.body);
+    arm
+}
+
+pub fn walk_pattern<F: Folder + ?Sized>(folder: &mut F, pattern: Pattern) -> Pattern {
+    match pattern {
+        Pattern::Wildcard(token) => Pattern::Wildcard(token),
+        Pattern::Identifier(identifier) => Pattern::Identifier(folder.fold_identifier(identifier)),
+        Pattern::Literal(literal) => Pattern::Literal(fold_boxed(folder, *literal)),
+        Pattern::Array(array) => Pattern::Array(folder.fold_array_pattern(array)),
+        Pattern::Hash(hash) => Pattern::Hash(folder.fold_hash_pattern(hash)),
+    }
+}
+
+pub fn walk_array_pattern<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut array: ArrayPattern,
+) -> ArrayPattern {
+    array.elements = array
+        .elements
+        .into_iter()
+        .map(|element| folder.fold_pattern(element))
+        .collect();
+    array.rest = array.rest.map(|rest| folder.fold_identifier(rest));
+    array
+}
+
+pub fn walk_hash_pattern<F: Folder + ?Sized>(folder: &mut F, mut hash: HashPattern) -> HashPattern {
+    hash.pairs = hash
+        .pairs
+        .into_iter()
+        .map(|(key, value)| (folder.fold_expression(key), folder.fold_pattern(value)))
+        .collect();
+    hash
+}
+
+fn fold_boxed<F: Folder + ?Sized>(folder: &mut F, expression: Expression) -> Box<Expression> {
+    Box::new(folder.fold_expression(expression))
+}
+
+fn fold_expressions<F: Folder + ?Sized>(
+    folder: &mut F,
+    expressions: Vec<Expression>,
+) -> Vec<Expression> {
+    expressions
+        .into_iter()
+        .map(|expression| folder.fold_expression(expression))
+        .collect()
+}
+
+fn fold_identifiers<F: Folder + ?Sized>(
+    folder: &mut F,
+    identifiers: Vec<Identifier>,
+) -> Vec<Identifier> {
+    identifiers
+        .into_iter()
+        .map(|identifier| folder.fold_identifier(identifier))
+        .collect()
+}
@@ -0,0 +1,132 @@
+use crate::ast::Program;
+use crate::convert::IntoObject;
+use crate::environment::Environment;
+use crate::error::{InterpreterError, RuntimeErrorKind};
+use crate::eval::eval_with_limits;
+use crate::lexer::Lexer;
+use crate::limits::Limits;
+use crate::macro_expansion::{define_macros, expand_macros};
+use crate::object::{Builtin, Object};
+use crate::parser::Parser;
+use std::time::Duration;
+
+// Interpreter owns a global environment which persists across calls of eval_str,
+// so that embedders can define values in one script and use them in the next.
+// macros are kept apart from values, as they only exist while expanding.
+#[derive(Debug, Default)]
+pub struct Interpreter {
+    env: Environment,
+    macros: Environment,
+    limits: Limits,
+    max_depth: Option<usize>,
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn builder() -> InterpreterBuilder {
+        Default::default()
+    }
+
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    pub fn register_fn<F>(&mut self, name: &str, function: F)
+    where
+        F: Fn(&[Object]) -> Result<Object, RuntimeErrorKind> + 'static,
+    {
+        self.env.set(
+            name.to_string(),
+            Object::Builtin(Builtin::new(name, function)),
+        );
+    }
+
+    pub fn eval_str(&mut self, input: &str) -> Result<Object, InterpreterError> {
+        let mut parser = Parser::new(Lexer::new(input.to_string()));
+        if let Some(max_depth) = self.max_depth {
+            parser.set_max_depth(max_depth);
+        }
+        let program = parser.parse_program();
+        if !parser.errors.is_empty() {
+            return Err(InterpreterError::Parse(parser.errors));
+        }
+        self.eval_program(program)
+    }
+
+    // eval_program evaluates an already parsed program, such as one loaded back from a
+    // serialized AST, exactly as eval_str would.
+    pub fn eval_program(&mut self, mut program: Program) -> Result<Object, InterpreterError> {
+        define_macros(&mut program, &mut self.macros);
+        let program =
+            expand_macros(program, &self.macros, self.limits).map_err(InterpreterError::Runtime)?;
+
+        match eval_with_limits(&program, &mut self.env, self.limits) {
+            Object::Error(error) => Err(InterpreterError::Runtime(error)),
+            object => Ok(object),
+        }
+    }
+
+    pub fn get_global(&self, name: &str) -> Option<Object> {
+        self.env.get(name)
+    }
+
+    pub fn set_global(&mut self, name: &str, value: impl IntoObject) {
+        self.env.set(name.to_string(), value.into_object());
+    }
+}
+
+// the globals are cleared when the interpreter goes away, as a function bound in
+// them holds on to them and would keep them alive forever otherwise.
+impl Drop for Interpreter {
+    fn drop(&mut self) {
+        std::mem::take(&mut self.env).clear();
+        std::mem::take(&mut self.macros).clear();
+    }
+}
+
+// InterpreterBuilder configures the resource limits applied to each eval_str call.
+// max_depth is enforced while parsing and falls back to the parser's default.
+#[derive(Debug, Default)]
+pub struct InterpreterBuilder {
+    limits: Limits,
+    max_depth: Option<usize>,
+}
+
+impl InterpreterBuilder {
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.limits.max_steps = Some(max_steps);
+        self
+    }
+
+    pub fn max_call_depth(mut self, max_call_depth: usize) -> Self {
+        self.limits.max_call_depth = Some(max_call_depth);
+        self
+    }
+
+    pub fn max_objects(mut self, max_objects: usize) -> Self {
+        self.limits.max_objects = Some(max_objects);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.limits.timeout = Some(timeout);
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn build(self) -> Interpreter {
+        Interpreter {
+            env: Environment::new(),
+            macros: Environment::new(),
+            limits: self.limits,
+            max_depth: self.max_depth,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::error::DeserializeError;
+use crate::serialize::{write_float, write_string, Scanner, Value};
+
+// a node is written as an object whose "kind" member names it, followed by its fields.
+pub fn to_string(value: &Value) -> String {
+    let mut output = String::new();
+    write_value(&mut output, value);
+    output
+}
+
+pub fn from_str(input: &str) -> Result<Value, DeserializeError> {
+    let mut scanner = Scanner::new(input);
+    let value = read_value(&mut scanner)?;
+    scanner.finish(value)
+}
+
+fn write_value(output: &mut String, value: &Value) {
+    match value {
+        Value::Null => output.push_str("null"),
+        Value::Boolean(value) => output.push_str(&value.to_string()),
+        Value::Integer(value) => output.push_str(&value.to_string()),
+        Value::Float(value) => write_float(output, *value),
+        Value::String(value) | Value::Symbol(value) => write_string(output, value),
+        Value::List(values) => {
+            output.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                write_value(output, value);
+            }
+            output.push(']');
+        }
+        Value::Node(kind, fields) => {
+            output.push_str("{\"kind\":");
+            write_string(output, kind);
+            for (name, value) in fields.iter() {
+                output.push(',');
+                write_string(output, name);
+                output.push(':');
+                write_value(output, value);
+            }
+            output.push('}');
+        }
+    }
+}
+
+fn read_value(scanner: &mut Scanner) -> Result<Value, DeserializeError> {
+    scanner.skip_whitespace();
+    match scanner.peek() {
+        Some('{') => scanner.nested(read_node),
+        Some('[') => scanner.nested(read_list),
+        Some('"') => scanner.read_string().map(Value::String),
+        Some(ch) if ch == '-' || ch.is_ascii_digit() => scanner.read_number(),
+        Some(ch) if ch.is_ascii_alphabetic() => {
+            let position = scanner.position();
+            match scanner.read_while(|ch| ch.is_ascii_alphabetic()).as_str() {
+                "null" => Ok(Value::Null),
+                "true" => Ok(Value::Boolean(true)),
+                "false" => Ok(Value::Boolean(false)),
+                _ => Err(DeserializeError::UnexpectedCharacter(ch, position)),
+            }
+        }
+        _ => Err(scanner.unexpected()),
+    }
+}
+
+fn read_list(scanner: &mut Scanner) -> Result<Value, DeserializeError> {
+    scanner.expect('[')?;
+    let mut values = vec![];
+    scanner.skip_whitespace();
+    if scanner.peek() == Some(']') {
+        scanner.expect(']')?;
+        return Ok(Value::List(values));
+    }
+    loop {
+        values.push(read_value(scanner)?);
+        scanner.skip_whitespace();
+        match scanner.peek() {
+            Some(',') => scanner.expect(',')?,
+            Some(']') => {
+                scanner.expect(']')?;
+                return Ok(Value::List(values));
+            }
+            _ => return Err(scanner.unexpected()),
+        }
+    }
+}
+
+// read_node reads an object, which has to carry a "kind" member to be a node.
+fn read_node(scanner: &mut Scanner) -> Result<Value, DeserializeError> {
+    let position = scanner.position();
+    scanner.expect('{')?;
+    let mut kind = None;
+    let mut fields = vec![];
+    scanner.skip_whitespace();
+    if scanner.peek() != Some('}') {
+        loop {
+            scanner.skip_whitespace();
+            let name = scanner.read_string()?;
+            scanner.skip_whitespace();
+            scanner.expect(':')?;
+            match (name.as_str(), read_value(scanner)?) {
+                ("kind", Value::String(value)) => kind = Some(value),
+                (_, value) => fields.push((name, value)),
+            }
+            scanner.skip_whitespace();
+            match scanner.peek() {
+                Some(',') => scanner.expect(',')?,
+                Some('}') => break,
+                _ => return Err(scanner.unexpected()),
+            }
+        }
+    }
+    scanner.expect('}')?;
+    match kind {
+        Some(kind) => Ok(Value::Node(kind, fields)),
+        None => Err(DeserializeError::MissingField(
+            "kind",
+            format!("object at {}", position),
+        )),
+    }
+}
@@ -1,4 +1,6 @@
-use crate::token::Token;
+use crate::error::LexError;
+use crate::span::{Position, Span};
+use crate::token::{Token, Trivia, TriviaKind};
 use crate::token_type::{TokenType, KEYWORDS};
 
 #[derive(Default)]
@@ -7,6 +9,15 @@ pub struct Lexer {
     position: usize,
     read_position: usize,
     examining_char: Option<char>,
+    location: Position,
+    // brace depth inside each string interpolation that is currently open.
+    interpolations: Vec<usize>,
+    // a lossless lexer keeps the trivia between tokens instead of skipping it.
+    lossless: bool,
+    errors: Vec<LexError>,
+    // errors in trailing trivia are held back until the next token, which is where a
+    // lexer that is not lossless finds them.
+    trailing_errors: Vec<LexError>,
 }
 
 impl Lexer {
@@ -19,20 +30,33 @@ impl Lexer {
         lexer
     }
 
+    pub fn lossless(input: String) -> Self {
+        Self {
+            lossless: true,
+            ..Self::new(input)
+        }
+    }
+
     pub fn next_token(&mut self) -> Token {
         use TokenType::*;
 
-        self.skip_whitespace();
+        self.errors.append(&mut self.trailing_errors);
+        let leading_trivia = self.read_trivia(false);
+        let start = self.location;
 
         let token = match self.examining_char {
-            Some(ch) if ch == '+' => Token::new(Plus, ch.to_string()),
-            Some(ch) if ch == '-' => Token::new(Minus, ch.to_string()),
-            Some(ch) if ch == '*' => Token::new(Asterisk, ch.to_string()),
-            Some(ch) if ch == '/' => Token::new(Slash, ch.to_string()),
+            Some('+') => self.read_operator(Plus, PlusAssign),
+            Some('-') => self.read_operator(Minus, MinusAssign),
+            Some('*') => self.read_operator(Asterisk, AsteriskAssign),
+            Some('/') => self.read_operator(Slash, SlashAssign),
+            Some(ch) if ch == '%' => Token::new(Percent, ch.to_string()),
             Some(ch) if ch == '=' => {
                 if let Some('=') = self.input.get(self.read_position) {
                     self.read_char();
                     Token::new(Equal, format!("{}{}", ch, self.examining_char.unwrap()))
+                } else if let Some('>') = self.input.get(self.read_position) {
+                    self.read_char();
+                    Token::new(FatArrow, format!("{}{}", ch, self.examining_char.unwrap()))
                 } else {
                     Token::new(Assign, ch.to_string())
                 }
@@ -45,15 +69,87 @@ impl Lexer {
                     Token::new(Bang, ch.to_string())
                 }
             }
-            Some(ch) if ch == '<' => Token::new(LessThan, ch.to_string()),
-            Some(ch) if ch == '>' => Token::new(GreaterThan, ch.to_string()),
+            Some(ch) if ch == '<' => {
+                if let Some('=') = self.input.get(self.read_position) {
+                    self.read_char();
+                    Token::new(
+                        LessThanOrEqual,
+                        format!("{}{}", ch, self.examining_char.unwrap()),
+                    )
+                } else {
+                    Token::new(LessThan, ch.to_string())
+                }
+            }
+            Some(ch) if ch == '>' => {
+                if let Some('=') = self.input.get(self.read_position) {
+                    self.read_char();
+                    Token::new(
+                        GreaterThanOrEqual,
+                        format!("{}{}", ch, self.examining_char.unwrap()),
+                    )
+                } else {
+                    Token::new(GreaterThan, ch.to_string())
+                }
+            }
+            Some(ch) if ch == '&' => {
+                if let Some('&') = self.input.get(self.read_position) {
+                    self.read_char();
+                    Token::new(And, format!("{}{}", ch, self.examining_char.unwrap()))
+                } else {
+                    Token::new(Illegal, ch.to_string())
+                }
+            }
+            Some(ch) if ch == '|' => {
+                if let Some('|') = self.input.get(self.read_position) {
+                    self.read_char();
+                    Token::new(Or, format!("{}{}", ch, self.examining_char.unwrap()))
+                } else {
+                    Token::new(Illegal, ch.to_string())
+                }
+            }
+            Some(ch) if ch == '.' => {
+                if let Some('.') = self.input.get(self.read_position) {
+                    self.read_char();
+                    if let Some('.') = self.input.get(self.read_position) {
+                        self.read_char();
+                        Token::new(Ellipsis, "...".to_string())
+                    } else {
+                        Token::new(DotDot, format!("{}{}", ch, self.examining_char.unwrap()))
+                    }
+                } else {
+                    Token::new(Illegal, ch.to_string())
+                }
+            }
             Some(ch) if ch == '(' => Token::new(LParen, ch.to_string()),
             Some(ch) if ch == ')' => Token::new(RParen, ch.to_string()),
-            Some(ch) if ch == '{' => Token::new(LBrace, ch.to_string()),
-            Some(ch) if ch == '}' => Token::new(RBrace, ch.to_string()),
+            Some(ch) if ch == '{' => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                Token::new(LBrace, ch.to_string())
+            }
+            Some(ch) if ch == '}' => match self.interpolations.last_mut() {
+                // this brace closes an interpolation, so the string continues after it.
+                Some(0) => {
+                    self.interpolations.pop();
+                    self.read_string(start, StringEnd, StringMiddle)
+                }
+                Some(depth) => {
+                    *depth -= 1;
+                    Token::new(RBrace, ch.to_string())
+                }
+                None => Token::new(RBrace, ch.to_string()),
+            },
+            Some('"') => self.read_string(start, String, StringStart),
+            Some(ch) if ch == '[' => Token::new(LBracket, ch.to_string()),
+            Some(ch) if ch == ']' => Token::new(RBracket, ch.to_string()),
             Some(ch) if ch == ',' => Token::new(Comma, ch.to_string()),
             Some(ch) if ch == ';' => Token::new(Semicolon, ch.to_string()),
-            Some(ch) if ch.is_ascii_digit() => Token::new(Int, self.read_number()),
+            Some(ch) if ch == ':' => Token::new(Colon, ch.to_string()),
+            Some(ch) if ch.is_ascii_digit() => {
+                let (token_type, literal) = self.read_number();
+                Token::new(token_type, literal)
+            }
             Some(ch) if is_letter(ch) => {
                 let ident = self.read_identifier();
                 Token::new(look_up_ident(&ident), ident)
@@ -62,38 +158,284 @@ impl Lexer {
             None => Token::new(EOF, "".to_string()),
         };
 
-        if !(token.token_type.is_keyword() || token.token_type.is_int()) {
+        if !(token.token_type.is_keyword() || token.token_type.is_number()) {
             self.read_char();
         }
-        token
+        let span = Span::new(start, self.location);
+        let trailing_trivia = if self.lossless {
+            let errors = self.errors.len();
+            let trivia = self.read_trivia(true);
+            self.trailing_errors = self.errors.split_off(errors);
+            trivia
+        } else {
+            vec![]
+        };
+        Token {
+            leading_trivia,
+            trailing_trivia,
+            ..Token::with_span(token.token_type, token.literal, span)
+        }
+    }
+
+    pub fn take_errors(&mut self) -> Vec<LexError> {
+        std::mem::take(&mut self.errors)
     }
 
     fn read_char(&mut self) {
+        if let Some(ch) = self.examining_char {
+            self.location.advance(ch);
+        }
         self.examining_char = self.input.get(self.read_position).cloned();
         self.position = self.read_position;
         self.read_position += 1;
     }
 
-    fn skip_whitespace(&mut self) {
-        while self
-            .examining_char
-            .as_ref()
-            .map_or(false, char::is_ascii_whitespace)
-        {
+    // reads an arithmetic operator, or its compound assignment form when `=` follows.
+    fn read_operator(&mut self, operator: TokenType, assign: TokenType) -> Token {
+        let ch = self.examining_char.unwrap();
+        if let Some('=') = self.peek_char() {
             self.read_char();
+            Token::new(assign, format!("{}=", ch))
+        } else {
+            Token::new(operator, ch.to_string())
         }
     }
 
-    fn read_number(&mut self) -> String {
+    fn peek_char(&self) -> Option<char> {
+        self.input.get(self.read_position).cloned()
+    }
+
+    // the position right after examining_char.
+    fn location_after(&self) -> Position {
+        let mut location = self.location;
+        if let Some(ch) = self.examining_char {
+            location.advance(ch);
+        }
+        location
+    }
+
+    // reads the whitespace and comments in front of the next token, which a lossless
+    // lexer keeps as trivia. doc comments are kept either way for the parser to attach.
+    // trivia trailing a token stops at the end of its line, or at a doc comment as it
+    // documents whatever follows it.
+    fn read_trivia(&mut self, trailing: bool) -> Vec<Trivia> {
+        let mut trivia = vec![];
+        loop {
+            let start = self.location;
+            let position = self.position;
+            let kind = match (self.examining_char, self.peek_char()) {
+                (Some('\n'), _) if trailing => break,
+                (Some(ch), _) if ch.is_ascii_whitespace() => {
+                    while self
+                        .examining_char
+                        .is_some_and(|ch| ch.is_ascii_whitespace() && !(trailing && ch == '\n'))
+                    {
+                        self.read_char();
+                    }
+                    TriviaKind::Whitespace
+                }
+                (Some('/'), Some('/')) if self.is_doc_comment() => {
+                    if trailing {
+                        break;
+                    }
+                    self.read_line_comment();
+                    TriviaKind::DocComment
+                }
+                (Some('/'), Some('/')) => {
+                    self.read_line_comment();
+                    TriviaKind::LineComment
+                }
+                (Some('/'), Some('*')) => {
+                    self.read_block_comment(start);
+                    TriviaKind::BlockComment
+                }
+                _ => break,
+            };
+            if self.lossless || kind == TriviaKind::DocComment {
+                trivia.push(Trivia::new(
+                    kind,
+                    self.input[position..self.position].iter().collect(),
+                    Span::new(start, self.location),
+                ));
+            }
+        }
+        trivia
+    }
+
+    // a doc comment starts with exactly three slashes, as `////` is an ordinary comment.
+    fn is_doc_comment(&self) -> bool {
+        let slashes = self.input[self.position..]
+            .iter()
+            .take_while(|ch| **ch == '/')
+            .count();
+        slashes == 3
+    }
+
+    // reads a comment up to the end of its line, leaving the newline to whitespace.
+    fn read_line_comment(&mut self) {
+        while self.examining_char.is_some_and(|ch| ch != '\n') {
+            self.read_char();
+        }
+    }
+
+    // reads a block comment including nested ones, reporting it if it never closes.
+    fn read_block_comment(&mut self, start: Position) {
+        let mut depth = 0;
+        loop {
+            match (self.examining_char, self.peek_char()) {
+                (None, _) => {
+                    self.errors.push(LexError::UnterminatedComment(Span::new(
+                        start,
+                        self.location,
+                    )));
+                    return;
+                }
+                (Some('/'), Some('*')) => {
+                    depth += 1;
+                    self.read_char();
+                }
+                (Some('*'), Some('/')) => {
+                    depth -= 1;
+                    self.read_char();
+                    if depth == 0 {
+                        self.read_char();
+                        return;
+                    }
+                }
+                _ => {}
+            }
+            self.read_char();
+        }
+    }
+
+    // reads the longest run that looks like a number, including letters and separators.
+    // checking the digits is left to the parser, which reports malformed literals.
+    fn read_number(&mut self) -> (TokenType, String) {
         let position = self.position;
-        while self
-            .examining_char
-            .as_ref()
-            .map_or(false, char::is_ascii_digit)
-        {
+        let prefixed = self.examining_char == Some('0')
+            && self.peek_char().is_some_and(|ch| "xXoObB".contains(ch));
+        let mut token_type = TokenType::Int;
+        loop {
+            match self.examining_char {
+                Some(ch) if ch.is_ascii_alphanumeric() || ch == '_' => {
+                    if !prefixed && (ch == 'e' || ch == 'E') {
+                        token_type = TokenType::Float;
+                        if matches!(self.peek_char(), Some('+') | Some('-')) {
+                            self.read_char();
+                        }
+                    }
+                    self.read_char();
+                }
+                // a dot belongs to the number unless it starts a range, so that a missing
+                // fraction is reported as a malformed literal.
+                Some('.')
+                    if !prefixed
+                        && token_type == TokenType::Int
+                        && self.peek_char() != Some('.') =>
+                {
+                    token_type = TokenType::Float;
+                    self.read_char();
+                }
+                _ => break,
+            }
+        }
+        (
+            token_type,
+            self.input[position..self.position].iter().collect(),
+        )
+    }
+
+    // reads a string up to its closing quote, or up to the `${` which opens an
+    // interpolation. examining_char is left on the last char of the token.
+    fn read_string(
+        &mut self,
+        start: Position,
+        closed: TokenType,
+        interpolated: TokenType,
+    ) -> Token {
+        let mut value = String::new();
+        loop {
             self.read_char();
+            match self.examining_char {
+                None => {
+                    self.errors.push(LexError::UnterminatedString(Span::new(
+                        start,
+                        self.location,
+                    )));
+                    return Token::new(closed, value);
+                }
+                Some('"') => return Token::new(closed, value),
+                Some('$') if self.peek_char() == Some('{') => {
+                    self.read_char();
+                    self.interpolations.push(0);
+                    return Token::new(interpolated, value);
+                }
+                Some('\\') => {
+                    if let Some(ch) = self.read_escape() {
+                        value.push(ch);
+                    }
+                }
+                Some(ch) => value.push(ch),
+            }
         }
-        self.input[position..self.position].iter().collect()
+    }
+
+    fn read_escape(&mut self) -> Option<char> {
+        let start = self.location;
+        self.read_char();
+        let ch = match self.examining_char {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('$') => '$',
+            Some('u') => return self.read_unicode_escape(start),
+            Some(ch) => {
+                self.errors.push(LexError::InvalidEscape(
+                    format!("\\{}", ch),
+                    Span::new(start, self.location_after()),
+                ));
+                return None;
+            }
+            // the caller reports the unterminated string.
+            None => return None,
+        };
+        Some(ch)
+    }
+
+    fn read_unicode_escape(&mut self, start: Position) -> Option<char> {
+        let mut escape = "\\u".to_string();
+        let mut digits = String::new();
+        if self.peek_char() == Some('{') {
+            self.read_char();
+            escape.push('{');
+            while let Some(ch) = self.peek_char().filter(char::is_ascii_hexdigit) {
+                self.read_char();
+                digits.push(ch);
+            }
+            escape.push_str(&digits);
+            if self.peek_char() == Some('}') {
+                self.read_char();
+                escape.push('}');
+            }
+        }
+
+        let ch = if escape.ends_with('}') && digits.len() <= 6 {
+            u32::from_str_radix(&digits, 16)
+                .ok()
+                .and_then(std::char::from_u32)
+        } else {
+            None
+        };
+        if ch.is_none() {
+            self.errors.push(LexError::InvalidEscape(
+                escape,
+                Span::new(start, self.location_after()),
+            ));
+        }
+        ch
     }
 
     fn read_identifier(&mut self) -> String {
@@ -105,6 +447,23 @@ impl Lexer {
     }
 }
 
+// TokenSource is where a Parser pulls its tokens from. the lexer is the usual source,
+// while a concrete syntax tree replays the tokens it already holds.
+pub trait TokenSource {
+    fn next_token(&mut self) -> Token;
+    fn take_errors(&mut self) -> Vec<LexError>;
+}
+
+impl TokenSource for Lexer {
+    fn next_token(&mut self) -> Token {
+        Lexer::next_token(self)
+    }
+
+    fn take_errors(&mut self) -> Vec<LexError> {
+        Lexer::take_errors(self)
+    }
+}
+
 fn is_letter(ch: char) -> bool {
     ch.is_ascii_alphabetic() || ch == '_'
 }
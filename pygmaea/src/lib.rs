@@ -1,7 +1,24 @@
 pub mod ast;
+pub mod builtins;
+pub mod convert;
+pub mod cst;
+pub mod environment;
 pub mod error;
+pub mod eval;
+pub mod fold;
+pub mod interpreter;
+pub mod json;
 pub mod lexer;
+pub mod limits;
+pub mod macro_expansion;
+pub mod modify;
+pub mod object;
 pub mod parser;
 pub mod precedence;
+pub mod serialize;
+pub mod sexp;
+pub mod span;
 pub mod token;
 pub mod token_type;
+pub mod visit;
+pub mod visit_mut;
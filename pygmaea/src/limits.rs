@@ -0,0 +1,74 @@
+use crate::error::RuntimeErrorKind;
+use std::time::{Duration, Instant};
+
+// Limits bounds the resources a single evaluation may use. every limit is off by
+// default. max_call_depth is also what keeps a runaway recursion from overflowing the
+// native stack, as the evaluator recurses on it once per call.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Limits {
+    pub max_steps: Option<usize>,
+    pub max_call_depth: Option<usize>,
+    pub max_objects: Option<usize>,
+    pub timeout: Option<Duration>,
+}
+
+// Budget tracks what an evaluation has used so far against its Limits.
+#[derive(Debug)]
+pub struct Budget {
+    limits: Limits,
+    steps: usize,
+    call_depth: usize,
+    objects: usize,
+    deadline: Option<Instant>,
+}
+
+impl Budget {
+    pub fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            steps: 0,
+            call_depth: 0,
+            objects: 0,
+            deadline: limits.timeout.map(|timeout| Instant::now() + timeout),
+        }
+    }
+
+    pub fn step(&mut self) -> Result<(), RuntimeErrorKind> {
+        self.steps += 1;
+        if let Some(max) = self.limits.max_steps {
+            if self.steps > max {
+                return Err(RuntimeErrorKind::StepLimitExceeded(max));
+            }
+        }
+        if let (Some(deadline), Some(timeout)) = (self.deadline, self.limits.timeout) {
+            if Instant::now() > deadline {
+                return Err(RuntimeErrorKind::DeadlineExceeded(timeout));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn enter_call(&mut self) -> Result<(), RuntimeErrorKind> {
+        if let Some(max) = self.limits.max_call_depth {
+            if self.call_depth >= max {
+                return Err(RuntimeErrorKind::CallDepthExceeded(max));
+            }
+        }
+        self.call_depth += 1;
+        Ok(())
+    }
+
+    pub fn exit_call(&mut self) {
+        self.call_depth -= 1;
+    }
+
+    pub fn allocate(&mut self) -> Result<(), RuntimeErrorKind> {
+        self.objects += 1;
+        if let Some(max) = self.limits.max_objects {
+            if self.objects > max {
+                return Err(RuntimeErrorKind::ObjectLimitExceeded(max));
+            }
+        }
+        Ok(())
+    }
+}
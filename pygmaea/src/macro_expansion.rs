@@ -0,0 +1,101 @@
+use crate::ast::*;
+use crate::environment::Environment;
+use crate::error::{Frame, RuntimeError, RuntimeErrorKind};
+use crate::eval::{eval_block_statement, finish};
+use crate::limits::{Budget, Limits};
+use crate::modify::modify_program;
+use crate::object::{Macro, Object};
+
+// define_macros removes the top level `let name = macro(...) { ... };` statements
+// from program and binds the macros they define in env instead.
+pub fn define_macros(program: &mut Program, env: &mut Environment) {
+    program.retain(|statement| {
+        let statement = match statement {
+            Statement::Let(statement) => statement,
+            _ => return true,
+        };
+        match *statement.expression {
+            Expression::Macro(ref macro_literal) => {
+                let definition = Macro::new(
+                    macro_literal.parameters.clone(),
+                    macro_literal.body.clone(),
+                    env.clone(),
+                );
+                env.set(
+                    statement.identifier.value.clone(),
+                    Object::Macro(definition),
+                );
+                false
+            }
+            _ => true,
+        }
+    });
+}
+
+// expand_macros replaces each call of a macro defined in env with the code the
+// macro returns when it is run on the quoted arguments of the call. errors raised
+// while expanding point at the call site.
+pub fn expand_macros(
+    program: Program,
+    env: &Environment,
+    limits: Limits,
+) -> Result<Program, Box<RuntimeError>> {
+    let mut budget = Budget::new(limits);
+    modify_program(program, &mut |expression| match expression {
+        Expression::Call(call) => match macro_of(&call, env) {
+            Some((name, definition)) => expand_macro_call(name, definition, call, &mut budget),
+            None => Ok(Expression::Call(call)),
+        },
+        expression => Ok(expression),
+    })
+}
+
+fn macro_of(call: &CallExpression, env: &Environment) -> Option<(String, Macro)> {
+    let identifier = match *call.function {
+        Expression::Identifier(ref identifier) => identifier,
+        _ => return None,
+    };
+    match env.get(&identifier.value) {
+        Some(Object::Macro(definition)) => Some((identifier.value.clone(), definition)),
+        _ => None,
+    }
+}
+
+fn expand_macro_call(
+    name: String,
+    definition: Macro,
+    call: CallExpression,
+    budget: &mut Budget,
+) -> Result<Expression, Box<RuntimeError>> {
+    if definition.parameters.len() != call.arguments.len() {
+        return Err(Box::new(RuntimeError::new(
+            RuntimeErrorKind::WrongNumberOfArguments(
+                definition.parameters.len(),
+                call.arguments.len(),
+            ),
+            call.span(),
+        )));
+    }
+
+    let mut macro_env = Environment::new_enclosed(&definition.env);
+    for (parameter, argument) in definition.parameters.iter().zip(call.arguments.iter()) {
+        macro_env.set(
+            parameter.value.clone(),
+            Object::Quote(Box::new(argument.clone())),
+        );
+    }
+
+    let result = eval_block_statement(&definition.body, &mut macro_env, budget);
+    macro_env.release();
+    match finish(result) {
+        Ok(Object::Quote(expression)) => Ok(*expression),
+        Ok(other) => Err(Box::new(RuntimeError::new(
+            RuntimeErrorKind::MacroResultNotQuote(other.type_name()),
+            call.span(),
+        ))),
+        Err(mut error) => {
+            error.push_frame(Frame::new(name, call.span()));
+            Err(error)
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::ast::*;
+use crate::fold::{self, Folder};
+
+// modify_program rebuilds program bottom-up: the children of each expression are
+// modified first, then modifier is handed the expression itself and returns its
+// replacement. the first error returned by modifier stops the modification.
+pub fn modify_program<E, F>(program: Program, modifier: &mut F) -> Result<Program, E>
+where
+    F: FnMut(Expression) -> Result<Expression, E>,
+{
+    Modifier::new(modifier).finish(|folder| folder.fold_program(program))
+}
+
+pub fn modify_statement<E, F>(statement: Statement, modifier: &mut F) -> Result<Statement, E>
+where
+    F: FnMut(Expression) -> Result<Expression, E>,
+{
+    Modifier::new(modifier).finish(|folder| folder.fold_statement(statement))
+}
+
+pub fn modify_block<E, F>(block: BlockStatement, modifier: &mut F) -> Result<BlockStatement, E>
+where
+    F: FnMut(Expression) -> Result<Expression, E>,
+{
+    Modifier::new(modifier).finish(|folder| folder.fold_block_statement(block))
+}
+
+pub fn modify_expression<E, F>(expression: Expression, modifier: &mut F) -> Result<Expression, E>
+where
+    F: FnMut(Expression) -> Result<Expression, E>,
+{
+    Modifier::new(modifier).finish(|folder| folder.fold_expression(expression))
+}
+
+struct Modifier<'a, E, F> {
+    modifier: &'a mut F,
+    error: Option<E>,
+}
+
+impl<'a, E, F> Modifier<'a, E, F>
+where
+    F: FnMut(Expression) -> Result<Expression, E>,
+{
+    fn new(modifier: &'a mut F) -> Self {
+        Self {
+            modifier,
+            error: None,
+        }
+    }
+
+    fn finish<T>(mut self, fold: impl FnOnce(&mut Self) -> T) -> Result<T, E> {
+        let folded = fold(&mut self);
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(folded),
+        }
+    }
+}
+
+impl<'a, E, F> Folder for Modifier<'a, E, F>
+where
+    F: FnMut(Expression) -> Result<Expression, E>,
+{
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        let expression = fold::walk_expression(self, expression);
+        if self.error.is_some() {
+            return expression;
+        }
+        match (self.modifier)(expression) {
+            Ok(expression) => expression,
+            Err(error) => {
+                self.error = Some(error);
+                // the whole tree is thrown away once an error is recorded, so any
+                // expression does as a stand-in for the one the modifier consumed.
+                Expression::Identifier(Identifier::new(Box::default()))
+            }
+        }
+    }
+}
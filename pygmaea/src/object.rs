@@ -0,0 +1,412 @@
+use crate::ast::{
+    ArrayLiteral, BlockStatement, Boolean, Expression, FloatLiteral, HashLiteral, Identifier,
+    IntegerLiteral, StringLiteral,
+};
+use crate::environment::Environment;
+use crate::error::{RuntimeError, RuntimeErrorKind};
+use crate::span::Span;
+use crate::token::Token;
+use crate::token_type::TokenType;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub enum Object {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+    Null,
+    Error(Box<RuntimeError>),
+    Function(Function),
+    Builtin(Builtin),
+    Array(Vec<Object>),
+    Hash(Hash),
+    Range(i64, i64),
+    Quote(Box<Expression>),
+    Macro(Macro),
+}
+
+impl Object {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Object::Integer(_) => "Integer",
+            Object::Float(_) => "Float",
+            Object::Boolean(_) => "Boolean",
+            Object::String(_) => "String",
+            Object::Null => "Null",
+            Object::Error(_) => "Error",
+            Object::Function(_) => "Function",
+            Object::Builtin(_) => "Builtin",
+            Object::Array(_) => "Array",
+            Object::Hash(_) => "Hash",
+            Object::Range(_, _) => "Range",
+            Object::Quote(_) => "Quote",
+            Object::Macro(_) => "Macro",
+        }
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Object::Null => false,
+            Object::Boolean(value) => *value,
+            _ => true,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, Object::Error(_))
+    }
+
+    // into_expression turns a value back into code which evaluates to it, so that
+    // unquote can splice values into a quote. the new tokens are located at span.
+    pub fn into_expression(self, span: Span) -> Result<Expression, RuntimeErrorKind> {
+        let token = |token_type, literal: &str| {
+            Box::new(Token::with_span(token_type, literal.to_string(), span))
+        };
+        match self {
+            Object::Quote(expression) => Ok(*expression),
+            Object::Integer(value) => Ok(Expression::Integer(IntegerLiteral {
+                token: token(TokenType::Int, &value.to_string()),
+                value,
+            })),
+            Object::Float(value) => Ok(Expression::Float(FloatLiteral {
+                token: token(TokenType::Float, &format!("{:?}", value)),
+                value,
+            })),
+            Object::Boolean(value) => {
+                let token_type = if value {
+                    TokenType::True
+                } else {
+                    TokenType::False
+                };
+                Ok(Expression::Boolean(Boolean::new(
+                    token(token_type, &value.to_string()),
+                    value,
+                )))
+            }
+            Object::String(value) => Ok(Expression::String(StringLiteral::new(token(
+                TokenType::String,
+                &value,
+            )))),
+            Object::Array(elements) => Ok(Expression::Array(ArrayLiteral::new(
+                token(TokenType::LBracket, "["),
+                elements
+                    .into_iter()
+                    .map(|element| element.into_expression(span))
+                    .collect::<Result<Vec<_>, _>>()?,
+                token(TokenType::RBracket, "]"),
+            ))),
+            Object::Hash(hash) => Ok(Expression::Hash(HashLiteral::new(
+                token(TokenType::LBrace, "{"),
+                hash.into_iter()
+                    .map(|(key, value)| {
+                        Ok((
+                            Object::from(key).into_expression(span)?,
+                            value.into_expression(span)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+                token(TokenType::RBrace, "}"),
+            ))),
+            other => Err(RuntimeErrorKind::UnquotableValue(other.type_name())),
+        }
+    }
+}
+
+impl std::fmt::Display for Object {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Object::Integer(value) => write!(f, "{}", value),
+            Object::Float(value) => write!(f, "{:?}", value),
+            Object::Boolean(value) => write!(f, "{}", value),
+            Object::String(value) => write!(f, "{}", value),
+            Object::Null => write!(f, "null"),
+            Object::Error(error) => write!(f, "ERROR: {}", error),
+            Object::Function(function) => write!(f, "{}", function),
+            Object::Builtin(builtin) => write!(f, "{}", builtin),
+            Object::Array(elements) => write!(
+                f,
+                "[{}]",
+                elements
+                    .iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Object::Hash(hash) => write!(f, "{}", hash),
+            Object::Range(start, end) => write!(f, "{}..{}", start, end),
+            Object::Quote(expression) => write!(f, "QUOTE({})", expression),
+            Object::Macro(definition) => write!(f, "{}", definition),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub parameters: Vec<Identifier>,
+    pub body: BlockStatement,
+    pub env: Environment,
+}
+
+impl Function {
+    pub fn new(parameters: Vec<Identifier>, body: BlockStatement, env: Environment) -> Self {
+        Self {
+            parameters,
+            body,
+            env,
+        }
+    }
+}
+
+impl std::fmt::Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "fn({}) {}",
+            self.parameters
+                .iter()
+                .map(|parameter| parameter.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.body
+        )
+    }
+}
+
+// Macro keeps the environment of the expansion pass it was defined in, just like
+// a function keeps the environment it was defined in.
+#[derive(Debug, Clone)]
+pub struct Macro {
+    pub parameters: Vec<Identifier>,
+    pub body: BlockStatement,
+    pub env: Environment,
+}
+
+impl Macro {
+    pub fn new(parameters: Vec<Identifier>, body: BlockStatement, env: Environment) -> Self {
+        Self {
+            parameters,
+            body,
+            env,
+        }
+    }
+}
+
+impl std::fmt::Display for Macro {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "macro({}) {}",
+            self.parameters
+                .iter()
+                .map(|parameter| parameter.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.body
+        )
+    }
+}
+
+pub type BuiltinFunction = fn(&[Object]) -> Result<Object, RuntimeErrorKind>;
+
+// builtins are stored as shared closures so that embedders can register functions
+// which capture their own state.
+pub type NativeFunction = Rc<dyn Fn(&[Object]) -> Result<Object, RuntimeErrorKind>>;
+
+#[derive(Clone)]
+pub struct Builtin {
+    pub name: String,
+    pub function: NativeFunction,
+}
+
+impl Builtin {
+    pub fn new<F>(name: impl Into<String>, function: F) -> Self
+    where
+        F: Fn(&[Object]) -> Result<Object, RuntimeErrorKind> + 'static,
+    {
+        Self {
+            name: name.into(),
+            function: Rc::new(function),
+        }
+    }
+}
+
+impl std::fmt::Debug for Builtin {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Builtin").field("name", &self.name).finish()
+    }
+}
+
+impl std::fmt::Display for Builtin {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "builtin function {}", self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    Integer(i64),
+    Boolean(bool),
+    String(String),
+}
+
+// HashKey turns a value into the key it is stored under in a hash. only integers,
+// booleans and strings are hashable.
+pub trait HashKey {
+    fn hash_key(&self) -> Result<Key, RuntimeErrorKind>;
+}
+
+impl HashKey for Object {
+    fn hash_key(&self) -> Result<Key, RuntimeErrorKind> {
+        match self {
+            Object::Integer(value) => Ok(Key::Integer(*value)),
+            Object::Boolean(value) => Ok(Key::Boolean(*value)),
+            Object::String(value) => Ok(Key::String(value.clone())),
+            other => Err(RuntimeErrorKind::UnhashableKey(other.type_name())),
+        }
+    }
+}
+
+impl From<Key> for Object {
+    fn from(key: Key) -> Self {
+        match key {
+            Key::Integer(value) => Object::Integer(value),
+            Key::Boolean(value) => Object::Boolean(value),
+            Key::String(value) => Object::String(value),
+        }
+    }
+}
+
+impl std::fmt::Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Key::Integer(value) => write!(f, "{}", value),
+            Key::Boolean(value) => write!(f, "{}", value),
+            Key::String(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+// Hash keeps its pairs in insertion order so that printing and iterating a hash
+// is reproducible.
+#[derive(Debug, Clone, Default)]
+pub struct Hash {
+    indices: HashMap<Key, usize>,
+    pairs: Vec<(Key, Object)>,
+}
+
+impl Hash {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&Object> {
+        self.indices.get(key).map(|&index| &self.pairs[index].1)
+    }
+
+    pub fn insert(&mut self, key: Key, value: Object) -> Option<Object> {
+        match self.indices.get(&key) {
+            Some(&index) => Some(std::mem::replace(&mut self.pairs[index].1, value)),
+            None => {
+                self.indices.insert(key.clone(), self.pairs.len());
+                self.pairs.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Key, Object)> {
+        self.pairs.iter()
+    }
+}
+
+impl IntoIterator for Hash {
+    type Item = (Key, Object);
+    type IntoIter = std::vec::IntoIter<(Key, Object)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.pairs.into_iter()
+    }
+}
+
+impl std::fmt::Display for Hash {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{{{}}}",
+            self.pairs
+                .iter()
+                .map(|(key, value)| format!("{}: {}", key, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+// Iterable turns a value into a lazy iterator over its (key, value) pairs: the
+// index and element for arrays, strings and ranges, and the key and value for
+// hashes.
+pub trait Iterable {
+    fn iterate(self) -> Result<ObjectIterator, RuntimeErrorKind>;
+}
+
+impl Iterable for Object {
+    fn iterate(self) -> Result<ObjectIterator, RuntimeErrorKind> {
+        match self {
+            Object::Array(elements) => Ok(ObjectIterator::Array(elements.into_iter().enumerate())),
+            Object::String(value) => Ok(ObjectIterator::String(value, 0, 0)),
+            Object::Hash(hash) => Ok(ObjectIterator::Hash(hash.into_iter())),
+            Object::Range(start, end) => Ok(ObjectIterator::Range(start, start..end)),
+            other => Err(RuntimeErrorKind::NotIterable(other.type_name())),
+        }
+    }
+}
+
+pub enum ObjectIterator {
+    Array(std::iter::Enumerate<std::vec::IntoIter<Object>>),
+    // the string, the byte offset of the next char and the index of the next char.
+    String(String, usize, i64),
+    Hash(std::vec::IntoIter<(Key, Object)>),
+    // the start of the range and the values left to yield.
+    Range(i64, std::ops::Range<i64>),
+}
+
+impl ObjectIterator {
+    // is_keyed reports whether a single loop variable is bound to the key of each
+    // pair rather than to its value, which is the case for hashes only.
+    pub fn is_keyed(&self) -> bool {
+        matches!(self, ObjectIterator::Hash(_))
+    }
+}
+
+impl Iterator for ObjectIterator {
+    type Item = (Object, Object);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            ObjectIterator::Array(elements) => elements
+                .next()
+                .map(|(index, element)| (Object::Integer(index as i64), element)),
+            ObjectIterator::String(value, offset, index) => {
+                let ch = value[*offset..].chars().next()?;
+                let item = (Object::Integer(*index), Object::String(ch.to_string()));
+                *offset += ch.len_utf8();
+                *index += 1;
+                Some(item)
+            }
+            ObjectIterator::Hash(pairs) => pairs.next().map(|(key, value)| (key.into(), value)),
+            ObjectIterator::Range(start, values) => values
+                .next()
+                .map(|value| (Object::Integer(value - *start), Object::Integer(value))),
+        }
+    }
+}
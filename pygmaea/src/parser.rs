@@ -1,23 +1,39 @@
 use crate::ast::*;
 use crate::error::*;
-use crate::lexer::Lexer;
+use crate::lexer::{Lexer, TokenSource};
 use crate::precedence::Precedence;
+use crate::span::Span;
 use crate::token::Token;
 use crate::token_type::TokenType;
 
-pub struct Parser {
-    lexer: Lexer,
+// DEFAULT_MAX_DEPTH bounds how deeply expressions, blocks and patterns may nest in the
+// source unless set_max_depth says otherwise. the parser, and the evaluator after it,
+// recurse on the native stack, so deeper input would overflow it and abort the process
+// instead of failing with an error. the default keeps both within the 2 MiB stack of a
+// spawned thread even in a debug build.
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+pub struct Parser<S = Lexer> {
+    lexer: S,
     current_token: Option<Box<Token>>,
     peek_token: Option<Box<Token>>,
+    // number of loops enclosing the current position within the current function.
+    loop_depth: usize,
+    // number of expressions, blocks and patterns enclosing the current position.
+    depth: usize,
+    max_depth: usize,
     pub errors: Vec<ParseError>,
 }
 
-impl Parser {
-    pub fn new(lexer: Lexer) -> Self {
+impl<S: TokenSource> Parser<S> {
+    pub fn new(lexer: S) -> Self {
         let mut parser = Self {
             lexer,
             current_token: Default::default(),
             peek_token: Default::default(),
+            loop_depth: 0,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
             errors: vec![],
         };
         parser.next_token();
@@ -25,9 +41,16 @@ impl Parser {
         parser
     }
 
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
     fn next_token(&mut self) {
         self.current_token = self.peek_token.take();
         self.peek_token = Some(Box::new(self.lexer.next_token()));
+        // the lexer recovers from malformed tokens by itself, so its errors are only reported.
+        let errors = self.lexer.take_errors();
+        self.errors.extend(errors.into_iter().map(ParseError::Lex));
     }
 
     pub fn parse_program(&mut self) -> Program {
@@ -37,80 +60,172 @@ impl Parser {
             .as_ref()
             .map_or(false, |token| !token.token_type.is_eof())
         {
-            if let Ok(statement) = self.parse_statement() {
+            if let Some(statement) = self.parse_statement_or_synchronize() {
                 program.push(statement);
             }
-            self.next_token();
         }
         program
     }
 
+    fn parse_statement_or_synchronize(&mut self) -> Option<Statement> {
+        let start = self.current_span();
+        match self.parse_statement() {
+            Ok(statement) => Some(statement),
+            Err(e) => {
+                self.report_error(e);
+                self.synchronize(start);
+                None
+            }
+        }
+    }
+
     fn parse_statement(&mut self) -> Result<Statement, ParseError> {
         match self.current_token {
             Some(ref token) if token.token_type == TokenType::Let => self.parse_let_statement(),
             Some(ref token) if token.token_type == TokenType::Return => {
                 self.parse_return_statement()
             }
+            Some(ref token) if token.token_type == TokenType::While => self.parse_while_statement(),
+            Some(ref token) if token.token_type == TokenType::For => self.parse_for_statement(),
+            Some(ref token)
+                if token.token_type == TokenType::Break
+                    || token.token_type == TokenType::Continue =>
+            {
+                self.parse_loop_control_statement()
+            }
             _ => self.parse_expression_statement(),
         }
     }
 
     fn parse_let_statement(&mut self) -> Result<Statement, ParseError> {
-        if !self.peek_token_is(TokenType::Ident) {
-            self.peek_error(TokenType::Ident);
-            return Err(ParseError::Statement(ParseStatementError::Let));
-        }
-
-        let let_token = self.current_token.take().unwrap();
-        self.next_token();
-
-        let identifier_token = self.current_token.take().ok_or_else(|| {
-            self.errors.push(ParseError::NoneToken);
-            ParseError::Statement(ParseStatementError::Let)
-        })?;
-        let identifier = Identifier::new(identifier_token);
-        self.next_token();
-
-        if !self.current_token_is(TokenType::Assign) {
-            self.peek_error(TokenType::Assign);
-            return Err(ParseError::Statement(ParseStatementError::Let));
-        }
-        self.next_token();
-        let expression = self.parse_expression(Precedence::Lowest).map_err(|e| {
-            self.errors.push(e);
-            ParseError::Statement(ParseStatementError::Let)
-        })?;
+        let let_token = self.expect_current_token(TokenType::Let)?;
+        let identifier = self.parse_identifier()?;
+        self.expect_current_token(TokenType::Assign)?;
+        let expression = self.parse_expression(Precedence::Lowest)?;
+        self.skip_semicolon();
         Ok(Statement::Let(LetStatement::new(
             let_token, identifier, expression,
         )))
     }
 
     fn parse_return_statement(&mut self) -> Result<Statement, ParseError> {
+        let token = self.expect_current_token(TokenType::Return)?;
+        let expression = self.parse_expression(Precedence::Lowest)?;
+        self.skip_semicolon();
+
+        Ok(Statement::Return(ReturnStatement::new(token, expression)))
+    }
+
+    fn parse_while_statement(&mut self) -> Result<Statement, ParseError> {
+        let token = self.expect_current_token(TokenType::While)?;
+        let paren = self.expect_current_token(TokenType::LParen)?;
+        let condition = self.parse_expression(Precedence::Lowest)?;
+        self.expect_closing_paren(&paren)?;
+
+        self.loop_depth += 1;
+        let body = self.parse_block_statement();
+        self.loop_depth -= 1;
+        let body = body?;
+        self.skip_semicolon();
+
+        Ok(Statement::While(WhileStatement::new(
+            token, condition, body,
+        )))
+    }
+
+    fn parse_for_statement(&mut self) -> Result<Statement, ParseError> {
+        let token = self.expect_current_token(TokenType::For)?;
+        let paren = self.expect_current_token(TokenType::LParen)?;
+        let key = Identifier::new(self.expect_current_token(TokenType::Ident)?);
+        let value = if self.current_token_is(TokenType::Comma) {
+            self.next_token();
+            Some(Identifier::new(
+                self.expect_current_token(TokenType::Ident)?,
+            ))
+        } else {
+            None
+        };
+        self.expect_current_token(TokenType::In)?;
+        let iterable = self.parse_expression(Precedence::Lowest)?;
+        self.expect_closing_paren(&paren)?;
+
+        self.loop_depth += 1;
+        let body = self.parse_block_statement();
+        self.loop_depth -= 1;
+        let body = body?;
+        self.skip_semicolon();
+
+        Ok(Statement::For(ForStatement::new(
+            token, key, value, iterable, body,
+        )))
+    }
+
+    fn parse_loop_control_statement(&mut self) -> Result<Statement, ParseError> {
         let token = self.current_token.take().unwrap();
         self.next_token();
+        if self.loop_depth == 0 {
+            return Err(ParseError::OutsideLoop(token));
+        }
+        self.skip_semicolon();
 
-        let expression = self.parse_expression(Precedence::Lowest).map_err(|e| {
-            self.errors.push(e);
-            ParseError::Statement(ParseStatementError::Return)
-        })?;
-
-        Ok(Statement::Return(ReturnStatement::new(token, expression)))
+        Ok(match token.token_type {
+            TokenType::Break => Statement::Break(BreakStatement::new(token)),
+            _ => Statement::Continue(ContinueStatement::new(token)),
+        })
     }
 
     fn parse_expression_statement(&mut self) -> Result<Statement, ParseError> {
-        let expression = self.parse_expression(Precedence::Lowest).map_err(|e| {
-            self.errors.push(e);
-            ParseError::Statement(ParseStatementError::Expression)
-        })?;
-        let statement = ExpressionStatement::new(expression);
-        if self.peek_token_is(TokenType::Semicolon) {
-            self.next_token();
+        let expression = self.parse_expression(Precedence::Lowest)?;
+        if self
+            .current_token
+            .as_ref()
+            .is_some_and(|token| is_assign_operator(token.token_type))
+        {
+            return self.parse_assign_statement(*expression);
         }
+        let statement = ExpressionStatement::new(expression);
+        self.skip_semicolon();
         Ok(Statement::Expression(statement))
     }
 
+    fn parse_assign_statement(&mut self, target: Expression) -> Result<Statement, ParseError> {
+        let target = match target {
+            Expression::Identifier(identifier) => AssignTarget::Identifier(identifier),
+            Expression::Index(index) if is_assignable(&index.left) => AssignTarget::Index(index),
+            other => return Err(ParseError::InvalidAssignmentTarget(other.span())),
+        };
+        let token = self.current_token.take().unwrap();
+        self.next_token();
+        let value = self.parse_expression(Precedence::Lowest)?;
+        self.skip_semicolon();
+
+        Ok(Statement::Assign(AssignStatement::new(
+            token, target, value,
+        )))
+    }
+
     fn parse_expression(&mut self, precedence: Precedence) -> Result<Box<Expression>, ParseError> {
-        let token = self.current_token.take().ok_or(ParseError::NoneToken)?;
+        self.enter_nested()?;
+        let expression = self.parse_nested_expression(precedence);
+        self.depth -= 1;
+        expression
+    }
+
+    fn parse_nested_expression(
+        &mut self,
+        precedence: Precedence,
+    ) -> Result<Box<Expression>, ParseError> {
+        let token = self
+            .current_token
+            .take()
+            .ok_or_else(|| self.none_token_error())?;
+        // leave the offending token in place so that synchronization can start from it.
+        if !has_prefix_parse(token.token_type) {
+            self.current_token = Some(token.clone());
+            return Err(ParseError::Expression(ParseExpressionError::NoPrefix(
+                token,
+            )));
+        }
         self.next_token();
 
         let mut expression = self.parse_prefix_expression(token)?;
@@ -130,56 +245,83 @@ impl Parser {
 
             self.next_token();
 
-            expression = self
-                .parse_infix_expression(expression, token, precedence)
-                .map_err(|e| {
-                    self.errors.push(e);
-                    ParseError::Expression(ParseExpressionError::Infix)
-                })?;
+            expression = self.parse_infix_expression(expression, token, precedence)?;
         }
 
         Ok(expression)
     }
 
+    // the literal and operator arms live in their own functions so that their temporaries
+    // do not grow the frame that every nesting level of the source goes through.
     fn parse_prefix_expression(
         &mut self,
         token: Box<Token>,
     ) -> Result<Box<Expression>, ParseError> {
+        match token.token_type {
+            TokenType::Bang | TokenType::Minus => self.parse_prefix_operator(token),
+            TokenType::LParen => self.parse_grouped_expression(token),
+            TokenType::If => self.parse_if_expression(token),
+            TokenType::Function | TokenType::Macro => self.parse_function_literal(token),
+            TokenType::StringStart => self.parse_string_interpolation(token),
+            TokenType::LBracket => self.parse_array_literal(token),
+            TokenType::Match => self.parse_match_expression(token),
+            // blocks are only parsed where a statement list is expected, so a brace in
+            // expression position always opens a hash literal.
+            TokenType::LBrace => self.parse_hash_literal(token),
+            _ => self.parse_literal(token),
+        }
+    }
+
+    fn parse_literal(&mut self, token: Box<Token>) -> Result<Box<Expression>, ParseError> {
         match token.token_type {
             TokenType::Ident => {
                 let expression = Expression::Identifier(Identifier::new(token));
                 Ok(Box::new(expression))
             }
             TokenType::Int => {
-                let expression = Expression::Integer(IntegerLiteral::new(token));
-                Ok(Box::new(expression))
+                let integer_literal = IntegerLiteral::new(token).map_err(ParseError::Expression)?;
+                Ok(Box::new(Expression::Integer(integer_literal)))
             }
-            TokenType::Bang | TokenType::Minus => {
-                let expression = self.parse_expression(Precedence::Prefix).map_err(|e| {
-                    self.errors.push(e);
-                    ParseError::Expression(ParseExpressionError::Prefix)
-                })?;
-                Ok(Box::new(Expression::Prefix(PrefixExpression::new(
-                    token, expression,
-                ))))
+            TokenType::Float => {
+                let float_literal = FloatLiteral::new(token).map_err(ParseError::Expression)?;
+                Ok(Box::new(Expression::Float(float_literal)))
             }
             TokenType::True | TokenType::False => Ok(Box::new(self.parse_boolean(token))),
+            TokenType::String => Ok(Box::new(Expression::String(StringLiteral::new(token)))),
             _ => Err(ParseError::Expression(ParseExpressionError::NoPrefix(
                 token,
             ))),
         }
     }
 
+    fn parse_prefix_operator(&mut self, token: Box<Token>) -> Result<Box<Expression>, ParseError> {
+        let expression = self.parse_expression(Precedence::Prefix)?;
+        Ok(Box::new(Expression::Prefix(PrefixExpression::new(
+            token, expression,
+        ))))
+    }
+
     fn parse_infix_expression(
         &mut self,
         left_expression: Box<Expression>,
         token: Box<Token>,
         precedence: Precedence,
     ) -> Result<Box<Expression>, ParseError> {
-        let right_expresion = self.parse_expression(precedence).map_err(|e| {
-            self.errors.push(e);
-            ParseError::Expression(ParseExpressionError::Infix)
-        })?;
+        match token.token_type {
+            TokenType::LParen => return self.parse_call_expression(left_expression, token),
+            TokenType::LBracket => return self.parse_index_expression(left_expression, token),
+            TokenType::DotDot => {
+                let end = self.parse_expression(precedence)?;
+                return Ok(Box::new(Expression::Range(RangeExpression::new(
+                    token,
+                    left_expression,
+                    end,
+                ))));
+            }
+            _ => {}
+        }
+
+        let right_expresion = self.parse_expression(precedence)?;
 
         Ok(Box::new(Expression::Infix(InfixExpression::new(
             token,
@@ -192,24 +334,518 @@ impl Parser {
         let value = token.token_type == TokenType::True;
         Expression::Boolean(Boolean::new(token, value))
     }
+
+    fn parse_grouped_expression(
+        &mut self,
+        token: Box<Token>,
+    ) -> Result<Box<Expression>, ParseError> {
+        let expression = self.parse_expression(Precedence::Lowest)?;
+        self.expect_closing_paren(&token)?;
+        Ok(expression)
+    }
+
+    fn parse_if_expression(&mut self, token: Box<Token>) -> Result<Box<Expression>, ParseError> {
+        let paren = self.expect_current_token(TokenType::LParen)?;
+        let condition = self.parse_expression(Precedence::Lowest)?;
+        self.expect_closing_paren(&paren)?;
+        let consequence = self.parse_block_statement()?;
+
+        let alternative = if self.current_token_is(TokenType::Else) {
+            self.next_token();
+            let alternative = self.parse_block_statement()?;
+            Some(alternative)
+        } else {
+            None
+        };
+
+        Ok(Box::new(Expression::If(IfExpression::new(
+            token,
+            condition,
+            consequence,
+            alternative,
+        ))))
+    }
+
+    fn parse_function_literal(&mut self, token: Box<Token>) -> Result<Box<Expression>, ParseError> {
+        let parameters = self.parse_function_parameters()?;
+        // a loop outside of the function cannot be left from inside its body.
+        let loop_depth = std::mem::replace(&mut self.loop_depth, 0);
+        let body = self.parse_block_statement();
+        self.loop_depth = loop_depth;
+        let body = body?;
+
+        Ok(Box::new(match token.token_type {
+            TokenType::Macro => Expression::Macro(MacroLiteral::new(token, parameters, body)),
+            _ => Expression::Function(FunctionLiteral::new(token, parameters, body)),
+        }))
+    }
+
+    fn parse_function_parameters(&mut self) -> Result<Vec<Identifier>, ParseError> {
+        let paren = self.expect_current_token(TokenType::LParen)?;
+
+        let mut parameters = vec![];
+        if self.current_token_is(TokenType::RParen) {
+            self.next_token();
+            return Ok(parameters);
+        }
+
+        parameters.push(self.parse_identifier()?);
+        while self.current_token_is(TokenType::Comma) {
+            self.next_token();
+            parameters.push(self.parse_identifier()?);
+        }
+
+        self.expect_closing_paren(&paren)?;
+        Ok(parameters)
+    }
+
+    fn parse_identifier(&mut self) -> Result<Identifier, ParseError> {
+        let token = self.expect_current_token(TokenType::Ident)?;
+        Ok(Identifier::new(token))
+    }
+
+    fn parse_call_expression(
+        &mut self,
+        function: Box<Expression>,
+        token: Box<Token>,
+    ) -> Result<Box<Expression>, ParseError> {
+        let (arguments, closing_token) = self.parse_expression_list(&token, TokenType::RParen)?;
+
+        Ok(Box::new(Expression::Call(CallExpression::new(
+            token,
+            function,
+            arguments,
+            closing_token,
+        ))))
+    }
+
+    fn parse_array_literal(&mut self, token: Box<Token>) -> Result<Box<Expression>, ParseError> {
+        let (elements, closing_token) = self.parse_expression_list(&token, TokenType::RBracket)?;
+
+        Ok(Box::new(Expression::Array(ArrayLiteral::new(
+            token,
+            elements,
+            closing_token,
+        ))))
+    }
+
+    fn parse_hash_literal(&mut self, token: Box<Token>) -> Result<Box<Expression>, ParseError> {
+        let mut pairs = vec![];
+        while !self.current_token_is(TokenType::RBrace) {
+            let key = self.parse_expression(Precedence::Lowest)?;
+            self.expect_current_token(TokenType::Colon)?;
+            let value = self.parse_expression(Precedence::Lowest)?;
+            pairs.push((*key, *value));
+
+            if !self.current_token_is(TokenType::RBrace) {
+                self.expect_current_token(TokenType::Comma)?;
+            }
+        }
+        let closing_token = self.expect_current_token(TokenType::RBrace)?;
+
+        Ok(Box::new(Expression::Hash(HashLiteral::new(
+            token,
+            pairs,
+            closing_token,
+        ))))
+    }
+
+    fn parse_match_expression(&mut self, token: Box<Token>) -> Result<Box<Expression>, ParseError> {
+        let paren = self.expect_current_token(TokenType::LParen)?;
+        let subject = self.parse_expression(Precedence::Lowest)?;
+        self.expect_closing_paren(&paren)?;
+        self.expect_current_token(TokenType::LBrace)?;
+
+        let mut arms = vec![];
+        while !self.current_token_is(TokenType::RBrace) {
+            let pattern = self.parse_pattern()?;
+            let guard = if self.current_token_is(TokenType::If) {
+                self.next_token();
+                Some(self.parse_expression(Precedence::Lowest)?)
+            } else {
+                None
+            };
+            self.expect_current_token(TokenType::FatArrow)?;
+            let body = self.parse_expression(Precedence::Lowest)?;
+            arms.push(MatchArm::new(pattern, guard, body));
+
+            if !self.current_token_is(TokenType::RBrace) {
+                self.expect_current_token(TokenType::Comma)?;
+            }
+        }
+        let closing_token = self.expect_current_token(TokenType::RBrace)?;
+
+        Ok(Box::new(Expression::Match(MatchExpression::new(
+            token,
+            subject,
+            arms,
+            closing_token,
+        ))))
+    }
+
+    fn parse_pattern(&mut self) -> Result<Pattern, ParseError> {
+        self.enter_nested()?;
+        let pattern = self.parse_nested_pattern();
+        self.depth -= 1;
+        pattern
+    }
+
+    fn parse_nested_pattern(&mut self) -> Result<Pattern, ParseError> {
+        let token = self
+            .current_token
+            .take()
+            .ok_or_else(|| self.none_token_error())?;
+        match token.token_type {
+            TokenType::Ident if token.literal == "_" => {
+                self.next_token();
+                Ok(Pattern::Wildcard(token))
+            }
+            TokenType::Ident => {
+                self.next_token();
+                Ok(Pattern::Identifier(Identifier::new(token)))
+            }
+            TokenType::LBracket => {
+                self.next_token();
+                self.parse_array_pattern(token)
+            }
+            TokenType::LBrace => {
+                self.next_token();
+                self.parse_hash_pattern(token)
+            }
+            _ => {
+                self.current_token = Some(token);
+                Ok(Pattern::Literal(self.parse_literal_pattern()?))
+            }
+        }
+    }
+
+    // literal patterns are numbers, optionally negated, strings and booleans.
+    fn parse_literal_pattern(&mut self) -> Result<Box<Expression>, ParseError> {
+        let token_type = self.current_token.as_ref().map(|token| token.token_type);
+        let negated_number = token_type == Some(TokenType::Minus)
+            && self
+                .peek_token
+                .as_ref()
+                .is_some_and(|token| token.token_type.is_number());
+        match token_type {
+            Some(TokenType::Int)
+            | Some(TokenType::Float)
+            | Some(TokenType::String)
+            | Some(TokenType::True)
+            | Some(TokenType::False) => self.parse_expression(Precedence::Prefix),
+            Some(TokenType::Minus) if negated_number => self.parse_expression(Precedence::Prefix),
+            Some(_) => Err(ParseError::InvalidPattern(
+                self.current_token.clone().unwrap(),
+            )),
+            None => Err(self.none_token_error()),
+        }
+    }
+
+    fn parse_array_pattern(&mut self, token: Box<Token>) -> Result<Pattern, ParseError> {
+        let mut elements = vec![];
+        let mut rest = None;
+        while !self.current_token_is(TokenType::RBracket) {
+            if self.current_token_is(TokenType::Ellipsis) {
+                self.next_token();
+                rest = Some(Identifier::new(
+                    self.expect_current_token(TokenType::Ident)?,
+                ));
+                break;
+            }
+            elements.push(self.parse_pattern()?);
+
+            if !self.current_token_is(TokenType::RBracket) {
+                self.expect_current_token(TokenType::Comma)?;
+            }
+        }
+        let closing_token = self.expect_closing(&token, TokenType::RBracket)?;
+
+        Ok(Pattern::Array(ArrayPattern::new(
+            token,
+            elements,
+            rest,
+            closing_token,
+        )))
+    }
+
+    fn parse_hash_pattern(&mut self, token: Box<Token>) -> Result<Pattern, ParseError> {
+        let mut pairs = vec![];
+        while !self.current_token_is(TokenType::RBrace) {
+            let key = self.parse_literal_pattern()?;
+            self.expect_current_token(TokenType::Colon)?;
+            let value = self.parse_pattern()?;
+            pairs.push((*key, value));
+
+            if !self.current_token_is(TokenType::RBrace) {
+                self.expect_current_token(TokenType::Comma)?;
+            }
+        }
+        let closing_token = self.expect_current_token(TokenType::RBrace)?;
+
+        Ok(Pattern::Hash(HashPattern::new(token, pairs, closing_token)))
+    }
+
+    fn parse_index_expression(
+        &mut self,
+        left: Box<Expression>,
+        token: Box<Token>,
+    ) -> Result<Box<Expression>, ParseError> {
+        let index = self.parse_expression(Precedence::Lowest)?;
+        let closing_token = self.expect_closing(&token, TokenType::RBracket)?;
+
+        Ok(Box::new(Expression::Index(IndexExpression::new(
+            token,
+            left,
+            index,
+            closing_token,
+        ))))
+    }
+
+    // parses comma separated expressions up to the closing token, as in call arguments
+    // and array elements.
+    fn parse_expression_list(
+        &mut self,
+        opening: &Token,
+        closing: TokenType,
+    ) -> Result<(Vec<Expression>, Box<Token>), ParseError> {
+        let mut expressions = vec![];
+        if self.current_token_is(closing) {
+            let closing_token = self.current_token.take().unwrap();
+            self.next_token();
+            return Ok((expressions, closing_token));
+        }
+
+        expressions.push(*self.parse_expression(Precedence::Lowest)?);
+        while self.current_token_is(TokenType::Comma) {
+            self.next_token();
+            expressions.push(*self.parse_expression(Precedence::Lowest)?);
+        }
+
+        let closing_token = self.expect_closing(opening, closing)?;
+        Ok((expressions, closing_token))
+    }
+
+    fn parse_string_interpolation(
+        &mut self,
+        token: Box<Token>,
+    ) -> Result<Box<Expression>, ParseError> {
+        let mut parts = vec![];
+        if !token.literal.is_empty() {
+            parts.push(StringPart::Text(token.literal.clone()));
+        }
+
+        let closing_token = loop {
+            let expression = self.parse_expression(Precedence::Lowest)?;
+            parts.push(StringPart::Expression(*expression));
+
+            let is_end = self.current_token_is(TokenType::StringEnd);
+            if !is_end && !self.current_token_is(TokenType::StringMiddle) {
+                return Err(self.unexpected_token_error(TokenType::StringEnd));
+            }
+            let text = self.current_token.take().unwrap();
+            self.next_token();
+            if !text.literal.is_empty() {
+                parts.push(StringPart::Text(text.literal.clone()));
+            }
+            if is_end {
+                break text;
+            }
+        };
+
+        Ok(Box::new(Expression::StringInterpolation(
+            StringInterpolation::new(token, parts, closing_token),
+        )))
+    }
+
+    fn parse_block_statement(&mut self) -> Result<BlockStatement, ParseError> {
+        self.enter_nested()?;
+        let block = self.parse_nested_block_statement();
+        self.depth -= 1;
+        block
+    }
+
+    fn parse_nested_block_statement(&mut self) -> Result<BlockStatement, ParseError> {
+        let token = self.expect_current_token(TokenType::LBrace)?;
+
+        let mut statements = Program::new();
+        while !self.current_token_is(TokenType::RBrace) && !self.current_token_is(TokenType::EOF) {
+            if let Some(statement) = self.parse_statement_or_synchronize() {
+                statements.push(statement);
+            }
+        }
+
+        let closing_token = self.expect_current_token(TokenType::RBrace)?;
+        Ok(BlockStatement::new(token, statements, closing_token))
+    }
 }
 
 // utility functions
-impl Parser {
+impl<S: TokenSource> Parser<S> {
     fn current_token_is(&self, token_type: TokenType) -> bool {
         self.current_token
             .as_ref()
             .map_or(false, |token| token.token_type == token_type)
     }
 
-    fn peek_token_is(&mut self, token_type: TokenType) -> bool {
-        self.peek_token
+    fn current_span(&self) -> Span {
+        self.current_token
             .as_ref()
-            .map_or(false, |token| token.token_type == token_type)
+            .map_or_else(Span::default, |token| token.span)
+    }
+
+    fn expect_current_token(&mut self, token_type: TokenType) -> Result<Box<Token>, ParseError> {
+        if !self.current_token_is(token_type) {
+            return Err(self.unexpected_token_error(token_type));
+        }
+        let token = self.current_token.take().unwrap();
+        self.next_token();
+        Ok(token)
     }
 
-    fn peek_error(&mut self, token_type: TokenType) {
-        self.errors
-            .push(ParseError::PeekToken(token_type, self.peek_token.take()))
+    fn expect_closing_paren(&mut self, paren: &Token) -> Result<Box<Token>, ParseError> {
+        self.expect_closing(paren, TokenType::RParen)
+    }
+
+    fn expect_closing(
+        &mut self,
+        opening: &Token,
+        closing: TokenType,
+    ) -> Result<Box<Token>, ParseError> {
+        if !self.current_token_is(closing) {
+            return Err(match self.current_token {
+                Some(ref token) if closing == TokenType::RBracket => ParseError::Expression(
+                    ParseExpressionError::UnclosedBracket(Box::new(opening.clone()), token.clone()),
+                ),
+                Some(ref token) => ParseError::Expression(ParseExpressionError::UnclosedParen(
+                    Box::new(opening.clone()),
+                    token.clone(),
+                )),
+                None => self.none_token_error(),
+            });
+        }
+        let token = self.current_token.take().unwrap();
+        self.next_token();
+        Ok(token)
+    }
+
+    // enter_nested goes one level deeper into the source. past max_depth the rest of
+    // the input is skipped, since recovering within it would only fail again on every
+    // construct nested as deeply.
+    fn enter_nested(&mut self) -> Result<(), ParseError> {
+        if self.depth < self.max_depth {
+            self.depth += 1;
+            return Ok(());
+        }
+        let span = self.current_span();
+        while !self.current_token_is(TokenType::EOF) && self.current_token.is_some() {
+            self.next_token();
+        }
+        Err(ParseError::NestingDepthExceeded(span, self.max_depth))
+    }
+
+    fn skip_semicolon(&mut self) {
+        if self.current_token_is(TokenType::Semicolon) {
+            self.next_token();
+        }
+    }
+
+    fn report_error(&mut self, error: ParseError) {
+        // an error on the same token as the previous one is a cascade of it, and so is
+        // any error after the input was skipped for nesting too deeply.
+        match self.errors.last() {
+            Some(ParseError::NestingDepthExceeded(..)) => return,
+            Some(last) if last.span() == error.span() => return,
+            _ => {}
+        }
+        self.errors.push(error);
+    }
+
+    // skips tokens until the start of the next statement. braces opened while skipping
+    // are skipped as a whole so that their closing brace does not start a new error.
+    fn synchronize(&mut self, start: Span) {
+        let mut depth = 0;
+        let mut progressed = self.current_span() != start;
+        loop {
+            match self.current_token.as_ref().map(|token| token.token_type) {
+                None | Some(TokenType::EOF) => return,
+                Some(TokenType::Semicolon) if depth == 0 => {
+                    self.next_token();
+                    return;
+                }
+                Some(TokenType::RBrace)
+                | Some(TokenType::Let)
+                | Some(TokenType::Return)
+                | Some(TokenType::While)
+                | Some(TokenType::For)
+                | Some(TokenType::Break)
+                | Some(TokenType::Continue)
+                    if depth == 0 && progressed =>
+                {
+                    return
+                }
+                Some(TokenType::LBrace) => depth += 1,
+                Some(TokenType::RBrace) if depth > 0 => depth -= 1,
+                Some(_) => {}
+            }
+            self.next_token();
+            progressed = true;
+        }
+    }
+
+    fn unexpected_token_error(&self, token_type: TokenType) -> ParseError {
+        match self.current_token {
+            Some(ref token) => ParseError::PeekToken(token_type, token.clone()),
+            None => self.none_token_error(),
+        }
+    }
+
+    fn none_token_error(&self) -> ParseError {
+        ParseError::NoneToken(
+            self.peek_token
+                .as_ref()
+                .map_or_else(Span::default, |token| token.span),
+        )
+    }
+}
+
+fn has_prefix_parse(token_type: TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Ident
+            | TokenType::Int
+            | TokenType::Float
+            | TokenType::Bang
+            | TokenType::Minus
+            | TokenType::True
+            | TokenType::False
+            | TokenType::LParen
+            | TokenType::If
+            | TokenType::Function
+            | TokenType::Macro
+            | TokenType::String
+            | TokenType::StringStart
+            | TokenType::LBracket
+            | TokenType::LBrace
+            | TokenType::Match
+    )
+}
+
+fn is_assign_operator(token_type: TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Assign
+            | TokenType::PlusAssign
+            | TokenType::MinusAssign
+            | TokenType::AsteriskAssign
+            | TokenType::SlashAssign
+    )
+}
+
+// only a variable, or an element reached from one through indices, can be assigned to.
+fn is_assignable(expression: &Expression) -> bool {
+    match expression {
+        Expression::Identifier(_) => true,
+        Expression::Index(index) => is_assignable(&index.left),
+        _ => false,
     }
 }
@@ -4,12 +4,16 @@ use std::cmp::Ordering;
 #[derive(Eq)]
 pub enum Precedence {
     Lowest,
+    LogicalOr,
+    LogicalAnd,
     Equals,
     LessGreater,
+    Range,
     Sum,
     Product,
     Prefix,
     Call,
+    Index,
 }
 
 impl Precedence {
@@ -19,10 +23,18 @@ impl Precedence {
             TokenType::NotEqual => Some(Precedence::Equals),
             TokenType::LessThan => Some(Precedence::LessGreater),
             TokenType::GreaterThan => Some(Precedence::LessGreater),
+            TokenType::LessThanOrEqual => Some(Precedence::LessGreater),
+            TokenType::GreaterThanOrEqual => Some(Precedence::LessGreater),
+            TokenType::DotDot => Some(Precedence::Range),
+            TokenType::And => Some(Precedence::LogicalAnd),
+            TokenType::Or => Some(Precedence::LogicalOr),
             TokenType::Plus => Some(Precedence::Sum),
             TokenType::Minus => Some(Precedence::Sum),
             TokenType::Slash => Some(Precedence::Product),
             TokenType::Asterisk => Some(Precedence::Product),
+            TokenType::Percent => Some(Precedence::Product),
+            TokenType::LParen => Some(Precedence::Call),
+            TokenType::LBracket => Some(Precedence::Index),
             _ => None,
         }
     }
@@ -30,12 +42,16 @@ impl Precedence {
     fn priority(&self) -> u32 {
         match self {
             Precedence::Lowest => 0,
-            Precedence::Equals => 1,
-            Precedence::LessGreater => 2,
-            Precedence::Sum => 3,
-            Precedence::Product => 4,
-            Precedence::Prefix => 5,
-            Precedence::Call => 6,
+            Precedence::LogicalOr => 1,
+            Precedence::LogicalAnd => 2,
+            Precedence::Equals => 3,
+            Precedence::LessGreater => 4,
+            Precedence::Range => 5,
+            Precedence::Sum => 6,
+            Precedence::Product => 7,
+            Precedence::Prefix => 8,
+            Precedence::Call => 9,
+            Precedence::Index => 10,
         }
     }
 }
@@ -0,0 +1,1397 @@
+use crate::ast::*;
+use crate::error::DeserializeError;
+use crate::json;
+use crate::parser::DEFAULT_MAX_DEPTH;
+use crate::sexp;
+use crate::span::{Position, Span};
+use crate::token::Token;
+use crate::token_type::TokenType;
+
+// Value is the format-independent shape of a serialized AST. every node becomes a
+// Node carrying its kind and its fields in declaration order, so json and sexp only
+// have to agree on how to spell a Value. field names are empty when read from an
+// S-expression, which stores fields by position instead.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Symbol(String),
+    List(Vec<Value>),
+    Node(String, Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn node(kind: &str, fields: Vec<(&str, Value)>) -> Self {
+        Value::Node(
+            kind.to_string(),
+            fields
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        )
+    }
+
+    pub fn kind(&self) -> Option<&str> {
+        match self {
+            Value::Node(kind, _) => Some(kind),
+            _ => None,
+        }
+    }
+
+    pub fn type_name(&self) -> &str {
+        match self {
+            Value::Null => "Null",
+            Value::Boolean(_) => "Boolean",
+            Value::Integer(_) => "Integer",
+            Value::Float(_) => "Float",
+            Value::String(_) => "String",
+            Value::Symbol(_) => "Symbol",
+            Value::List(_) => "List",
+            Value::Node(kind, _) => kind,
+        }
+    }
+
+    fn unexpected(&self, want: &'static str) -> DeserializeError {
+        DeserializeError::UnexpectedValue(want, self.type_name().to_string())
+    }
+}
+
+pub trait ToValue {
+    fn to_value(&self) -> Value;
+}
+
+pub trait FromValue: Sized {
+    fn from_value(value: Value) -> Result<Self, DeserializeError>;
+}
+
+pub fn to_json(program: &[Statement]) -> String {
+    json::to_string(&program.to_value())
+}
+
+pub fn from_json(input: &str) -> Result<Program, DeserializeError> {
+    Program::from_value(json::from_str(input)?)
+}
+
+pub fn to_sexp(program: &[Statement]) -> String {
+    sexp::to_string(&program.to_value())
+}
+
+pub fn from_sexp(input: &str) -> Result<Program, DeserializeError> {
+    Program::from_value(sexp::from_str(input)?)
+}
+
+// Fields hands out the fields of a node by name, or by position when the node was
+// read without names.
+struct Fields {
+    kind: String,
+    fields: Vec<(String, Value)>,
+    next: usize,
+}
+
+impl Fields {
+    fn of(value: Value, kind: &'static str) -> Result<Self, DeserializeError> {
+        match value {
+            Value::Node(actual, fields) if actual == kind => Ok(Self {
+                kind: actual,
+                fields,
+                next: 0,
+            }),
+            other => Err(other.unexpected(kind)),
+        }
+    }
+
+    fn take<T: FromValue>(&mut self, name: &'static str) -> Result<T, DeserializeError> {
+        let index = match self.fields.iter().position(|(field, _)| field == name) {
+            Some(index) => Some(index),
+            None if self.next < self.fields.len() && self.fields[self.next].0.is_empty() => {
+                self.next += 1;
+                Some(self.next - 1)
+            }
+            None => None,
+        };
+        match index {
+            Some(index) => T::from_value(std::mem::replace(&mut self.fields[index].1, Value::Null)),
+            None => Err(DeserializeError::MissingField(name, self.kind.clone())),
+        }
+    }
+}
+
+impl ToValue for bool {
+    fn to_value(&self) -> Value {
+        Value::Boolean(*self)
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            Value::Boolean(value) => Ok(value),
+            other => Err(other.unexpected("Boolean")),
+        }
+    }
+}
+
+impl ToValue for i64 {
+    fn to_value(&self) -> Value {
+        Value::Integer(*self)
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            Value::Integer(value) => Ok(value),
+            other => Err(other.unexpected("Integer")),
+        }
+    }
+}
+
+impl ToValue for usize {
+    fn to_value(&self) -> Value {
+        Value::Integer(*self as i64)
+    }
+}
+
+impl FromValue for usize {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            Value::Integer(value) if value >= 0 => Ok(value as usize),
+            other => Err(other.unexpected("Integer")),
+        }
+    }
+}
+
+impl ToValue for f64 {
+    fn to_value(&self) -> Value {
+        Value::Float(*self)
+    }
+}
+
+impl FromValue for f64 {
+    // infinities and NaN have no number literal in either format, so they are
+    // written as strings and parsed back here.
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            Value::Float(value) => Ok(value),
+            Value::Integer(value) => Ok(value as f64),
+            Value::String(value) | Value::Symbol(value) => value
+                .parse()
+                .map_err(|_| DeserializeError::UnexpectedValue("Float", value)),
+            other => Err(other.unexpected("Float")),
+        }
+    }
+}
+
+impl ToValue for String {
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            Value::String(value) => Ok(value),
+            other => Err(other.unexpected("String")),
+        }
+    }
+}
+
+impl<T: ToValue + ?Sized> ToValue for Box<T> {
+    fn to_value(&self) -> Value {
+        self.as_ref().to_value()
+    }
+}
+
+impl<T: FromValue> FromValue for Box<T> {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        Ok(Box::new(T::from_value(value)?))
+    }
+}
+
+impl<T: ToValue> ToValue for Option<T> {
+    fn to_value(&self) -> Value {
+        match self {
+            Some(value) => value.to_value(),
+            None => Value::Null,
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+}
+
+impl<T: ToValue> ToValue for [T] {
+    fn to_value(&self) -> Value {
+        Value::List(self.iter().map(ToValue::to_value).collect())
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            // a plain loop rather than collect, which nests several more frames between
+            // an element and its list in a debug build.
+            Value::List(values) => {
+                let mut elements = Vec::with_capacity(values.len());
+                for value in values {
+                    elements.push(T::from_value(value)?);
+                }
+                Ok(elements)
+            }
+            other => Err(other.unexpected("List")),
+        }
+    }
+}
+
+impl<A: ToValue, B: ToValue> ToValue for (A, B) {
+    fn to_value(&self) -> Value {
+        Value::List(vec![self.0.to_value(), self.1.to_value()])
+    }
+}
+
+impl<A: FromValue, B: FromValue> FromValue for (A, B) {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            Value::List(values) if values.len() == 2 => {
+                let mut values = values.into_iter();
+                let first = A::from_value(values.next().unwrap())?;
+                let second = B::from_value(values.next().unwrap())?;
+                Ok((first, second))
+            }
+            other => Err(other.unexpected("Pair")),
+        }
+    }
+}
+
+impl ToValue for TokenType {
+    fn to_value(&self) -> Value {
+        Value::Symbol(self.to_string())
+    }
+}
+
+impl FromValue for TokenType {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            Value::Symbol(name) | Value::String(name) => name.parse(),
+            other => Err(other.unexpected("TokenType")),
+        }
+    }
+}
+
+// a position is written as [offset, line, column] and a span as [start, end], which
+// keeps the tokens that make up most of a dump short.
+impl ToValue for Position {
+    fn to_value(&self) -> Value {
+        Value::List(vec![
+            self.offset.to_value(),
+            self.line.to_value(),
+            self.column.to_value(),
+        ])
+    }
+}
+
+impl FromValue for Position {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            Value::List(values) if values.len() == 3 => {
+                let mut values = values.into_iter().map(usize::from_value);
+                let offset = values.next().unwrap()?;
+                let line = values.next().unwrap()?;
+                let column = values.next().unwrap()?;
+                Ok(Position::new(offset, line, column))
+            }
+            other => Err(other.unexpected("Position")),
+        }
+    }
+}
+
+impl ToValue for Span {
+    fn to_value(&self) -> Value {
+        (self.start, self.end).to_value()
+    }
+}
+
+impl FromValue for Span {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let (start, end) = FromValue::from_value(value)?;
+        Ok(Span::new(start, end))
+    }
+}
+
+impl ToValue for Token {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "Token",
+            vec![
+                ("type", self.token_type.to_value()),
+                ("literal", self.literal.to_value()),
+                ("span", self.span.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for Token {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "Token")?;
+        Ok(Token::with_span(
+            fields.take("type")?,
+            fields.take("literal")?,
+            fields.take("span")?,
+        ))
+    }
+}
+
+impl ToValue for Statement {
+    fn to_value(&self) -> Value {
+        match self {
+            Statement::Let(statement) => statement.to_value(),
+            Statement::Return(statement) => statement.to_value(),
+            Statement::Expression(statement) => statement.to_value(),
+            Statement::Assign(statement) => statement.to_value(),
+            Statement::While(statement) => statement.to_value(),
+            Statement::Break(statement) => statement.to_value(),
+            Statement::Continue(statement) => statement.to_value(),
+            Statement::For(statement) => statement.to_value(),
+        }
+    }
+}
+
+impl FromValue for Statement {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value.kind() {
+            Some("LetStatement") => FromValue::from_value(value).map(Statement::Let),
+            Some("ReturnStatement") => FromValue::from_value(value).map(Statement::Return),
+            Some("ExpressionStatement") => FromValue::from_value(value).map(Statement::Expression),
+            Some("AssignStatement") => FromValue::from_value(value).map(Statement::Assign),
+            Some("WhileStatement") => FromValue::from_value(value).map(Statement::While),
+            Some("BreakStatement") => FromValue::from_value(value).map(Statement::Break),
+            Some("ContinueStatement") => FromValue::from_value(value).map(Statement::Continue),
+            Some("ForStatement") => FromValue::from_value(value).map(Statement::For),
+            Some(kind) => Err(DeserializeError::UnknownKind(kind.to_string())),
+            None => Err(value.unexpected("Statement")),
+        }
+    }
+}
+
+impl ToValue for LetStatement {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "LetStatement",
+            vec![
+                ("token", self.token.to_value()),
+                ("identifier", self.identifier.to_value()),
+                ("expression", self.expression.to_value()),
+                ("doc", self.doc.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for LetStatement {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "LetStatement")?;
+        Ok(LetStatement {
+            token: fields.take("token")?,
+            identifier: fields.take("identifier")?,
+            expression: fields.take("expression")?,
+            doc: fields.take("doc")?,
+        })
+    }
+}
+
+impl ToValue for ReturnStatement {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "ReturnStatement",
+            vec![
+                ("token", self.token.to_value()),
+                ("return_value", self.return_value.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for ReturnStatement {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "ReturnStatement")?;
+        Ok(ReturnStatement {
+            token: fields.take("token")?,
+            return_value: fields.take("return_value")?,
+        })
+    }
+}
+
+impl ToValue for ExpressionStatement {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "ExpressionStatement",
+            vec![("expression", self.expression.to_value())],
+        )
+    }
+}
+
+impl FromValue for ExpressionStatement {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "ExpressionStatement")?;
+        Ok(ExpressionStatement {
+            expression: fields.take("expression")?,
+        })
+    }
+}
+
+impl ToValue for AssignStatement {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "AssignStatement",
+            vec![
+                ("token", self.token.to_value()),
+                ("target", self.target.to_value()),
+                ("operator", self.operator.to_value()),
+                ("value", self.value.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for AssignStatement {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "AssignStatement")?;
+        Ok(AssignStatement {
+            token: fields.take("token")?,
+            target: fields.take("target")?,
+            operator: fields.take("operator")?,
+            value: fields.take("value")?,
+        })
+    }
+}
+
+impl ToValue for AssignTarget {
+    fn to_value(&self) -> Value {
+        match self {
+            AssignTarget::Identifier(identifier) => identifier.to_value(),
+            AssignTarget::Index(index) => index.to_value(),
+        }
+    }
+}
+
+impl FromValue for AssignTarget {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value.kind() {
+            Some("Identifier") => FromValue::from_value(value).map(AssignTarget::Identifier),
+            Some("IndexExpression") => FromValue::from_value(value).map(AssignTarget::Index),
+            Some(kind) => Err(DeserializeError::UnknownKind(kind.to_string())),
+            None => Err(value.unexpected("AssignTarget")),
+        }
+    }
+}
+
+impl ToValue for WhileStatement {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "WhileStatement",
+            vec![
+                ("token", self.token.to_value()),
+                ("condition", self.condition.to_value()),
+                ("body", self.body.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for WhileStatement {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "WhileStatement")?;
+        Ok(WhileStatement {
+            token: fields.take("token")?,
+            condition: fields.take("condition")?,
+            body: fields.take("body")?,
+        })
+    }
+}
+
+impl ToValue for BreakStatement {
+    fn to_value(&self) -> Value {
+        Value::node("BreakStatement", vec![("token", self.token.to_value())])
+    }
+}
+
+impl FromValue for BreakStatement {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "BreakStatement")?;
+        Ok(BreakStatement {
+            token: fields.take("token")?,
+        })
+    }
+}
+
+impl ToValue for ContinueStatement {
+    fn to_value(&self) -> Value {
+        Value::node("ContinueStatement", vec![("token", self.token.to_value())])
+    }
+}
+
+impl FromValue for ContinueStatement {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "ContinueStatement")?;
+        Ok(ContinueStatement {
+            token: fields.take("token")?,
+        })
+    }
+}
+
+impl ToValue for ForStatement {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "ForStatement",
+            vec![
+                ("token", self.token.to_value()),
+                ("key", self.key.to_value()),
+                ("value", self.value.to_value()),
+                ("iterable", self.iterable.to_value()),
+                ("body", self.body.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for ForStatement {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "ForStatement")?;
+        Ok(ForStatement {
+            token: fields.take("token")?,
+            key: fields.take("key")?,
+            value: fields.take("value")?,
+            iterable: fields.take("iterable")?,
+            body: fields.take("body")?,
+        })
+    }
+}
+
+impl ToValue for BlockStatement {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "BlockStatement",
+            vec![
+                ("token", self.token.to_value()),
+                ("statements", self.statements.to_value()),
+                ("closing_token", self.closing_token.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for BlockStatement {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "BlockStatement")?;
+        Ok(BlockStatement {
+            token: fields.take("token")?,
+            statements: fields.take("statements")?,
+            closing_token: fields.take("closing_token")?,
+        })
+    }
+}
+
+impl ToValue for Expression {
+    fn to_value(&self) -> Value {
+        match self {
+            Expression::Identifier(identifier) => identifier.to_value(),
+            Expression::Integer(integer_literal) => integer_literal.to_value(),
+            Expression::Float(float_literal) => float_literal.to_value(),
+            Expression::Prefix(prefix) => prefix.to_value(),
+            Expression::Infix(infix) => infix.to_value(),
+            Expression::Boolean(boolean) => boolean.to_value(),
+            Expression::If(if_expression) => if_expression.to_value(),
+            Expression::Function(function) => function.to_value(),
+            Expression::Call(call) => call.to_value(),
+            Expression::String(string) => string.to_value(),
+            Expression::StringInterpolation(interpolation) => interpolation.to_value(),
+            Expression::Array(array) => array.to_value(),
+            Expression::Index(index) => index.to_value(),
+            Expression::Hash(hash) => hash.to_value(),
+            Expression::Range(range) => range.to_value(),
+            Expression::Match(match_expression) => match_expression.to_value(),
+            Expression::Macro(macro_literal) => macro_literal.to_value(),
+        }
+    }
+}
+
+impl FromValue for Expression {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        // picking the conversion before calling it keeps the frame of this function small,
+        // which every level of nesting goes through.
+        let convert: fn(Value) -> Result<Self, DeserializeError> = match value.kind() {
+            Some("Identifier") => |value| FromValue::from_value(value).map(Expression::Identifier),
+            Some("IntegerLiteral") => |value| FromValue::from_value(value).map(Expression::Integer),
+            Some("FloatLiteral") => |value| FromValue::from_value(value).map(Expression::Float),
+            Some("PrefixExpression") => {
+                |value| FromValue::from_value(value).map(Expression::Prefix)
+            }
+            Some("InfixExpression") => |value| FromValue::from_value(value).map(Expression::Infix),
+            Some("Boolean") => |value| FromValue::from_value(value).map(Expression::Boolean),
+            Some("IfExpression") => |value| FromValue::from_value(value).map(Expression::If),
+            Some("FunctionLiteral") => {
+                |value| FromValue::from_value(value).map(Expression::Function)
+            }
+            Some("CallExpression") => |value| FromValue::from_value(value).map(Expression::Call),
+            Some("StringLiteral") => |value| FromValue::from_value(value).map(Expression::String),
+            Some("StringInterpolation") => {
+                |value| FromValue::from_value(value).map(Expression::StringInterpolation)
+            }
+            Some("ArrayLiteral") => |value| FromValue::from_value(value).map(Expression::Array),
+            Some("IndexExpression") => |value| FromValue::from_value(value).map(Expression::Index),
+            Some("HashLiteral") => |value| FromValue::from_value(value).map(Expression::Hash),
+            Some("RangeExpression") => |value| FromValue::from_value(value).map(Expression::Range),
+            Some("MatchExpression") => |value| FromValue::from_value(value).map(Expression::Match),
+            Some("MacroLiteral") => |value| FromValue::from_value(value).map(Expression::Macro),
+            Some(kind) => return Err(DeserializeError::UnknownKind(kind.to_string())),
+            None => return Err(value.unexpected("Expression")),
+        };
+        convert(value)
+    }
+}
+
+impl ToValue for Identifier {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "Identifier",
+            vec![
+                ("token", self.token.to_value()),
+                ("value", self.value.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for Identifier {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "Identifier")?;
+        Ok(Identifier {
+            token: fields.take("token")?,
+            value: fields.take("value")?,
+        })
+    }
+}
+
+impl ToValue for IntegerLiteral {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "IntegerLiteral",
+            vec![
+                ("token", self.token.to_value()),
+                ("value", self.value.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for IntegerLiteral {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "IntegerLiteral")?;
+        Ok(IntegerLiteral {
+            token: fields.take("token")?,
+            value: fields.take("value")?,
+        })
+    }
+}
+
+impl ToValue for FloatLiteral {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "FloatLiteral",
+            vec![
+                ("token", self.token.to_value()),
+                ("value", self.value.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for FloatLiteral {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "FloatLiteral")?;
+        Ok(FloatLiteral {
+            token: fields.take("token")?,
+            value: fields.take("value")?,
+        })
+    }
+}
+
+impl ToValue for PrefixExpression {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "PrefixExpression",
+            vec![
+                ("token", self.token.to_value()),
+                ("operator", self.operator.to_value()),
+                ("right", self.right.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for PrefixExpression {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "PrefixExpression")?;
+        Ok(PrefixExpression {
+            token: fields.take("token")?,
+            operator: fields.take("operator")?,
+            right: fields.take("right")?,
+        })
+    }
+}
+
+impl ToValue for InfixExpression {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "InfixExpression",
+            vec![
+                ("token", self.token.to_value()),
+                ("left", self.left.to_value()),
+                ("operator", self.operator.to_value()),
+                ("right", self.right.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for InfixExpression {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "InfixExpression")?;
+        Ok(InfixExpression {
+            token: fields.take("token")?,
+            left: fields.take("left")?,
+            operator: fields.take("operator")?,
+            right: fields.take("right")?,
+        })
+    }
+}
+
+impl ToValue for Boolean {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "Boolean",
+            vec![
+                ("token", self.token.to_value()),
+                ("value", self.value.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for Boolean {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "Boolean")?;
+        Ok(Boolean {
+            token: fields.take("token")?,
+            value: fields.take("value")?,
+        })
+    }
+}
+
+impl ToValue for IfExpression {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "IfExpression",
+            vec![
+                ("token", self.token.to_value()),
+                ("condition", self.condition.to_value()),
+                ("consequence", self.consequence.to_value()),
+                ("alternative", self.alternative.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for IfExpression {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "IfExpression")?;
+        Ok(IfExpression {
+            token: fields.take("token")?,
+            condition: fields.take("condition")?,
+            consequence: fields.take("consequence")?,
+            alternative: fields.take("alternative")?,
+        })
+    }
+}
+
+impl ToValue for FunctionLiteral {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "FunctionLiteral",
+            vec![
+                ("token", self.token.to_value()),
+                ("parameters", self.parameters.to_value()),
+                ("body", self.body.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for FunctionLiteral {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "FunctionLiteral")?;
+        Ok(FunctionLiteral {
+            token: fields.take("token")?,
+            parameters: fields.take("parameters")?,
+            body: fields.take("body")?,
+        })
+    }
+}
+
+impl ToValue for MacroLiteral {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "MacroLiteral",
+            vec![
+                ("token", self.token.to_value()),
+                ("parameters", self.parameters.to_value()),
+                ("body", self.body.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for MacroLiteral {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "MacroLiteral")?;
+        Ok(MacroLiteral {
+            token: fields.take("token")?,
+            parameters: fields.take("parameters")?,
+            body: fields.take("body")?,
+        })
+    }
+}
+
+impl ToValue for CallExpression {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "CallExpression",
+            vec![
+                ("token", self.token.to_value()),
+                ("function", self.function.to_value()),
+                ("arguments", self.arguments.to_value()),
+                ("closing_token", self.closing_token.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for CallExpression {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "CallExpression")?;
+        Ok(CallExpression {
+            token: fields.take("token")?,
+            function: fields.take("function")?,
+            arguments: fields.take("arguments")?,
+            closing_token: fields.take("closing_token")?,
+        })
+    }
+}
+
+impl ToValue for StringLiteral {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "StringLiteral",
+            vec![
+                ("token", self.token.to_value()),
+                ("value", self.value.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for StringLiteral {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "StringLiteral")?;
+        Ok(StringLiteral {
+            token: fields.take("token")?,
+            value: fields.take("value")?,
+        })
+    }
+}
+
+// text parts are plain strings, and every other part is an expression node.
+impl ToValue for StringPart {
+    fn to_value(&self) -> Value {
+        match self {
+            StringPart::Text(text) => text.to_value(),
+            StringPart::Expression(expression) => expression.to_value(),
+        }
+    }
+}
+
+impl FromValue for StringPart {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value {
+            Value::String(text) => Ok(StringPart::Text(text)),
+            value => FromValue::from_value(value).map(StringPart::Expression),
+        }
+    }
+}
+
+impl ToValue for StringInterpolation {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "StringInterpolation",
+            vec![
+                ("token", self.token.to_value()),
+                ("parts", self.parts.to_value()),
+                ("closing_token", self.closing_token.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for StringInterpolation {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "StringInterpolation")?;
+        Ok(StringInterpolation {
+            token: fields.take("token")?,
+            parts: fields.take("parts")?,
+            closing_token: fields.take("closing_token")?,
+        })
+    }
+}
+
+impl ToValue for ArrayLiteral {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "ArrayLiteral",
+            vec![
+                ("token", self.token.to_value()),
+                ("elements", self.elements.to_value()),
+                ("closing_token", self.closing_token.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for ArrayLiteral {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "ArrayLiteral")?;
+        Ok(ArrayLiteral {
+            token: fields.take("token")?,
+            elements: fields.take("elements")?,
+            closing_token: fields.take("closing_token")?,
+        })
+    }
+}
+
+impl ToValue for IndexExpression {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "IndexExpression",
+            vec![
+                ("token", self.token.to_value()),
+                ("left", self.left.to_value()),
+                ("index", self.index.to_value()),
+                ("closing_token", self.closing_token.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for IndexExpression {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "IndexExpression")?;
+        Ok(IndexExpression {
+            token: fields.take("token")?,
+            left: fields.take("left")?,
+            index: fields.take("index")?,
+            closing_token: fields.take("closing_token")?,
+        })
+    }
+}
+
+impl ToValue for HashLiteral {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "HashLiteral",
+            vec![
+                ("token", self.token.to_value()),
+                ("pairs", self.pairs.to_value()),
+                ("closing_token", self.closing_token.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for HashLiteral {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "HashLiteral")?;
+        Ok(HashLiteral {
+            token: fields.take("token")?,
+            pairs: fields.take("pairs")?,
+            closing_token: fields.take("closing_token")?,
+        })
+    }
+}
+
+impl ToValue for RangeExpression {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "RangeExpression",
+            vec![
+                ("token", self.token.to_value()),
+                ("start", self.start.to_value()),
+                ("end", self.end.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for RangeExpression {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "RangeExpression")?;
+        Ok(RangeExpression {
+            token: fields.take("token")?,
+            start: fields.take("start")?,
+            end: fields.take("end")?,
+        })
+    }
+}
+
+impl ToValue for MatchExpression {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "MatchExpression",
+            vec![
+                ("token", self.token.to_value()),
+                ("subject", self.subject.to_value()),
+                ("arms", self.arms.to_value()),
+                ("closing_token", self.closing_token.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for MatchExpression {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "MatchExpression")?;
+        Ok(MatchExpression {
+            token: fields.take("token")?,
+            subject: fields.take("subject")?,
+            arms: fields.take("arms")?,
+            closing_token: fields.take("closing_token")?,
+        })
+    }
+}
+
+impl ToValue for MatchArm {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "MatchArm",
+            vec![
+                ("pattern", self.pattern.to_value()),
+                ("guard", self.guard.to_value()),
+                ("body", self.body.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for MatchArm {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "MatchArm")?;
+        Ok(MatchArm {
+            pattern: fields.take("pattern")?,
+            guard: fields.take("guard")?,
+            body: fields.take("body")?,
+        })
+    }
+}
+
+impl ToValue for Pattern {
+    fn to_value(&self) -> Value {
+        match self {
+            Pattern::Wildcard(token) => {
+                Value::node("WildcardPattern", vec![("token", token.to_value())])
+            }
+            Pattern::Identifier(identifier) => identifier.to_value(),
+            Pattern::Literal(literal) => {
+                Value::node("LiteralPattern", vec![("literal", literal.to_value())])
+            }
+            Pattern::Array(array) => array.to_value(),
+            Pattern::Hash(hash) => hash.to_value(),
+        }
+    }
+}
+
+impl FromValue for Pattern {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        match value.kind() {
+            Some("WildcardPattern") => {
+                let mut fields = Fields::of(value, "WildcardPattern")?;
+                fields.take("token").map(Pattern::Wildcard)
+            }
+            Some("Identifier") => FromValue::from_value(value).map(Pattern::Identifier),
+            Some("LiteralPattern") => {
+                let mut fields = Fields::of(value, "LiteralPattern")?;
+                fields.take("literal").map(Pattern::Literal)
+            }
+            Some("ArrayPattern") => FromValue::from_value(value).map(Pattern::Array),
+            Some("HashPattern") => FromValue::from_value(value).map(Pattern::Hash),
+            Some(kind) => Err(DeserializeError::UnknownKind(kind.to_string())),
+            None => Err(value.unexpected("Pattern")),
+        }
+    }
+}
+
+impl ToValue for ArrayPattern {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "ArrayPattern",
+            vec![
+                ("token", self.token.to_value()),
+                ("elements", self.elements.to_value()),
+                ("rest", self.rest.to_value()),
+                ("closing_token", self.closing_token.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for ArrayPattern {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "ArrayPattern")?;
+        Ok(ArrayPattern {
+            token: fields.take("token")?,
+            elements: fields.take("elements")?,
+            rest: fields.take("rest")?,
+            closing_token: fields.take("closing_token")?,
+        })
+    }
+}
+
+impl ToValue for HashPattern {
+    fn to_value(&self) -> Value {
+        Value::node(
+            "HashPattern",
+            vec![
+                ("token", self.token.to_value()),
+                ("pairs", self.pairs.to_value()),
+                ("closing_token", self.closing_token.to_value()),
+            ],
+        )
+    }
+}
+
+impl FromValue for HashPattern {
+    fn from_value(value: Value) -> Result<Self, DeserializeError> {
+        let mut fields = Fields::of(value, "HashPattern")?;
+        Ok(HashPattern {
+            token: fields.take("token")?,
+            pairs: fields.take("pairs")?,
+            closing_token: fields.take("closing_token")?,
+        })
+    }
+}
+
+// MAX_DEPTH bounds how deeply lists and nodes may nest in serialized input, as they
+// are read, converted and later evaluated recursively. most constructs take one or
+// two levels of values per level of nesting in the source, so this admits about what
+// the parser does, though hash literals and match arms run out sooner.
+pub const MAX_DEPTH: usize = 2 * DEFAULT_MAX_DEPTH + 8;
+
+// Scanner is the character cursor shared by the json and sexp readers. it tracks the
+// position of the next character so that syntax errors point into the input.
+pub(crate) struct Scanner<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    position: Position,
+    // number of lists and nodes enclosing the next character.
+    depth: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+            position: Position::default(),
+            depth: 0,
+        }
+    }
+
+    pub(crate) fn position(&self) -> Position {
+        self.position
+    }
+
+    // nested reads a list or node one level deeper, failing past MAX_DEPTH.
+    pub(crate) fn nested<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<T, DeserializeError>,
+    ) -> Result<T, DeserializeError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(DeserializeError::NestingDepthExceeded(
+                self.position,
+                MAX_DEPTH,
+            ));
+        }
+        self.depth += 1;
+        let value = read(self);
+        self.depth -= 1;
+        value
+    }
+
+    pub(crate) fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    pub(crate) fn next_char(&mut self) -> Result<char, DeserializeError> {
+        match self.chars.next() {
+            Some(ch) => {
+                self.position.advance(ch);
+                Ok(ch)
+            }
+            None => Err(DeserializeError::UnexpectedEnd(self.position)),
+        }
+    }
+
+    pub(crate) fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            let _ = self.next_char();
+        }
+    }
+
+    pub(crate) fn expect(&mut self, expected: char) -> Result<(), DeserializeError> {
+        let position = self.position;
+        match self.next_char()? {
+            ch if ch == expected => Ok(()),
+            ch => Err(DeserializeError::UnexpectedCharacter(ch, position)),
+        }
+    }
+
+    pub(crate) fn unexpected(&mut self) -> DeserializeError {
+        match self.peek() {
+            Some(ch) => DeserializeError::UnexpectedCharacter(ch, self.position),
+            None => DeserializeError::UnexpectedEnd(self.position),
+        }
+    }
+
+    // read_while consumes characters for as long as accept holds and returns them.
+    pub(crate) fn read_while(&mut self, accept: impl Fn(char) -> bool) -> String {
+        let mut read = String::new();
+        while let Some(ch) = self.peek().filter(|ch| accept(*ch)) {
+            read.push(ch);
+            let _ = self.next_char();
+        }
+        read
+    }
+
+    // read_number reads an integer, or a float when it has a fraction or an exponent.
+    pub(crate) fn read_number(&mut self) -> Result<Value, DeserializeError> {
+        let position = self.position;
+        let number = self.read_while(|ch| ch.is_ascii_digit() || "+-.eE".contains(ch));
+        if let Ok(value) = number.parse() {
+            return Ok(Value::Integer(value));
+        }
+        match number.parse() {
+            Ok(value) if number.contains(|ch: char| ch.is_ascii_digit()) => Ok(Value::Float(value)),
+            _ => Err(DeserializeError::InvalidNumber(number, position)),
+        }
+    }
+
+    // read_string reads a double quoted string with JSON escapes, which is what
+    // write_string produces for both formats.
+    pub(crate) fn read_string(&mut self) -> Result<String, DeserializeError> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            let position = self.position;
+            match self.next_char()? {
+                '"' => return Ok(string),
+                '\\' => match self.next_char()? {
+                    '"' => string.push('"'),
+                    '\\' => string.push('\\'),
+                    '/' => string.push('/'),
+                    'b' => string.push('\u{8}'),
+                    'f' => string.push('\u{c}'),
+                    'n' => string.push('\n'),
+                    'r' => string.push('\r'),
+                    't' => string.push('\t'),
+                    'u' => string.push(self.read_unicode_escape(position)?),
+                    ch => {
+                        return Err(DeserializeError::InvalidEscape(
+                            format!("\\{}", ch),
+                            position,
+                        ))
+                    }
+                },
+                ch => string.push(ch),
+            }
+        }
+    }
+
+    fn read_unicode_escape(&mut self, position: Position) -> Result<char, DeserializeError> {
+        let high = self.read_hex(position)?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            self.expect('\\')?;
+            self.expect('u')?;
+            let low = self.read_hex(position)?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(DeserializeError::InvalidEscape(
+                    format!("\\u{:04x}\\u{:04x}", high, low),
+                    position,
+                ));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        std::char::from_u32(code)
+            .ok_or_else(|| DeserializeError::InvalidEscape(format!("\\u{:04x}", code), position))
+    }
+
+    fn read_hex(&mut self, position: Position) -> Result<u32, DeserializeError> {
+        let mut digits = String::new();
+        for _ in 0..4 {
+            digits.push(self.next_char()?);
+        }
+        u32::from_str_radix(&digits, 16)
+            .map_err(|_| DeserializeError::InvalidEscape(format!("\\u{}", digits), position))
+    }
+
+    pub(crate) fn finish(mut self, value: Value) -> Result<Value, DeserializeError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(_) => Err(self.unexpected()),
+            None => Ok(value),
+        }
+    }
+}
+
+pub(crate) fn write_string(output: &mut String, string: &str) {
+    output.push('"');
+    for ch in string.chars() {
+        match ch {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => output.push(ch),
+        }
+    }
+    output.push('"');
+}
+
+// write_float keeps a fraction on whole numbers so the value reads back as a float,
+// and spells the values without a literal as strings.
+pub(crate) fn write_float(output: &mut String, value: f64) {
+    if value.is_finite() {
+        output.push_str(&format!("{:?}", value));
+    } else {
+        write_string(output, &value.to_string());
+    }
+}
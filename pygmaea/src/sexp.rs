@@ -0,0 +1,100 @@
+use crate::error::DeserializeError;
+use crate::serialize::{write_float, write_string, Scanner, Value};
+
+// a node is written as (Kind field...) with its fields by position, and a list as
+// [value...]. null and booleans are the symbols nil, true and false.
+pub fn to_string(value: &Value) -> String {
+    let mut output = String::new();
+    write_value(&mut output, value);
+    output
+}
+
+pub fn from_str(input: &str) -> Result<Value, DeserializeError> {
+    let mut scanner = Scanner::new(input);
+    let value = read_value(&mut scanner)?;
+    scanner.finish(value)
+}
+
+fn write_value(output: &mut String, value: &Value) {
+    match value {
+        Value::Null => output.push_str("nil"),
+        Value::Boolean(value) => output.push_str(&value.to_string()),
+        Value::Integer(value) => output.push_str(&value.to_string()),
+        Value::Float(value) => write_float(output, *value),
+        Value::String(value) => write_string(output, value),
+        Value::Symbol(value) => output.push_str(value),
+        Value::List(values) => {
+            output.push('[');
+            write_values(output, values.iter());
+            output.push(']');
+        }
+        Value::Node(kind, fields) => {
+            output.push('(');
+            output.push_str(kind);
+            if !fields.is_empty() {
+                output.push(' ');
+            }
+            write_values(output, fields.iter().map(|(_, value)| value));
+            output.push(')');
+        }
+    }
+}
+
+fn write_values<'a>(output: &mut String, values: impl Iterator<Item = &'a Value>) {
+    for (i, value) in values.enumerate() {
+        if i > 0 {
+            output.push(' ');
+        }
+        write_value(output, value);
+    }
+}
+
+fn is_symbol(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
+fn read_value(scanner: &mut Scanner) -> Result<Value, DeserializeError> {
+    scanner.skip_whitespace();
+    match scanner.peek() {
+        Some('(') => scanner.nested(read_node),
+        Some('[') => scanner.nested(|scanner| {
+            scanner.expect('[')?;
+            read_values(scanner, ']').map(Value::List)
+        }),
+        Some('"') => scanner.read_string().map(Value::String),
+        Some(ch) if ch == '-' || ch.is_ascii_digit() => scanner.read_number(),
+        Some(ch) if is_symbol(ch) => match scanner.read_while(is_symbol).as_str() {
+            "nil" => Ok(Value::Null),
+            "true" => Ok(Value::Boolean(true)),
+            "false" => Ok(Value::Boolean(false)),
+            symbol => Ok(Value::Symbol(symbol.to_string())),
+        },
+        _ => Err(scanner.unexpected()),
+    }
+}
+
+fn read_node(scanner: &mut Scanner) -> Result<Value, DeserializeError> {
+    scanner.expect('(')?;
+    scanner.skip_whitespace();
+    let kind = scanner.read_while(is_symbol);
+    if kind.is_empty() {
+        return Err(scanner.unexpected());
+    }
+    let fields = read_values(scanner, ')')?
+        .into_iter()
+        .map(|value| (String::new(), value))
+        .collect();
+    Ok(Value::Node(kind, fields))
+}
+
+fn read_values(scanner: &mut Scanner, closing: char) -> Result<Vec<Value>, DeserializeError> {
+    let mut values = vec![];
+    loop {
+        scanner.skip_whitespace();
+        if scanner.peek() == Some(closing) {
+            scanner.expect(closing)?;
+            return Ok(values);
+        }
+        values.push(read_value(scanner)?);
+    }
+}
@@ -1,16 +1,48 @@
+use crate::span::Span;
 use crate::token_type::TokenType;
 
 #[derive(Debug, Eq, PartialEq, Default, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub literal: String,
+    pub span: Span,
+    // trivia is only collected by a lossless lexer, apart from doc comments. trailing
+    // trivia runs up to the end of the line the token is on, and everything after it
+    // leads the next token.
+    pub leading_trivia: Vec<Trivia>,
+    pub trailing_trivia: Vec<Trivia>,
 }
 
 impl Token {
     pub fn new(token_type: TokenType, literal: String) -> Self {
+        Self::with_span(token_type, literal, Span::default())
+    }
+
+    pub fn with_span(token_type: TokenType, literal: String, span: Span) -> Self {
         Self {
             token_type,
             literal,
+            span,
+            leading_trivia: vec![],
+            trailing_trivia: vec![],
+        }
+    }
+
+    // doc_comment joins the lines of the `///` comments leading the token.
+    pub fn doc_comment(&self) -> Option<String> {
+        let lines: Vec<_> = self
+            .leading_trivia
+            .iter()
+            .filter(|trivia| trivia.kind == TriviaKind::DocComment)
+            .map(|trivia| {
+                let line = trivia.text.trim_start_matches('/').trim_end_matches('\r');
+                line.strip_prefix(' ').unwrap_or(line)
+            })
+            .collect();
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
         }
     }
 }
@@ -20,3 +52,24 @@ impl std::fmt::Display for Token {
         write!(f, "[Type:{}, Literal: {}]", self.token_type, self.literal)
     }
 }
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum TriviaKind {
+    Whitespace,
+    LineComment,
+    BlockComment,
+    DocComment,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+    pub span: Span,
+}
+
+impl Trivia {
+    pub fn new(kind: TriviaKind, text: String, span: Span) -> Self {
+        Self { kind, text, span }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::DeserializeError;
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 
@@ -7,18 +8,33 @@ pub enum TokenType {
     Minus,
     Asterisk,
     Slash,
+    Percent,
     Assign,
+    PlusAssign,
+    MinusAssign,
+    AsteriskAssign,
+    SlashAssign,
     Bang,
     LessThan,
     GreaterThan,
+    LessThanOrEqual,
+    GreaterThanOrEqual,
     Equal,
     NotEqual,
+    And,
+    Or,
     Comma,
     Semicolon,
+    Colon,
+    DotDot,
+    Ellipsis,
+    FatArrow,
     LParen,
     RParen,
     LBrace,
     RBrace,
+    LBracket,
+    RBracket,
     True,
     False,
     Let,
@@ -26,7 +42,19 @@ pub enum TokenType {
     If,
     Else,
     Return,
+    While,
+    Break,
+    Continue,
+    For,
+    In,
+    Match,
+    Macro,
     Int,
+    Float,
+    String,
+    StringStart,
+    StringMiddle,
+    StringEnd,
     Ident,
     EOF,
     Illegal,
@@ -42,6 +70,13 @@ impl TokenType {
             | TokenType::If
             | TokenType::Else
             | TokenType::Return
+            | TokenType::While
+            | TokenType::Break
+            | TokenType::Continue
+            | TokenType::For
+            | TokenType::In
+            | TokenType::Match
+            | TokenType::Macro
             | TokenType::Ident => true,
             _ => false,
         }
@@ -51,6 +86,10 @@ impl TokenType {
         self == TokenType::Int
     }
 
+    pub fn is_number(self) -> bool {
+        self == TokenType::Int || self == TokenType::Float
+    }
+
     pub fn is_eof(self) -> bool {
         self == TokenType::EOF
     }
@@ -72,18 +111,33 @@ impl std::fmt::Display for TokenType {
                 TokenType::Minus => "Minus",
                 TokenType::Asterisk => "Asterisk",
                 TokenType::Slash => "Slash",
+                TokenType::Percent => "Percent",
                 TokenType::Assign => "Assign",
+                TokenType::PlusAssign => "PlusAssign",
+                TokenType::MinusAssign => "MinusAssign",
+                TokenType::AsteriskAssign => "AsteriskAssign",
+                TokenType::SlashAssign => "SlashAssign",
                 TokenType::Bang => "Bang",
                 TokenType::LessThan => "LessThan",
                 TokenType::GreaterThan => "GreaterThan",
+                TokenType::LessThanOrEqual => "LessThanOrEqual",
+                TokenType::GreaterThanOrEqual => "GreaterThanOrEqual",
                 TokenType::Equal => "Equal",
                 TokenType::NotEqual => "NotEqual",
+                TokenType::And => "And",
+                TokenType::Or => "Or",
                 TokenType::LParen => "LParen",
                 TokenType::RParen => "RParen",
                 TokenType::LBrace => "LBrace",
                 TokenType::RBrace => "RBrace",
+                TokenType::LBracket => "LBracket",
+                TokenType::RBracket => "RBracket",
                 TokenType::Comma => "Comma",
                 TokenType::Semicolon => "Semicolon",
+                TokenType::Colon => "Colon",
+                TokenType::DotDot => "DotDot",
+                TokenType::Ellipsis => "Ellipsis",
+                TokenType::FatArrow => "FatArrow",
                 TokenType::True => "True",
                 TokenType::False => "False",
                 TokenType::Let => "Let",
@@ -91,7 +145,19 @@ impl std::fmt::Display for TokenType {
                 TokenType::If => "If",
                 TokenType::Else => "Else",
                 TokenType::Return => "Return",
+                TokenType::While => "While",
+                TokenType::Break => "Break",
+                TokenType::Continue => "Continue",
+                TokenType::For => "For",
+                TokenType::In => "In",
+                TokenType::Match => "Match",
+                TokenType::Macro => "Macro",
                 TokenType::Int => "Int",
+                TokenType::Float => "Float",
+                TokenType::String => "String",
+                TokenType::StringStart => "StringStart",
+                TokenType::StringMiddle => "StringMiddle",
+                TokenType::StringEnd => "StringEnd",
                 TokenType::Ident => "Ident",
                 TokenType::EOF => "EOF",
                 TokenType::Illegal => "Illegal",
@@ -100,6 +166,70 @@ impl std::fmt::Display for TokenType {
     }
 }
 
+impl std::str::FromStr for TokenType {
+    type Err = DeserializeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Plus" => Ok(TokenType::Plus),
+            "Minus" => Ok(TokenType::Minus),
+            "Asterisk" => Ok(TokenType::Asterisk),
+            "Slash" => Ok(TokenType::Slash),
+            "Percent" => Ok(TokenType::Percent),
+            "Assign" => Ok(TokenType::Assign),
+            "PlusAssign" => Ok(TokenType::PlusAssign),
+            "MinusAssign" => Ok(TokenType::MinusAssign),
+            "AsteriskAssign" => Ok(TokenType::AsteriskAssign),
+            "SlashAssign" => Ok(TokenType::SlashAssign),
+            "Bang" => Ok(TokenType::Bang),
+            "LessThan" => Ok(TokenType::LessThan),
+            "GreaterThan" => Ok(TokenType::GreaterThan),
+            "LessThanOrEqual" => Ok(TokenType::LessThanOrEqual),
+            "GreaterThanOrEqual" => Ok(TokenType::GreaterThanOrEqual),
+            "Equal" => Ok(TokenType::Equal),
+            "NotEqual" => Ok(TokenType::NotEqual),
+            "And" => Ok(TokenType::And),
+            "Or" => Ok(TokenType::Or),
+            "LParen" => Ok(TokenType::LParen),
+            "RParen" => Ok(TokenType::RParen),
+            "LBrace" => Ok(TokenType::LBrace),
+            "RBrace" => Ok(TokenType::RBrace),
+            "LBracket" => Ok(TokenType::LBracket),
+            "RBracket" => Ok(TokenType::RBracket),
+            "Comma" => Ok(TokenType::Comma),
+            "Semicolon" => Ok(TokenType::Semicolon),
+            "Colon" => Ok(TokenType::Colon),
+            "DotDot" => Ok(TokenType::DotDot),
+            "Ellipsis" => Ok(TokenType::Ellipsis),
+            "FatArrow" => Ok(TokenType::FatArrow),
+            "True" => Ok(TokenType::True),
+            "False" => Ok(TokenType::False),
+            "Let" => Ok(TokenType::Let),
+            "Function" => Ok(TokenType::Function),
+            "If" => Ok(TokenType::If),
+            "Else" => Ok(TokenType::Else),
+            "Return" => Ok(TokenType::Return),
+            "While" => Ok(TokenType::While),
+            "Break" => Ok(TokenType::Break),
+            "Continue" => Ok(TokenType::Continue),
+            "For" => Ok(TokenType::For),
+            "In" => Ok(TokenType::In),
+            "Match" => Ok(TokenType::Match),
+            "Macro" => Ok(TokenType::Macro),
+            "Int" => Ok(TokenType::Int),
+            "Float" => Ok(TokenType::Float),
+            "String" => Ok(TokenType::String),
+            "StringStart" => Ok(TokenType::StringStart),
+            "StringMiddle" => Ok(TokenType::StringMiddle),
+            "StringEnd" => Ok(TokenType::StringEnd),
+            "Ident" => Ok(TokenType::Ident),
+            "EOF" => Ok(TokenType::EOF),
+            "Illegal" => Ok(TokenType::Illegal),
+            _ => Err(DeserializeError::UnknownTokenType(s.to_string())),
+        }
+    }
+}
+
 lazy_static! {
     pub static ref KEYWORDS: HashMap<&'static str, TokenType> = [
         ("true", TokenType::True),
@@ -109,6 +239,13 @@ lazy_static! {
         ("if", TokenType::If),
         ("else", TokenType::Else),
         ("return", TokenType::Return),
+        ("while", TokenType::While),
+        ("break", TokenType::Break),
+        ("continue", TokenType::Continue),
+        ("for", TokenType::For),
+        ("in", TokenType::In),
+        ("match", TokenType::Match),
+        ("macro", TokenType::Macro),
     ]
     .iter()
     .cloned()
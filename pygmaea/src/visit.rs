@@ -0,0 +1,334 @@
+use crate::ast::*;
+
+// Visitor walks the AST by reference. every method defaults to walking the children
+// of its node, so an implementation only overrides the nodes it cares about and
+// calls the matching walk function to keep descending into them.
+pub trait Visitor {
+    fn visit_program(&mut self, program: &[Statement]) {
+        walk_program(self, program)
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement)
+    }
+
+    fn visit_let_statement(&mut self, statement: &LetStatement) {
+        walk_let_statement(self, statement)
+    }
+
+    fn visit_return_statement(&mut self, statement: &ReturnStatement) {
+        walk_return_statement(self, statement)
+    }
+
+    fn visit_expression_statement(&mut self, statement: &ExpressionStatement) {
+        walk_expression_statement(self, statement)
+    }
+
+    fn visit_assign_statement(&mut self, statement: &AssignStatement) {
+        walk_assign_statement(self, statement)
+    }
+
+    fn visit_assign_target(&mut self, target: &AssignTarget) {
+        walk_assign_target(self, target)
+    }
+
+    fn visit_while_statement(&mut self, statement: &WhileStatement) {
+        walk_while_statement(self, statement)
+    }
+
+    fn visit_for_statement(&mut self, statement: &ForStatement) {
+        walk_for_statement(self, statement)
+    }
+
+    fn visit_break_statement(&mut self, _statement: &BreakStatement) {}
+
+    fn visit_continue_statement(&mut self, _statement: &ContinueStatement) {}
+
+    fn visit_block_statement(&mut self, block: &BlockStatement) {
+        walk_block_statement(self, block)
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression)
+    }
+
+    fn visit_identifier(&mut self, _identifier: &Identifier) {}
+
+    fn visit_integer_literal(&mut self, _integer_literal: &IntegerLiteral) {}
+
+    fn visit_float_literal(&mut self, _float_literal: &FloatLiteral) {}
+
+    fn visit_boolean(&mut self, _boolean: &Boolean) {}
+
+    fn visit_string_literal(&mut self, _string: &StringLiteral) {}
+
+    fn visit_prefix_expression(&mut self, prefix: &PrefixExpression) {
+        walk_prefix_expression(self, prefix)
+    }
+
+    fn visit_infix_expression(&mut self, infix: &InfixExpression) {
+        walk_infix_expression(self, infix)
+    }
+
+    fn visit_if_expression(&mut self, if_expression: &IfExpression) {
+        walk_if_expression(self, if_expression)
+    }
+
+    fn visit_function_literal(&mut self, function: &FunctionLiteral) {
+        walk_function_literal(self, function)
+    }
+
+    fn visit_macro_literal(&mut self, macro_literal: &MacroLiteral) {
+        walk_macro_literal(self, macro_literal)
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression) {
+        walk_call_expression(self, call)
+    }
+
+    fn visit_string_interpolation(&mut self, interpolation: &StringInterpolation) {
+        walk_string_interpolation(self, interpolation)
+    }
+
+    fn visit_array_literal(&mut self, array: &ArrayLiteral) {
+        walk_array_literal(self, array)
+    }
+
+    fn visit_index_expression(&mut self, index: &IndexExpression) {
+        walk_index_expression(self, index)
+    }
+
+    fn visit_hash_literal(&mut self, hash: &HashLiteral) {
+        walk_hash_literal(self, hash)
+    }
+
+    fn visit_range_expression(&mut self, range: &RangeExpression) {
+        walk_range_expression(self, range)
+    }
+
+    fn visit_match_expression(&mut self, match_expression: &MatchExpression) {
+        walk_match_expression(self, match_expression)
+    }
+
+    fn visit_match_arm(&mut self, arm: &MatchArm) {
+        walk_match_arm(self, arm)
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        walk_pattern(self, pattern)
+    }
+
+    fn visit_array_pattern(&mut self, array: &ArrayPattern) {
+        walk_array_pattern(self, array)
+    }
+
+    fn visit_hash_pattern(&mut self, hash: &HashPattern) {
+        walk_hash_pattern(self, hash)
+    }
+}
+
+pub fn walk_program<V: Visitor + ?Sized>(visitor: &mut V, program: &[Statement]) {
+    for statement in program.iter() {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::Let(statement) => visitor.visit_let_statement(statement),
+        Statement::Return(statement) => visitor.visit_return_statement(statement),
+        Statement::Expression(statement) => visitor.visit_expression_statement(statement),
+        Statement::Assign(statement) => visitor.visit_assign_statement(statement),
+        Statement::While(statement) => visitor.visit_while_statement(statement),
+        Statement::Break(statement) => visitor.visit_break_statement(statement),
+        Statement::Continue(statement) => visitor.visit_continue_statement(statement),
+        Statement::For(statement) => visitor.visit_for_statement(statement),
+    }
+}
+
+pub fn walk_let_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &LetStatement) {
+    visitor.visit_identifier(&statement.identifier);
+    visitor.visit_expression(&statement.expression);
+}
+
+pub fn walk_return_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &ReturnStatement) {
+    visitor.visit_expression(&statement.return_value);
+}
+
+pub fn walk_expression_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    statement: &ExpressionStatement,
+) {
+    visitor.visit_expression(&statement.expression);
+}
+
+pub fn walk_assign_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &AssignStatement) {
+    visitor.visit_assign_target(&statement.target);
+    visitor.visit_expression(&statement.value);
+}
+
+pub fn walk_assign_target<V: Visitor + ?Sized>(visitor: &mut V, target: &AssignTarget) {
+    match target {
+        AssignTarget::Identifier(identifier) => visitor.visit_identifier(identifier),
+        AssignTarget::Index(index) => visitor.visit_index_expression(index),
+    }
+}
+
+pub fn walk_while_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &WhileStatement) {
+    visitor.visit_expression(&statement.condition);
+    visitor.visit_block_statement(&statement.body);
+}
+
+pub fn walk_for_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &ForStatement) {
+    visitor.visit_identifier(&statement.key);
+    if let Some(ref value) = statement.value {
+        visitor.visit_identifier(value);
+    }
+    visitor.visit_expression(&statement.iterable);
+    visitor.visit_block_statement(&statement.body);
+}
+
+pub fn walk_block_statement<V: Visitor + ?Sized>(visitor: &mut V, block: &BlockStatement) {
+    visitor.visit_program(&block.statements);
+}
+
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::Identifier(identifier) => visitor.visit_identifier(identifier),
+        Expression::Integer(integer_literal) => visitor.visit_integer_literal(integer_literal),
+        Expression::Float(float_literal) => visitor.visit_float_literal(float_literal),
+        Expression::Prefix(prefix) => visitor.visit_prefix_expression(prefix),
+        Expression::Infix(infix) => visitor.visit_infix_expression(infix),
+        Expression::Boolean(boolean) => visitor.visit_boolean(boolean),
+        Expression::If(if_expression) => visitor.visit_if_expression(if_expression),
+        Expression::Function(function) => visitor.visit_function_literal(function),
+        Expression::Call(call) => visitor.visit_call_expression(call),
+        Expression::String(string) => visitor.visit_string_literal(string),
+        Expression::StringInterpolation(interpolation) => {
+            visitor.visit_string_interpolation(interpolation)
+        }
+        Expression::Array(array) => visitor.visit_array_literal(array),
+        Expression::Index(index) => visitor.visit_index_expression(index),
+        Expression::Hash(hash) => visitor.visit_hash_literal(hash),
+        Expression::Range(range) => visitor.visit_range_expression(range),
+        Expression::Match(match_expression) => visitor.visit_match_expression(match_expression),
+        Expression::Macro(macro_literal) => visitor.visit_macro_literal(macro_literal),
+    }
+}
+
+pub fn walk_prefix_expression<V: Visitor + ?Sized>(visitor: &mut V, prefix: &PrefixExpression) {
+    visitor.visit_expression(&prefix.right);
+}
+
+pub fn walk_infix_expression<V: Visitor + ?Sized>(visitor: &mut V, infix: &InfixExpression) {
+    visitor.visit_expression(&infix.left);
+    visitor.visit_expression(&infix.right);
+}
+
+pub fn walk_if_expression<V: Visitor + ?Sized>(visitor: &mut V, if_expression: &IfExpression) {
+    visitor.visit_expression(&if_expression.condition);
+    visitor.visit_block_statement(&if_expression.consequence);
+    if let Some(ref alternative) = if_expression.alternative {
+        visitor.visit_block_statement(alternative);
+    }
+}
+
+pub fn walk_function_literal<V: Visitor + ?Sized>(visitor: &mut V, function: &FunctionLiteral) {
+    for parameter in function.parameters.iter() {
+        visitor.visit_identifier(parameter);
+    }
+    visitor.visit_block_statement(&function.body);
+}
+
+pub fn walk_macro_literal<V: Visitor + ?Sized>(visitor: &mut V, macro_literal: &MacroLiteral) {
+    for parameter in macro_literal.parameters.iter() {
+        visitor.visit_identifier(parameter);
+    }
+    visitor.visit_block_statement(&macro_literal.body);
+}
+
+pub fn walk_call_expression<V: Visitor + ?Sized>(visitor: &mut V, call: &CallExpression) {
+    visitor.visit_expression(&call.function);
+    for argument in call.arguments.iter() {
+        visitor.visit_expression(argument);
+    }
+}
+
+pub fn walk_string_interpolation<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    interpolation: &StringInterpolation,
+) {
+    for part in interpolation.parts.iter() {
+        match part {
+            StringPart::Text(_) => {}
+            StringPart::Expression(expression) => visitor.visit_expression(expression),
+        }
+    }
+}
+
+pub fn walk_array_literal<V: Visitor + ?Sized>(visitor: &mut V, array: &ArrayLiteral) {
+    for element in array.elements.iter() {
+        visitor.visit_expression(element);
+    }
+}
+
+pub fn walk_index_expression<V: Visitor + ?Sized>(visitor: &mut V, index: &IndexExpression) {
+    visitor.visit_expression(&index.left);
+    visitor.visit_expression(&index.index);
+}
+
+pub fn walk_hash_literal<V: Visitor + ?Sized>(visitor: &mut V, hash: &HashLiteral) {
+    for (key, value) in hash.pairs.iter() {
+        visitor.visit_expression(key);
+        visitor.visit_expression(value);
+    }
+}
+
+pub fn walk_range_expression<V: Visitor + ?Sized>(visitor: &mut V, range: &RangeExpression) {
+    visitor.visit_expression(&range.start);
+    visitor.visit_expression(&range.end);
+}
+
+pub fn walk_match_expression<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    match_expression: &MatchExpression,
+) {
+    visitor.visit_expression(&match_expression.subject);
+    for arm in match_expression.arms.iter() {
+        visitor.visit_match_arm(arm);
+    }
+}
+
+pub fn walk_match_arm<V: Visitor + ?Sized>(visitor: &mut V, arm: &MatchArm) {
+    visitor.visit_pattern(&arm.pattern);
+    if let Some(ref guard) = arm.guard {
+        visitor.visit_expression(guard);
+    }
+    visitor.visit_expression(&arm.body);
+}
+
+pub fn walk_pattern<V: Visitor + ?Sized>(visitor: &mut V, pattern: &Pattern) {
+    match pattern {
+        Pattern::Wildcard(_) => {}
+        Pattern::Identifier(identifier) => visitor.visit_identifier(identifier),
+        Pattern::Literal(literal) => visitor.visit_expression(literal),
+        Pattern::Array(array) => visitor.visit_array_pattern(array),
+        Pattern::Hash(hash) => visitor.visit_hash_pattern(hash),
+    }
+}
+
+pub fn walk_array_pattern<V: Visitor + ?Sized>(visitor: &mut V, array: &ArrayPattern) {
+    for element in array.elements.iter() {
+        visitor.visit_pattern(element);
+    }
+    if let Some(ref rest) = array.rest {
+        visitor.visit_identifier(rest);
+    }
+}
+
+pub fn walk_hash_pattern<V: Visitor + ?Sized>(visitor: &mut V, hash: &HashPattern) {
+    for (key, value) in hash.pairs.iter() {
+        visitor.visit_expression(key);
+        visitor.visit_pattern(value);
+    }
+}
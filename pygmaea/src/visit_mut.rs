@@ -0,0 +1,354 @@
+use crate::ast::*;
+
+// VisitorMut is the counterpart of Visitor which walks the AST by mutable reference,
+// so that nodes can be rewritten in place.
+pub trait VisitorMut {
+    fn visit_program(&mut self, program: &mut Program) {
+        walk_program(self, program)
+    }
+
+    fn visit_statement(&mut self, statement: &mut Statement) {
+        walk_statement(self, statement)
+    }
+
+    fn visit_let_statement(&mut self, statement: &mut LetStatement) {
+        walk_let_statement(self, statement)
+    }
+
+    fn visit_return_statement(&mut self, statement: &mut ReturnStatement) {
+        walk_return_statement(self, statement)
+    }
+
+    fn visit_expression_statement(&mut self, statement: &mut ExpressionStatement) {
+        walk_expression_statement(self, statement)
+    }
+
+    fn visit_assign_statement(&mut self, statement: &mut AssignStatement) {
+        walk_assign_statement(self, statement)
+    }
+
+    fn visit_assign_target(&mut self, target: &mut AssignTarget) {
+        walk_assign_target(self, target)
+    }
+
+    fn visit_while_statement(&mut self, statement: &mut WhileStatement) {
+        walk_while_statement(self, statement)
+    }
+
+    fn visit_for_statement(&mut self, statement: &mut ForStatement) {
+        walk_for_statement(self, statement)
+    }
+
+    fn visit_break_statement(&mut self, _statement: &mut BreakStatement) {}
+
+    fn visit_continue_statement(&mut self, _statement: &mut ContinueStatement) {}
+
+    fn visit_block_statement(&mut self, block: &mut BlockStatement) {
+        walk_block_statement(self, block)
+    }
+
+    fn visit_expression(&mut self, expression: &mut Expression) {
+        walk_expression(self, expression)
+    }
+
+    fn visit_identifier(&mut self, _identifier: &mut Identifier) {}
+
+    fn visit_integer_literal(&mut self, _integer_literal: &mut IntegerLiteral) {}
+
+    fn visit_float_literal(&mut self, _float_literal: &mut FloatLiteral) {}
+
+    fn visit_boolean(&mut self, _boolean: &mut Boolean) {}
+
+    fn visit_string_literal(&mut self, _string: &mut StringLiteral) {}
+
+    fn visit_prefix_expression(&mut self, prefix: &mut PrefixExpression) {
+        walk_prefix_expression(self, prefix)
+    }
+
+    fn visit_infix_expression(&mut self, infix: &mut InfixExpression) {
+        walk_infix_expression(self, infix)
+    }
+
+    fn visit_if_expression(&mut self, if_expression: &mut IfExpression) {
+        walk_if_expression(self, if_expression)
+    }
+
+    fn visit_function_literal(&mut self, function: &mut FunctionLiteral) {
+        walk_function_literal(self, function)
+    }
+
+    fn visit_macro_literal(&mut self, macro_literal: &mut MacroLiteral) {
+        walk_macro_literal(self, macro_literal)
+    }
+
+    fn visit_call_expression(&mut self, call: &mut CallExpression) {
+        walk_call_expression(self, call)
+    }
+
+    fn visit_string_interpolation(&mut self, interpolation: &mut StringInterpolation) {
+        walk_string_interpolation(self, interpolation)
+    }
+
+    fn visit_array_literal(&mut self, array: &mut ArrayLiteral) {
+        walk_array_literal(self, array)
+    }
+
+    fn visit_index_expression(&mut self, index: &mut IndexExpression) {
+        walk_index_expression(self, index)
+    }
+
+    fn visit_hash_literal(&mut self, hash: &mut HashLiteral) {
+        walk_hash_literal(self, hash)
+    }
+
+    fn visit_range_expression(&mut self, range: &mut RangeExpression) {
+        walk_range_expression(self, range)
+    }
+
+    fn visit_match_expression(&mut self, match_expression: &mut MatchExpression) {
+        walk_match_expression(self, match_expression)
+    }
+
+    fn visit_match_arm(&mut self, arm: &mut MatchArm) {
+        walk_match_arm(self, arm)
+    }
+
+    fn visit_pattern(&mut self, pattern: &mut Pattern) {
+        walk_pattern(self, pattern)
+    }
+
+    fn visit_array_pattern(&mut self, array: &mut ArrayPattern) {
+        walk_array_pattern(self, array)
+    }
+
+    fn visit_hash_pattern(&mut self, hash: &mut HashPattern) {
+        walk_hash_pattern(self, hash)
+    }
+}
+
+pub fn walk_program<V: VisitorMut + ?Sized>(visitor: &mut V, program: &mut Program) {
+    for statement in program.iter_mut() {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_statement<V: VisitorMut + ?Sized>(visitor: &mut V, statement: &mut Statement) {
+    match statement {
+        Statement::Let(statement) => visitor.visit_let_statement(statement),
+        Statement::Return(statement) => visitor.visit_return_statement(statement),
+        Statement::Expression(statement) => visitor.visit_expression_statement(statement),
+        Statement::Assign(statement) => visitor.visit_assign_statement(statement),
+        Statement::While(statement) => visitor.visit_while_statement(statement),
+        Statement::Break(statement) => visitor.visit_break_statement(statement),
+        Statement::Continue(statement) => visitor.visit_continue_statement(statement),
+        Statement::For(statement) => visitor.visit_for_statement(statement),
+    }
+}
+
+pub fn walk_let_statement<V: VisitorMut + ?Sized>(visitor: &mut V, statement: &mut LetStatement) {
+    visitor.visit_identifier(&mut statement.identifier);
+    visitor.visit_expression(&mut statement.expression);
+}
+
+pub fn walk_return_statement<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut ReturnStatement,
+) {
+    visitor.visit_expression(&mut statement.return_value);
+}
+
+pub fn walk_expression_statement<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut ExpressionStatement,
+) {
+    visitor.visit_expression(&mut statement.expression);
+}
+
+pub fn walk_assign_statement<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut AssignStatement,
+) {
+    visitor.visit_assign_target(&mut statement.target);
+    visitor.visit_expression(&mut statement.value);
+}
+
+pub fn walk_assign_target<V: VisitorMut + ?Sized>(visitor: &mut V, target: &mut AssignTarget) {
+    match target {
+        AssignTarget::Identifier(identifier) => visitor.visit_identifier(identifier),
+        AssignTarget::Index(index) => visitor.visit_index_expression(index),
+    }
+}
+
+pub fn walk_while_statement<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut WhileStatement,
+) {
+    visitor.visit_expression(&mut statement.condition);
+    visitor.visit_block_statement(&mut statement.body);
+}
+
+pub fn walk_for_statement<V: VisitorMut + ?Sized>(visitor: &mut V, statement: &mut ForStatement) {
+    visitor.visit_identifier(&mut statement.key);
+    if let Some(ref mut value) = statement.value {
+        visitor.visit_identifier(value);
+    }
+    visitor.visit_expression(&mut statement.iterable);
+    visitor.visit_block_statement(&mut statement.body);
+}
+
+pub fn walk_block_statement<V: VisitorMut + ?Sized>(visitor: &mut V, block: &mut BlockStatement) {
+    visitor.visit_program(&mut block.statements);
+}
+
+pub fn walk_expression<V: VisitorMut + ?Sized>(visitor: &mut V, expression: &mut Expression) {
+    match expression {
+        Expression::Identifier(identifier) => visitor.visit_identifier(identifier),
+        Expression::Integer(integer_literal) => visitor.visit_integer_literal(integer_literal),
+        Expression::Float(float_literal) => visitor.visit_float_literal(float_literal),
+        Expression::Prefix(prefix) => visitor.visit_prefix_expression(prefix),
+        Expression::Infix(infix) => visitor.visit_infix_expression(infix),
+        Expression::Boolean(boolean) => visitor.visit_boolean(boolean),
+        Expression::If(if_expression) => visitor.visit_if_expression(if_expression),
+        Expression::Function(function) => visitor.visit_function_literal(function),
+        Expression::Call(call) => visitor.visit_call_expression(call),
+        Expression::String(string) => visitor.visit_string_literal(string),
+        Expression::StringInterpolation(interpolation) => {
+            visitor.visit_string_interpolation(interpolation)
+        }
+        Expression::Array(array) => visitor.visit_array_literal(array),
+        Expression::Index(index) => visitor.visit_index_expression(index),
+        Expression::Hash(hash) => visitor.visit_hash_literal(hash),
+        Expression::Range(range) => visitor.visit_range_expression(range),
+        Expression::Match(match_expression) => visitor.visit_match_expression(match_expression),
+        Expression::Macro(macro_literal) => visitor.visit_macro_literal(macro_literal),
+    }
+}
+
+pub fn walk_prefix_expression<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    prefix: &mut PrefixExpression,
+) {
+    visitor.visit_expression(&mut prefix.right);
+}
+
+pub fn walk_infix_expression<V: VisitorMut + ?Sized>(visitor: &mut V, infix: &mut InfixExpression) {
+    visitor.visit_expression(&mut infix.left);
+    visitor.visit_expression(&mut infix.right);
+}
+
+pub fn walk_if_expression<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    if_expression: &mut IfExpression,
+) {
+    visitor.visit_expression(&mut if_expression.condition);
+    visitor.visit_block_statement(&mut if_expression.consequence);
+    if let Some(ref mut alternative) = if_expression.alternative {
+        visitor.visit_block_statement(alternative);
+    }
+}
+
+pub fn walk_function_literal<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    function: &mut FunctionLiteral,
+) {
+    for parameter in function.parameters.iter_mut() {
+        visitor.visit_identifier(parameter);
+    }
+    visitor.visit_block_statement(&mut function.body);
+}
+
+pub fn walk_macro_literal<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    macro_literal: &mut MacroLiteral,
+) {
+    for parameter in macro_literal.parameters.iter_mut() {
+        visitor.visit_identifier(parameter);
+    }
+    visitor.visit_block_statement(&mut macro_literal.body);
+}
+
+pub fn walk_call_expression<V: VisitorMut + ?Sized>(visitor: &mut V, call: &mut CallExpression) {
+    visitor.visit_expression(&mut call.function);
+    for argument in call.arguments.iter_mut() {
+        visitor.visit_expression(argument);
+    }
+}
+
+pub fn walk_string_interpolation<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    interpolation: &mut StringInterpolation,
+) {
+    for part in interpolation.parts.iter_mut() {
+        match part {
+            StringPart::Text(_) => {}
+            StringPart::Expression(expression) => visitor.visit_expression(expression),
+        }
+    }
+}
+
+pub fn walk_array_literal<V: VisitorMut + ?Sized>(visitor: &mut V, array: &mut ArrayLiteral) {
+    for element in array.elements.iter_mut() {
+        visitor.visit_expression(element);
+    }
+}
+
+pub fn walk_index_expression<V: VisitorMut + ?Sized>(visitor: &mut V, index: &mut IndexExpression) {
+    visitor.visit_expression(&mut index.left);
+    visitor.visit_expression(&mut index.index);
+}
+
+pub fn walk_hash_literal<V: VisitorMut + ?Sized>(visitor: &mut V, hash: &mut HashLiteral) {
+    for (key, value) in hash.pairs.iter_mut() {
+        visitor.visit_expression(key);
+        visitor.visit_expression(value);
+    }
+}
+
+pub fn walk_range_expression<V: VisitorMut + ?Sized>(visitor: &mut V, range: &mut RangeExpression) {
+    visitor.visit_expression(&mut range.start);
+    visitor.visit_expression(&mut range.end);
+}
+
+pub fn walk_match_expression<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    match_expression: &mut MatchExpression,
+) {
+    visitor.visit_expression(&mut match_expression.subject);
+    for arm in match_expression.arms.iter_mut() {
+        visitor.visit_match_arm(arm);
+    }
+}
+
+pub fn walk_match_arm<V: VisitorMut + ?Sized>(visitor: &mut V, arm: &mut MatchArm) {
+    visitor.visit_pattern(&mut arm.pattern);
+    if let Some(ref mut guard) = arm.guard {
+        visitor.visit_expression(guard);
+    }
+    visitor.visit_expression(&mut arm.body);
+}
+
+pub fn walk_pattern<V: VisitorMut + ?Sized>(visitor: &mut V, pattern: &mut Pattern) {
+    match pattern {
+        Pattern::Wildcard(_) => {}
+        Pattern::Identifier(identifier) => visitor.visit_identifier(identifier),
+        Pattern::Literal(literal) => visitor.visit_expression(literal),
+        Pattern::Array(array) => visitor.visit_array_pattern(array),
+        Pattern::Hash(hash) => visitor.visit_hash_pattern(hash),
+    }
+}
+
+pub fn walk_array_pattern<V: VisitorMut + ?Sized>(visitor: &mut V, array: &mut ArrayPattern) {
+    for element in array.elements.iter_mut() {
+        visitor.visit_pattern(element);
+    }
+    if let Some(ref mut rest) = array.rest {
+        visitor.visit_identifier(rest);
+    }
+}
+
+pub fn walk_hash_pattern<V: VisitorMut + ?Sized>(visitor: &mut V, hash: &mut HashPattern) {
+    for (key, value) in hash.pairs.iter_mut() {
+        visitor.visit_expression(key);
+        visitor.visit_pattern(value);
+    }
+}
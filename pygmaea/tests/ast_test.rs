@@ -19,4 +19,46 @@ mod tests {
             format!("{}", program.get(0).unwrap())
         );
     }
+
+    fn setup_span_input() -> Vec<String> {
+        vec![
+            "let x = a + b;",
+            "  -a * (b)",
+            "return add(1,\n  2);",
+            "if (x) { y } else { z }",
+            "fn(x) {\n  x\n}",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_span_expect() -> Vec<(usize, usize)> {
+        vec![(0, 13), (2, 9), (0, 18), (0, 23), (0, 13)]
+    }
+
+    #[test]
+    fn test_span() {
+        use pygmaea::lexer::Lexer;
+        use pygmaea::parser::Parser;
+
+        setup_span_input()
+            .into_iter()
+            .zip(setup_span_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                assert!(parser.errors.is_empty(), "[{}] parser has errors", i);
+
+                let span = program.get(0).unwrap().span();
+                assert_eq!(
+                    expect,
+                    (span.start.offset, span.end.offset),
+                    "[{}] span wrong. got={:?}",
+                    i,
+                    span
+                );
+            });
+    }
 }
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use pygmaea::builtins::{look_up, BUILTINS};
+    use pygmaea::error::RuntimeErrorKind;
+    use pygmaea::object::Object;
+
+    fn array(elements: &[i64]) -> Object {
+        Object::Array(elements.iter().cloned().map(Object::Integer).collect())
+    }
+
+    fn call(name: &str, arguments: &[Object]) -> Result<Object, RuntimeErrorKind> {
+        match look_up(name) {
+            Some(Object::Builtin(builtin)) => (builtin.function)(arguments),
+            other => panic!("{} is not Builtin. got={:?}", name, other),
+        }
+    }
+
+    #[test]
+    fn test_builtins() {
+        let mut names = BUILTINS.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(vec!["first", "last", "len", "push", "puts", "rest"], names);
+        assert!(look_up("unknown").is_none());
+    }
+
+    fn setup_call_input() -> Vec<(&'static str, Vec<Object>)> {
+        vec![
+            ("len", vec![array(&[])]),
+            ("len", vec![array(&[1, 2, 3])]),
+            ("first", vec![array(&[1, 2, 3])]),
+            ("first", vec![array(&[])]),
+            ("last", vec![array(&[1, 2, 3])]),
+            ("last", vec![array(&[])]),
+            ("rest", vec![array(&[1, 2, 3])]),
+            ("rest", vec![array(&[1])]),
+            ("rest", vec![array(&[])]),
+            ("push", vec![array(&[1, 2]), Object::Integer(3)]),
+            ("push", vec![array(&[]), Object::Boolean(true)]),
+            ("puts", vec![Object::Integer(1), array(&[2])]),
+        ]
+    }
+
+    fn setup_call_expect() -> Vec<&'static str> {
+        vec![
+            "0",
+            "3",
+            "1",
+            "null",
+            "3",
+            "null",
+            "[2, 3]",
+            "[]",
+            "null",
+            "[1, 2, 3]",
+            "[true]",
+            "null",
+        ]
+    }
+
+    #[test]
+    fn test_call() {
+        setup_call_input()
+            .into_iter()
+            .zip(setup_call_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, ((name, arguments), expect))| {
+                let result = call(name, &arguments)
+                    .unwrap_or_else(|e| panic!("[{}] {} returned error. got={}", i, name, e));
+                assert_eq!(
+                    expect,
+                    result.to_string(),
+                    "[{}] {} returned wrong value",
+                    i,
+                    name
+                );
+            });
+    }
+
+    fn setup_call_error_input() -> Vec<(&'static str, Vec<Object>)> {
+        vec![
+            ("len", vec![]),
+            ("len", vec![array(&[]), array(&[])]),
+            ("len", vec![Object::Integer(1)]),
+            ("first", vec![Object::Boolean(true)]),
+            ("last", vec![Object::Null]),
+            ("rest", vec![Object::Integer(1)]),
+            ("push", vec![array(&[])]),
+            ("push", vec![Object::Integer(1), Object::Integer(2)]),
+        ]
+    }
+
+    fn setup_call_error_expect() -> Vec<RuntimeErrorKind> {
+        vec![
+            RuntimeErrorKind::WrongNumberOfArguments(1, 0),
+            RuntimeErrorKind::WrongNumberOfArguments(1, 2),
+            RuntimeErrorKind::UnsupportedArgument("len", "Integer"),
+            RuntimeErrorKind::UnsupportedArgument("first", "Boolean"),
+            RuntimeErrorKind::UnsupportedArgument("last", "Null"),
+            RuntimeErrorKind::UnsupportedArgument("rest", "Integer"),
+            RuntimeErrorKind::WrongNumberOfArguments(2, 1),
+            RuntimeErrorKind::UnsupportedArgument("push", "Integer"),
+        ]
+    }
+
+    #[test]
+    fn test_call_error() {
+        setup_call_error_input()
+            .into_iter()
+            .zip(setup_call_error_expect().into_iter())
+            .enumerate()
+            .for_each(
+                |(i, ((name, arguments), expect))| match call(name, &arguments) {
+                    Err(kind) => assert_eq!(expect, kind, "[{}] wrong error kind", i),
+                    Ok(result) => panic!("[{}] {} returned no error. got={}", i, name, result),
+                },
+            );
+    }
+}
@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+    use pygmaea::ast::*;
+    use pygmaea::cst::{parse, SyntaxElement, SyntaxNode};
+    use pygmaea::error::ParseError;
+    use pygmaea::lexer::Lexer;
+    use pygmaea::parser::Parser;
+
+    fn string(program: &[Statement]) -> String {
+        program
+            .iter()
+            .map(|statement| statement.to_string())
+            .collect()
+    }
+
+    // shape renders the nodes of a tree with the text of their tokens, leaving out trivia.
+    fn shape(node: &SyntaxNode) -> String {
+        let children: Vec<_> = node
+            .children
+            .iter()
+            .map(|child| match child {
+                SyntaxElement::Node(node) => shape(node),
+                SyntaxElement::Token(token) => format!("{:?}", token.text),
+            })
+            .collect();
+        format!("{}({})", node.kind, children.join(" "))
+    }
+
+    #[test]
+    fn test_parse_shape() {
+        vec![
+            (
+                "let x = 1 + 2; ",
+                r#"Program(LetStatement("let" Identifier("x") "=" InfixExpression(IntegerLiteral("1") "+" IntegerLiteral("2"))) ";" "")"#,
+            ),
+            (
+                "f(a)[0]",
+                r#"Program(ExpressionStatement(IndexExpression(CallExpression(Identifier("f") "(" Identifier("a") ")") "[" IntegerLiteral("0") "]")) "")"#,
+            ),
+            (
+                "while (x) { break; }",
+                r#"Program(WhileStatement("while" "(" Identifier("x") ")" BlockStatement("{" BreakStatement("break") ";" "}")) "")"#,
+            ),
+            (
+                "match (x) { [_, ...r] => r }",
+                r#"Program(ExpressionStatement(MatchExpression("match" "(" Identifier("x") ")" "{" MatchArm(ArrayPattern("[" WildcardPattern("_") "," "..." Identifier("r") "]") "=>" Identifier("r")) "}")) "")"#,
+            ),
+            (
+                "let = 1; x",
+                r#"Program("let" "=" "1" ";" ExpressionStatement(Identifier("x")) "")"#,
+            ),
+        ]
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, (input, expect))| {
+            let (tree, _) = parse(input);
+            assert_eq!(shape(&tree), expect, "tests[{}] - shape wrong", i);
+        });
+    }
+
+    fn setup_print_input() -> Vec<&'static str> {
+        vec![
+            "",
+            "   \n\t ",
+            "let x = 1;\n\nlet   y=x+ 2 ;\n",
+            "fn (a,b)  {\n  a +\tb\n}\r\n( 1 , 2 )",
+            "\"a\\n${ {\"k\" : x} [\"k\"] }b\"  \"\\u{1F600}\"",
+            "match (x) {\n  [a, ...rest] if a > 0 => rest,\n  _ => 0,\n}",
+            "let = ; ) } \"unterminated ${ x",
+            "é 1..2 & | . 0x 1e",
+            "/// doc\nlet x = 1; // line\n/* a /* nested */ b */ x /* unterminated",
+        ]
+    }
+
+    #[test]
+    fn test_parse_print() {
+        setup_print_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, input)| {
+                let (tree, _) = parse(input);
+                assert_eq!(tree.to_string(), input, "tests[{}] - print wrong", i);
+            });
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let (_, errors) = parse("let = 1; \"a");
+        let errors: Vec<_> = errors.iter().map(|error| error.to_string()).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "1:5: expected next token to be Ident, got Assign instead.",
+                "1:10: unterminated string.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lower() {
+        let input = "let add = fn(a, b) { a + b };\nadd(1, 2)  ";
+        let (tree, _) = parse(input);
+        let mut parser = Parser::new(Lexer::new(input.to_string()));
+        let program = parser.parse_program();
+        assert_eq!(string(&tree.lower().unwrap()), string(&program));
+
+        // renaming a token in the tree shows up in the lowered program.
+        let mut tree = tree;
+        rename(&mut tree, "add", "sum");
+        assert_eq!(
+            tree.to_string(),
+            "let sum = fn(a, b) { a + b };\nsum(1, 2)  "
+        );
+        assert_eq!(
+            string(&tree.lower().unwrap()),
+            "let sum = fn(a, b) { (a + b) };sum(1, 2)"
+        );
+
+        let (tree, _) = parse("let = 1;");
+        assert!(tree.lower().is_err(), "lowering has no errors");
+    }
+
+    fn rename(node: &mut SyntaxNode, from: &str, to: &str) {
+        for child in node.children.iter_mut() {
+            match child {
+                SyntaxElement::Node(node) => rename(node, from, to),
+                SyntaxElement::Token(token) if token.text == from => {
+                    token.text = to.to_string();
+                    token.token.literal = to.to_string();
+                }
+                SyntaxElement::Token(_) => {}
+            }
+        }
+    }
+
+    // Random is a xorshift generator, which keeps the property test reproducible
+    // without pulling in a crate for it.
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self, bound: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % bound as u64) as usize
+        }
+    }
+
+    const FRAGMENTS: [&str; 44] = [
+        "let", "fn", "if", "else", "return", "while", "for", "in", "break", "match", "macro", "x",
+        "_", "12", "0x1F", "3.5", "1e", "\"s\"", "\"a${", "}", "{", "(", ")", "[", "]", ",", ";",
+        ":", "=", "+=", "==", "=>", "..", "...", "-", "!", "&", "é", "\\", "\"", "//", "///", "/*",
+        "*/",
+    ];
+
+    const TRIVIA: [&str; 6] = ["", " ", "  ", "\n", "\t", "\r\n"];
+
+    fn arbitrary_input(random: &mut Random) -> String {
+        let mut input = String::new();
+        for _ in 0..random.next(40) {
+            input.push_str(TRIVIA[random.next(TRIVIA.len())]);
+            input.push_str(FRAGMENTS[random.next(FRAGMENTS.len())]);
+        }
+        input.push_str(TRIVIA[random.next(TRIVIA.len())]);
+        input
+    }
+
+    #[test]
+    fn test_parse_print_property() {
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+        for i in 0..2000 {
+            let input = arbitrary_input(&mut random);
+            let (tree, errors) = parse(&input);
+            assert_eq!(tree.to_string(), input, "tests[{}] - print wrong", i);
+
+            let mut parser = Parser::new(Lexer::new(input.clone()));
+            let program = parser.parse_program();
+            let messages = |errors: &[ParseError]| -> Vec<String> {
+                errors.iter().map(|error| error.to_string()).collect()
+            };
+            assert_eq!(
+                messages(&errors),
+                messages(&parser.errors),
+                "tests[{}] - errors wrong for {:?}",
+                i,
+                input
+            );
+            if errors.is_empty() {
+                let lowered = tree.lower().unwrap();
+                assert_eq!(string(&lowered), string(&program), "tests[{}] - lower", i);
+            }
+        }
+    }
+}
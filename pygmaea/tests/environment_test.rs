@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use pygmaea::environment::Environment;
+    use pygmaea::object::Object;
+
+    fn assert_integer(object: Option<Object>, expect: i64) {
+        match object {
+            Some(Object::Integer(value)) => assert_eq!(expect, value),
+            other => panic!("object is not Integer. got={:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut env = Environment::new();
+        assert!(env.get("x").is_none());
+
+        env.set("x".to_string(), Object::Integer(1));
+        assert_integer(env.get("x"), 1);
+
+        env.set("x".to_string(), Object::Integer(2));
+        assert_integer(env.get("x"), 2);
+    }
+
+    #[test]
+    fn test_enclosed() {
+        let mut outer = Environment::new();
+        outer.set("x".to_string(), Object::Integer(1));
+        outer.set("y".to_string(), Object::Integer(2));
+
+        let mut inner = Environment::new_enclosed(&outer);
+        inner.set("x".to_string(), Object::Integer(10));
+
+        assert_integer(inner.get("x"), 10);
+        assert_integer(inner.get("y"), 2);
+        assert_integer(outer.get("x"), 1);
+
+        // the inner scope sees bindings added to the outer scope later on.
+        outer.set("z".to_string(), Object::Integer(3));
+        assert_integer(inner.get("z"), 3);
+        assert!(outer.get("w").is_none());
+    }
+
+    #[test]
+    fn test_assign() {
+        let mut outer = Environment::new();
+        outer.set("x".to_string(), Object::Integer(1));
+        outer.set("y".to_string(), Object::Integer(2));
+
+        let mut inner = Environment::new_enclosed(&outer);
+        inner.set("y".to_string(), Object::Integer(20));
+
+        // assignment rebinds the innermost declaration only.
+        assert!(inner.assign("x", Object::Integer(10)));
+        assert!(inner.assign("y", Object::Integer(30)));
+        assert_integer(outer.get("x"), 10);
+        assert_integer(outer.get("y"), 2);
+        assert_integer(inner.get("y"), 30);
+
+        assert!(!inner.assign("z", Object::Integer(1)));
+        assert!(inner.get("z").is_none());
+    }
+}
@@ -0,0 +1,956 @@
+#[cfg(test)]
+mod tests {
+    use pygmaea::ast::Statement;
+    use pygmaea::environment::Environment;
+    use pygmaea::eval::eval;
+    use pygmaea::lexer::Lexer;
+    use pygmaea::object::Object;
+    use pygmaea::parser::Parser;
+
+    fn test_eval(input: String, i: usize) -> Object {
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+        assert!(
+            parser.errors.is_empty(),
+            "[{}] parser has errors. got={:?}",
+            i,
+            parser.errors
+        );
+        let mut env = Environment::new();
+        eval(&program, &mut env)
+    }
+
+    fn assert_integer_object(object: &Object, expect: i64, i: usize) {
+        match object {
+            Object::Integer(value) => assert_eq!(
+                expect, *value,
+                "[{}] object has wrong value. want={}, got={}",
+                i, expect, value
+            ),
+            other => panic!("[{}] object is not Integer. got={:?}", i, other),
+        }
+    }
+
+    fn assert_boolean_object(object: &Object, expect: bool, i: usize) {
+        match object {
+            Object::Boolean(value) => assert_eq!(
+                expect, *value,
+                "[{}] object has wrong value. want={}, got={}",
+                i, expect, value
+            ),
+            other => panic!("[{}] object is not Boolean. got={:?}", i, other),
+        }
+    }
+
+    fn assert_null_object(object: &Object, i: usize) {
+        match object {
+            Object::Null => {}
+            other => panic!("[{}] object is not Null. got={:?}", i, other),
+        }
+    }
+
+    fn setup_eval_integer_expression_input() -> Vec<(&'static str, i64)> {
+        vec![
+            ("5", 5),
+            ("10", 10),
+            ("-5", -5),
+            ("-10", -10),
+            ("5 + 5 + 5 + 5 - 10", 10),
+            ("2 * 2 * 2 * 2 * 2", 32),
+            ("-50 + 100 + -50", 0),
+            ("5 * 2 + 10", 20),
+            ("5 + 2 * 10", 25),
+            ("20 + 2 * -10", 0),
+            ("50 / 2 * 2 + 10", 60),
+            ("2 * (5 + 10)", 30),
+            ("3 * 3 * 3 + 10", 37),
+            ("3 * (3 * 3) + 10", 37),
+            ("(5 + 10 * 2 + 15 / 3) * 2 + -10", 50),
+        ]
+    }
+
+    #[test]
+    fn test_eval_integer_expression() {
+        setup_eval_integer_expression_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_integer_object(&evaluated, expect, i);
+            });
+    }
+
+    fn setup_eval_boolean_expression_input() -> Vec<(&'static str, bool)> {
+        vec![
+            ("true", true),
+            ("false", false),
+            ("1 < 2", true),
+            ("1 > 2", false),
+            ("1 < 1", false),
+            ("1 > 1", false),
+            ("1 == 1", true),
+            ("1 != 1", false),
+            ("1 == 2", false),
+            ("1 != 2", true),
+            ("true == true", true),
+            ("false == false", true),
+            ("true == false", false),
+            ("true != false", true),
+            ("(1 < 2) == true", true),
+            ("(1 < 2) == false", false),
+            ("(1 > 2) == true", false),
+            ("(1 > 2) == false", true),
+        ]
+    }
+
+    #[test]
+    fn test_eval_boolean_expression() {
+        setup_eval_boolean_expression_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_boolean_object(&evaluated, expect, i);
+            });
+    }
+
+    fn setup_bang_operator_input() -> Vec<(&'static str, bool)> {
+        vec![
+            ("!true", false),
+            ("!false", true),
+            ("!5", false),
+            ("!!true", true),
+            ("!!false", false),
+            ("!!5", true),
+        ]
+    }
+
+    #[test]
+    fn test_bang_operator() {
+        setup_bang_operator_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_boolean_object(&evaluated, expect, i);
+            });
+    }
+
+    fn setup_if_else_expression_input() -> Vec<(&'static str, Option<i64>)> {
+        vec![
+            ("if (true) { 10 }", Some(10)),
+            ("if (false) { 10 }", None),
+            ("if (1) { 10 }", Some(10)),
+            ("if (1 < 2) { 10 }", Some(10)),
+            ("if (1 > 2) { 10 }", None),
+            ("if (1 > 2) { 10 } else { 20 }", Some(20)),
+            ("if (1 < 2) { 10 } else { 20 }", Some(10)),
+        ]
+    }
+
+    #[test]
+    fn test_if_else_expression() {
+        setup_if_else_expression_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                match expect {
+                    Some(expect) => assert_integer_object(&evaluated, expect, i),
+                    None => assert_null_object(&evaluated, i),
+                }
+            });
+    }
+
+    fn setup_return_statement_input() -> Vec<(&'static str, i64)> {
+        vec![
+            ("return 10;", 10),
+            ("return 10; 9;", 10),
+            ("return 2 * 5; 9;", 10),
+            ("9; return 2 * 5; 9;", 10),
+            (
+                "if (10 > 1) {
+                    if (10 > 1) {
+                        return 10;
+                    }
+                    return 1;
+                }",
+                10,
+            ),
+            (
+                "let f = fn(x) {
+                    if (x > 1) {
+                        if (x > 2) { return x; }
+                    }
+                    return 0;
+                };
+                f(3) + f(2);",
+                3,
+            ),
+            (
+                "let f = fn() { let x = if (true) { return 5; }; 10 }; f()",
+                5,
+            ),
+            (
+                "let g = fn(a, b) { a + b }; let f = fn() { g(1, if (true) { return 5; }); 10 }; f()",
+                5,
+            ),
+            (
+                "let f = fn() { 1 + if (true) { return 5; } * 2; 10 }; f()",
+                5,
+            ),
+            ("let f = fn() { [1, if (true) { return 5; }]; 10 }; f()", 5),
+            (r#"let f = fn() { "${if (true) { return 5; }}"; 10 }; f()"#, 5),
+            ("let x = if (true) { return 5; }; 10", 5),
+        ]
+    }
+
+    #[test]
+    fn test_return_statement() {
+        setup_return_statement_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_integer_object(&evaluated, expect, i);
+            });
+    }
+
+    fn setup_error_handling_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("5 + true;", "1:1: type mismatch: Integer + Boolean."),
+            ("5 + true; 5;", "1:1: type mismatch: Integer + Boolean."),
+            ("-true", "1:1: unknown operator: -Boolean."),
+            ("true + false;", "1:1: unknown operator: Boolean + Boolean."),
+            (
+                "5; true + false; 5",
+                "1:4: unknown operator: Boolean + Boolean.",
+            ),
+            (
+                "if (10 > 1) { true + false; }",
+                "1:15: unknown operator: Boolean + Boolean.",
+            ),
+            (
+                "if (10 > 1) {
+                    if (10 > 1) {
+                        return true + false;
+                    }
+                    return 1;
+                }",
+                "3:32: unknown operator: Boolean + Boolean.",
+            ),
+            ("foobar", "1:1: identifier not found: foobar."),
+            ("5 / 0", "1:1: division by zero."),
+            ("9223372036854775807 + 1", "1:1: integer overflow."),
+            ("let x = 1; x(2)", "1:12: not a function: Integer."),
+            (
+                "fn(x) { x }()",
+                "1:1: wrong number of arguments. want=1, got=0.",
+            ),
+            (
+                "let add = fn(a, b) { a + b };\nlet twice = fn(x) { add(x, true) };\ntwice(1);",
+                "1:22: type mismatch: Integer + Boolean.\n    at add (2:21)\n    at twice (3:1)",
+            ),
+            (
+                "fn(x) { x / 0 }(1)",
+                "1:9: division by zero.\n    at <anonymous> (1:1)",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_error_handling() {
+        setup_error_handling_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                match evaluated {
+                    Object::Error(error) => assert_eq!(
+                        expect,
+                        error.to_string(),
+                        "[{}] wrong error message. expected={}, got={}",
+                        i,
+                        expect,
+                        error
+                    ),
+                    other => panic!("[{}] no error object returned. got={:?}", i, other),
+                }
+            });
+    }
+
+    #[test]
+    fn test_runtime_error_stack() {
+        use pygmaea::error::RuntimeErrorKind;
+
+        let input = "let inner = fn() { missing };
+        let outer = fn() { inner() };
+        outer();";
+        let evaluated = test_eval(input.to_string(), 0);
+        let error = match evaluated {
+            Object::Error(error) => error,
+            other => panic!("no error object returned. got={:?}", other),
+        };
+
+        assert_eq!(
+            RuntimeErrorKind::IdentifierNotFound("missing".to_string()),
+            error.kind
+        );
+        assert_eq!("identifier not found: missing.", error.message());
+        assert_eq!(
+            vec!["inner", "outer"],
+            error
+                .stack
+                .iter()
+                .map(|frame| frame.function.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            (2, 28),
+            (
+                error.stack[0].span.start.line,
+                error.stack[0].span.start.column
+            )
+        );
+        assert_eq!(
+            (3, 9),
+            (
+                error.stack[1].span.start.line,
+                error.stack[1].span.start.column
+            )
+        );
+    }
+
+    fn setup_let_statement_input() -> Vec<(&'static str, i64)> {
+        vec![
+            ("let a = 5; a;", 5),
+            ("let a = 5 * 5; a;", 25),
+            ("let a = 5; let b = a; b;", 5),
+            ("let a = 5; let b = a; let c = a + b + 5; c;", 15),
+        ]
+    }
+
+    #[test]
+    fn test_let_statement() {
+        setup_let_statement_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_integer_object(&evaluated, expect, i);
+            });
+    }
+
+    #[test]
+    fn test_function_object() {
+        let evaluated = test_eval("fn(x) { x + 2; };".to_string(), 0);
+        let function = match evaluated {
+            Object::Function(function) => function,
+            other => panic!("object is not Function. got={:?}", other),
+        };
+        assert_eq!(1, function.parameters.len());
+        assert_eq!("x", function.parameters[0].value);
+        assert_eq!("{ (x + 2) }", function.body.to_string());
+    }
+
+    fn setup_function_application_input() -> Vec<(&'static str, i64)> {
+        vec![
+            ("let identity = fn(x) { x; }; identity(5);", 5),
+            ("let identity = fn(x) { return x; }; identity(5);", 5),
+            ("let double = fn(x) { x * 2; }; double(5);", 10),
+            ("let add = fn(x, y) { x + y; }; add(5, 5);", 10),
+            ("let add = fn(x, y) { x + y; }; add(5 + 5, add(5, 5));", 20),
+            ("fn(x) { x; }(5)", 5),
+            (
+                "let fact = fn(n) { if (n < 2) { 1 } else { n * fact(n - 1) } }; fact(5);",
+                120,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_function_application() {
+        setup_function_application_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_integer_object(&evaluated, expect, i);
+            });
+    }
+
+    fn setup_closure_input() -> Vec<(&'static str, i64)> {
+        vec![
+            (
+                "let newAdder = fn(x) { fn(y) { x + y } }; let addTwo = newAdder(2); addTwo(2);",
+                4,
+            ),
+            (
+                "let adder = fn(x) { fn(y) { x + y } }; adder(1)(2) + adder(10)(20);",
+                33,
+            ),
+            (
+                "let counter = fn(x) { fn() { x } }; let a = counter(1); let b = counter(2); a() + b();",
+                3,
+            ),
+            (
+                "let compose = fn(f, g) { fn(x) { g(f(x)) } };
+                let inc = fn(x) { x + 1 };
+                let double = fn(x) { x * 2 };
+                compose(inc, double)(5);",
+                12,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_closure() {
+        setup_closure_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_integer_object(&evaluated, expect, i);
+            });
+    }
+
+    fn setup_shadowing_input() -> Vec<(&'static str, i64)> {
+        vec![
+            ("let x = 1; let f = fn(x) { x }; f(2);", 2),
+            ("let x = 1; let f = fn(x) { x }; f(2); x;", 1),
+            (
+                "let x = 1; let f = fn() { let x = 2; x }; f() * 10 + x;",
+                21,
+            ),
+            (
+                "let x = 1; let f = fn() { let x = x + 1; x }; f() + f() + x;",
+                5,
+            ),
+            ("let x = 1; let x = x + 1; x;", 2),
+            (
+                "let x = 1; let f = fn() { fn() { let x = 3; x } }; f()() + x;",
+                4,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_shadowing() {
+        setup_shadowing_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_integer_object(&evaluated, expect, i);
+            });
+    }
+
+    fn setup_builtin_function_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("puts(1, true)", "null"),
+            ("let f = len; f", "builtin function len"),
+            ("let len = fn(x) { x * 2 }; len(21)", "42"),
+            (
+                "len(1)",
+                "ERROR: 1:1: argument to `len` not supported, got Integer.",
+            ),
+            (
+                "len()",
+                "ERROR: 1:1: wrong number of arguments. want=1, got=0.",
+            ),
+            (
+                "let f = fn(x) { first(x) }; f(true)",
+                "ERROR: 1:17: argument to `first` not supported, got Boolean.\n    at f (1:29)",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_builtin_function() {
+        setup_builtin_function_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(
+                    expect,
+                    evaluated.to_string(),
+                    "[{}] wrong result. expected={}, got={}",
+                    i,
+                    expect,
+                    evaluated
+                );
+            });
+    }
+
+    fn setup_string_expression_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            (r#""Hello World!""#, "Hello World!"),
+            (r#""Hello" + " " + "World!""#, "Hello World!"),
+            (r#""a" == "a""#, "true"),
+            (r#""a" != "a""#, "false"),
+            (r#""a" < "b""#, "true"),
+            (r#""b" > "ab""#, "true"),
+            (r#"let name = "monkey"; "hi ${name}!""#, "hi monkey!"),
+            (
+                r#""${1 + 2} is ${true} and ${"nested ${-1}"}""#,
+                "3 is true and nested -1",
+            ),
+            (r#"let f = fn(x) { "<${x}>" }; f(f("a"))"#, "<<a>>"),
+            (r#"len("") + len("four") + len("日本")"#, "6"),
+            (
+                r#""a" - "b""#,
+                "ERROR: 1:1: unknown operator: String - String.",
+            ),
+            (r#""a" + 1"#, "ERROR: 1:1: type mismatch: String + Integer."),
+        ]
+    }
+
+    #[test]
+    fn test_string_expression() {
+        setup_string_expression_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+
+    fn setup_float_expression_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("3.5", "3.5"),
+            ("-1.5", "-1.5"),
+            ("1.5 + 1.5", "3.0"),
+            ("1 + 0.5", "1.5"),
+            ("0.5 * 4", "2.0"),
+            ("7 / 2", "3"),
+            ("7 / 2.0", "3.5"),
+            ("1e3 - 1", "999.0"),
+            ("0.1 < 1", "true"),
+            ("1 == 1.0", "true"),
+            ("2.0 != 2", "false"),
+            ("0xff + 0b1 + 0o7 + 1_000", "1263"),
+            ("1.0 / 0", "ERROR: 1:1: division by zero."),
+            ("1e308 * 10", "ERROR: 1:1: float overflow."),
+            (
+                "1.5 == true",
+                "ERROR: 1:1: type mismatch: Float == Boolean.",
+            ),
+            ("!1.5", "false"),
+        ]
+    }
+
+    #[test]
+    fn test_float_expression() {
+        setup_float_expression_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+
+    fn setup_comparison_and_logical_expression_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("1 <= 1", "true"),
+            ("2 <= 1", "false"),
+            ("1 >= 2", "false"),
+            ("1.5 >= 1", "true"),
+            (r#""a" <= "b""#, "true"),
+            ("7 % 3", "1"),
+            ("-7 % 3", "-1"),
+            ("7.5 % 2", "1.5"),
+            ("7 % 0", "ERROR: 1:1: division by zero."),
+            ("true && false", "false"),
+            ("true && 1", "true"),
+            ("false || 0", "true"),
+            ("false || false", "false"),
+            ("1 < 2 && 2 < 3", "true"),
+            ("false && undefined", "false"),
+            ("true || undefined", "true"),
+            (
+                "true && undefined",
+                "ERROR: 1:9: identifier not found: undefined.",
+            ),
+            (
+                "let calls = fn(x) { if (x) { puts(x) } x }; false && calls(true)",
+                "false",
+            ),
+            (
+                "true && true % 2",
+                "ERROR: 1:9: type mismatch: Boolean % Integer.",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_comparison_and_logical_expression() {
+        setup_comparison_and_logical_expression_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+
+    fn setup_array_expression_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("[1, 2 * 2, 3 + 3]", "[1, 4, 6]"),
+            ("[]", "[]"),
+            ("[1, 2, 3][0]", "1"),
+            ("[1, 2, 3][1 + 1]", "3"),
+            ("let i = 0; [1][i]", "1"),
+            ("let array = [1, 2, 3]; array[0] + array[1] + array[2]", "6"),
+            ("[[1, 2], [3]][0][1]", "2"),
+            (r#""héllo"[1]"#, "é"),
+            ("len([1, [2, 3]])", "2"),
+            ("rest(push([1, 2], 3))", "[2, 3]"),
+            ("let map = fn(f, xs) { if (len(xs) == 0) { [] } else { [f(first(xs))] } }; map(fn(x) { x * 2 }, [21])", "[42]"),
+            ("[1, 2, 3][3]", "ERROR: 1:1: index out of bounds: index=3, length=3."),
+            ("[1, 2, 3][-1]", "ERROR: 1:1: index out of bounds: index=-1, length=3."),
+            (r#""abc"[3]"#, "ERROR: 1:1: index out of bounds: index=3, length=3."),
+            ("[1][true]", "ERROR: 1:1: index operator not supported: Array[Boolean]."),
+            ("1[0]", "ERROR: 1:1: index operator not supported: Integer[Integer]."),
+        ]
+    }
+
+    #[test]
+    fn test_array_expression() {
+        setup_array_expression_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+
+    fn setup_hash_expression_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            (
+                r#"let two = "two"; {"one": 10 - 9, two: 1 + 1, "thr" + "ee": 6 / 2, 4: 4, true: 5, false: 6}"#,
+                r#"{"one": 1, "two": 2, "three": 3, 4: 4, true: 5, false: 6}"#,
+            ),
+            ("{}", "{}"),
+            (r#"{"b": 1, "a": 2, "b": 3}"#, r#"{"b": 3, "a": 2}"#),
+            (r#"{"foo": 5}["foo"]"#, "5"),
+            (r#"{"foo": 5}["bar"]"#, "null"),
+            (r#"let key = "foo"; {"foo": 5}[key]"#, "5"),
+            ("{5: 5}[5]", "5"),
+            ("{true: 5}[true]", "5"),
+            ("{false: 5}[false]", "5"),
+            ("{1: 5}[true]", "null"),
+            (r#"len({"a": 1, "b": 2})"#, "2"),
+            (
+                r#"{"name": "monkey"}[fn(x) { x }]"#,
+                "ERROR: 1:1: unusable as hash key: Function.",
+            ),
+            (
+                r#"{"ok": 1, [1]: 2}"#,
+                "ERROR: 1:11: unusable as hash key: Array.",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_hash_expression() {
+        setup_hash_expression_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+
+    fn setup_assign_statement_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("let x = 1; x = 2; x", "2"),
+            ("let x = 1; x = x + 1; x", "2"),
+            ("let x = 10; x += 5; x -= 3; x *= 2; x /= 4; x", "6"),
+            (r#"let s = "a"; s += "b"; s"#, "ab"),
+            ("let x = 1.5; x *= 2; x", "3.0"),
+            ("let x = 1; x = 2", "null"),
+            ("let arr = [1, 2, 3]; arr[0] = 10; arr", "[10, 2, 3]"),
+            ("let arr = [1, 2, 3]; arr[2] += 5; arr", "[1, 2, 8]"),
+            (
+                "let m = [[1, 2], [3, 4]]; m[1][0] = 9; m",
+                "[[1, 2], [9, 4]]",
+            ),
+            (
+                r#"let h = {"k": 1}; h["k"] = 2; h["n"] = 3; h"#,
+                r#"{"k": 2, "n": 3}"#,
+            ),
+            (r#"let h = {"k": [1]}; h["k"][0] += 1; h"#, r#"{"k": [2]}"#),
+            ("let a = [1]; let b = a; a[0] = 2; b", "[1]"),
+            (
+                "let a = [1, 2]; let f = fn() { a[1] = 9; 0 }; a[0] = f(); a",
+                "[0, 9]",
+            ),
+            ("let x = 1; let f = fn() { x = 10; 2 }; x += f(); x", "12"),
+            (
+                "let count = 0; let inc = fn() { count += 1 }; inc(); inc(); count",
+                "2",
+            ),
+            (
+                "let x = 1; let f = fn() { let x = 5; x = 6; x }; f() + x",
+                "7",
+            ),
+            (
+                "x = 1",
+                "ERROR: 1:1: assignment to undeclared identifier: x.",
+            ),
+            (
+                "let f = fn() { y = 1 }; f()",
+                "ERROR: 1:16: assignment to undeclared identifier: y.\n    at f (1:25)",
+            ),
+            (
+                "len = 1",
+                "ERROR: 1:1: assignment to undeclared identifier: len.",
+            ),
+            (
+                "let x = 1; x += true",
+                "ERROR: 1:12: type mismatch: Integer + Boolean.",
+            ),
+            (
+                "let a = [1]; a[1] = 2",
+                "ERROR: 1:14: index out of bounds: index=1, length=1.",
+            ),
+            (
+                r#"let s = "ab"; s[0] = "c""#,
+                "ERROR: 1:15: index assignment not supported: String.",
+            ),
+            (
+                "let h = {}; h[[1]] = 2",
+                "ERROR: 1:13: unusable as hash key: Array.",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_assign_statement() {
+        setup_assign_statement_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+
+    fn setup_while_statement_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("let i = 0; while (i < 10) { i += 1; }; i", "10"),
+            ("let i = 0; while (i < 10) { i += 1; }", "null"),
+            ("while (false) { 1 / 0; }; 5", "5"),
+            ("let i = 0; while (true) { i += 1; if (i == 5) { break; } }; i", "5"),
+            (
+                "let i = 0; let sum = 0; while (i < 10) { i += 1; if (i % 2 == 0) { continue; } sum += i; }; sum",
+                "25",
+            ),
+            (
+                "let i = 0; let n = 0; while (i < 3) { let j = 0; while (true) { j += 1; n += 1; if (j == 2) { break; } } i += 1; }; n",
+                "6",
+            ),
+            (
+                "let f = fn() { let i = 0; while (true) { i += 1; if (i == 3) { return i * 10; } } }; f()",
+                "30",
+            ),
+            (
+                "let i = 0; while (i < 3) { let f = fn() { return 1; }; i += f(); }; i",
+                "3",
+            ),
+            (
+                "let i = 0; while (i < 10) { let y = if (i == 3) { break; }; i += 1; } i",
+                "3",
+            ),
+            (
+                "let i = 0; let n = 0; while (i < 5) { i += 1; n += if (i % 2 == 0) { continue; } else { 1 }; } n",
+                "3",
+            ),
+            (
+                "let i = 0; while (true) { i += 1; [i, if (i == 4) { break; }]; } i",
+                "4",
+            ),
+            ("while (1 + true) { }", "ERROR: 1:8: type mismatch: Integer + Boolean."),
+        ]
+    }
+
+    #[test]
+    fn test_while_statement() {
+        setup_while_statement_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+
+    fn setup_for_statement_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("let sum = 0; for (x in [1, 2, 3]) { sum += x; }; sum", "6"),
+            ("let sum = 0; for (i, x in [10, 20]) { sum += i * x; }; sum", "20"),
+            ("let s = \"\"; for (ch in \"héllo\") { s = ch + s; }; s", "olléh"),
+            ("let n = 0; for (i, ch in \"ab\") { n = i; }; n", "1"),
+            (
+                "let ks = []; for (k in {\"a\": 1, \"b\": 2}) { ks = push(ks, k); }; ks",
+                "[a, b]",
+            ),
+            (
+                "let s = 0; for (k, v in {1: 10, 2: 20}) { s += k * v; }; s",
+                "50",
+            ),
+            ("let sum = 0; for (i in 0..5) { sum += i; }; sum", "10"),
+            ("let n = 0; for (i in 5..0) { n += 1; }; n", "0"),
+            ("let last = 0; for (i, x in 3..6) { last = i; }; last", "2"),
+            (
+                "let sum = 0; for (i in 0..100) { if (i == 5) { break; } if (i % 2 == 0) { continue; } sum += i; }; sum",
+                "4",
+            ),
+            (
+                "let find = fn(xs, y) { for (i, x in xs) { if (x == y) { return i; } } -1 }; find([4, 5, 6], 6)",
+                "2",
+            ),
+            (
+                "let id = fn(v) { v }; let last = 0; for (x in 0..10) { last = x; id(if (x == 2) { break; }); } last",
+                "2",
+            ),
+            (
+                "let sum = 0; for (x in 0..5) { sum += x * if (x == 1) { continue; } else { 1 }; } sum",
+                "9",
+            ),
+            ("for (x in []) { x }", "null"),
+            ("0..3", "0..3"),
+            ("for (x in 5) { }", "ERROR: 1:11: not iterable: Integer."),
+            ("0..true", "ERROR: 1:4: expected Integer, got Boolean."),
+        ]
+    }
+
+    #[test]
+    fn test_break_outside_loop() {
+        // the parser rejects these, so they are taken out of the body of a loop.
+        let input = "while (true) { break; continue; }".to_string();
+        let program = Parser::new(Lexer::new(input)).parse_program();
+        let body = match program[0] {
+            Statement::While(ref statement) => statement.body.statements.clone(),
+            ref other => panic!("statement is not While. got={:?}", other),
+        };
+        let expects = vec![
+            "ERROR: 1:16: break outside of a loop.",
+            "ERROR: 1:23: continue outside of a loop.",
+        ];
+        body.into_iter()
+            .zip(expects.into_iter())
+            .enumerate()
+            .for_each(|(i, (statement, expect))| {
+                let evaluated = eval(&[statement], &mut Environment::new());
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+
+    #[test]
+    fn test_for_statement() {
+        setup_for_statement_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+
+    fn setup_match_expression_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("match (0) { 0 => \"zero\", _ => \"other\" }", "zero"),
+            ("match (7) { 0 => \"zero\", _ => \"other\" }", "other"),
+            ("match (-1) { -1 => true, _ => false }", "true"),
+            ("match (2.0) { 2 => \"two\", _ => \"other\" }", "two"),
+            ("match (\"a\") { 1 => 1, \"a\" => 2, _ => 3 }", "2"),
+            ("match (5) { n => n * 2 }", "10"),
+            (
+                "let sum = fn(xs) { match (xs) { [] => 0, [x, ...rest] => x + sum(rest) } }; sum([1, 2, 3])",
+                "6",
+            ),
+            ("match ([1, 2, 3]) { [a, b] => 0, [a, b, c] => a + b + c }", "6"),
+            ("match ([1, 2, 3]) { [_, ...rest] => rest }", "[2, 3]"),
+            ("match ([1]) { [1, ..._] => true, _ => false }", "true"),
+            (
+                "match ({\"k\": 1, \"j\": [2, 3]}) { {\"k\": 2} => 0, {\"j\": [x, y]} => x * y }",
+                "6",
+            ),
+            ("match ({\"k\": 1}) { {\"other\": v} => v, _ => 0 }", "0"),
+            (
+                "match (5) { n if n > 10 => \"big\", n if n > 1 => \"medium\", _ => \"small\" }",
+                "medium",
+            ),
+            ("let x = 1; match (2) { x => x }; x", "1"),
+            (
+                "let f = fn(x) { while (true) { match (x) { 1 => if (true) { return 10; }, _ => 0 }; x -= 1; } }; f(3)",
+                "10",
+            ),
+            ("match (3) { 1 => 1, 2 => 2 }", "ERROR: 1:1: unmatched value: 3."),
+            (
+                "match ([1, 2]) { [x] => x }",
+                "ERROR: 1:1: unmatched value: [1, 2].",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_match_expression() {
+        setup_match_expression_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+
+    fn setup_quote_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("quote(5)", "QUOTE(5)"),
+            ("quote(5 + 8)", "QUOTE((5 + 8))"),
+            ("quote(foobar + barfoo)", "QUOTE((foobar + barfoo))"),
+            ("quote(unquote(4))", "QUOTE(4)"),
+            ("quote(unquote(4 + 4))", "QUOTE(8)"),
+            ("quote(8 + unquote(4 + 4))", "QUOTE((8 + 8))"),
+            ("quote(unquote(4 + 4) + 8)", "QUOTE((8 + 8))"),
+            ("let foobar = 8; quote(foobar)", "QUOTE(foobar)"),
+            ("let foobar = 8; quote(unquote(foobar))", "QUOTE(8)"),
+            ("quote(unquote(true == false))", "QUOTE(false)"),
+            ("quote(unquote(quote(4 + 4)))", "QUOTE((4 + 4))"),
+            (
+                "let q = quote(4 + 4); quote(unquote(4 + 4) + unquote(q))",
+                "QUOTE((8 + (4 + 4)))",
+            ),
+            (
+                "quote(unquote(\"a${1}\") + unquote([1, -2.5, {\"k\": false}]))",
+                "QUOTE((\"a1\" + [1, -2.5, {\"k\": false}]))",
+            ),
+            (
+                "quote(unquote(fn(x) { x }))",
+                "ERROR: 1:7: unable to unquote Function.",
+            ),
+            (
+                "quote(1, 2)",
+                "ERROR: 1:1: wrong number of arguments. want=1, got=2.",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_quote() {
+        setup_quote_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let evaluated = test_eval(input.to_string(), i);
+                assert_eq!(expect, evaluated.to_string(), "[{}] wrong result", i);
+            });
+    }
+}
@@ -0,0 +1,315 @@
+#[cfg(test)]
+mod tests {
+    use pygmaea::convert::{FromObject, IntoObject};
+    use pygmaea::error::{InterpreterError, RuntimeErrorKind};
+    use pygmaea::interpreter::{Interpreter, InterpreterBuilder};
+    use pygmaea::object::{Builtin, Object};
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_eval_str() {
+        let mut interpreter = Interpreter::new();
+        let result = interpreter.eval_str("let x = 5; x * 2").unwrap();
+        assert_eq!(10, i64::from_object(result).unwrap());
+
+        // globals persist across calls.
+        let result = interpreter.eval_str("x + 1").unwrap();
+        assert_eq!(6, i64::from_object(result).unwrap());
+        assert_eq!(
+            "5",
+            interpreter.get_global("x").unwrap().to_string(),
+            "x has wrong value"
+        );
+        assert!(interpreter.get_global("y").is_none());
+    }
+
+    #[test]
+    fn test_eval_str_error() {
+        let mut interpreter = Interpreter::new();
+        match interpreter.eval_str("let = 5;") {
+            Err(InterpreterError::Parse(errors)) => assert_eq!(1, errors.len()),
+            other => panic!("result is not parse error. got={:?}", other),
+        }
+        match interpreter.eval_str("5 + true") {
+            Err(error @ InterpreterError::Runtime(_)) => {
+                assert_eq!("1:1: type mismatch: Integer + Boolean.", error.to_string())
+            }
+            other => panic!("result is not runtime error. got={:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_register_fn() {
+        let mut interpreter = Interpreter::new();
+        interpreter.register_fn("add", |arguments| {
+            let (a, b) = match arguments {
+                [a, b] => (i64::from_object(a.clone())?, i64::from_object(b.clone())?),
+                _ => return Err(RuntimeErrorKind::WrongNumberOfArguments(2, arguments.len())),
+            };
+            Ok((a + b).into_object())
+        });
+
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        interpreter.register_fn("tick", move |_| {
+            counter.set(counter.get() + 1);
+            Ok(counter.get().into_object())
+        });
+
+        let result = interpreter
+            .eval_str("let f = fn(x) { add(x, tick()) }; f(10) + f(20)")
+            .unwrap();
+        assert_eq!("33", result.to_string());
+        assert_eq!(2, calls.get());
+
+        match interpreter.eval_str("add(1, true)") {
+            Err(InterpreterError::Runtime(error)) => {
+                assert_eq!("1:1: expected Integer, got Boolean.", error.to_string())
+            }
+            other => panic!("result is not runtime error. got={:?}", other),
+        }
+
+        interpreter.register_fn("fail", |_| {
+            Err(RuntimeErrorKind::Custom("service unavailable.".to_string()))
+        });
+        match interpreter.eval_str("fail()") {
+            Err(error) => assert_eq!("1:1: service unavailable.", error.to_string()),
+            other => panic!("result is not error. got={:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_set_global() {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_global("limit", 3);
+        interpreter.set_global("names", vec!["a", "b"]);
+        let result = interpreter.eval_str("len(names) + limit").unwrap();
+        assert_eq!("5", result.to_string());
+    }
+
+    #[test]
+    fn test_convert() {
+        let object = vec![1, 2, 3].into_object();
+        assert_eq!("[1, 2, 3]", object.to_string());
+        assert_eq!(vec![1, 2, 3], Vec::<i64>::from_object(object).unwrap());
+
+        assert!(bool::from_object(true.into_object()).unwrap());
+        assert_eq!(
+            "monkey",
+            String::from_object("monkey".into_object()).unwrap()
+        );
+        assert_eq!(
+            None,
+            Option::<i64>::from_object(None::<i64>.into_object()).unwrap()
+        );
+
+        let mut map = HashMap::new();
+        map.insert("b".to_string(), vec![true]);
+        map.insert("a".to_string(), vec![]);
+        let object = map.clone().into_object();
+        assert_eq!(r#"{"a": [], "b": [true]}"#, object.to_string());
+        assert_eq!(
+            map,
+            HashMap::<String, Vec<bool>>::from_object(object).unwrap()
+        );
+    }
+
+    fn setup_convert_error_input() -> Vec<Result<(), RuntimeErrorKind>> {
+        vec![
+            i64::from_object(Object::Boolean(true)).map(|_| ()),
+            bool::from_object(Object::Null).map(|_| ()),
+            String::from_object(Object::Integer(1)).map(|_| ()),
+            Vec::<i64>::from_object(vec![true].into_object()).map(|_| ()),
+            HashMap::<String, i64>::from_object(vec![1].into_object()).map(|_| ()),
+        ]
+    }
+
+    fn setup_convert_error_expect() -> Vec<&'static str> {
+        vec![
+            "expected Integer, got Boolean.",
+            "expected Boolean, got Null.",
+            "expected String, got Integer.",
+            "expected Integer, got Boolean.",
+            "expected Hash, got Array.",
+        ]
+    }
+
+    #[test]
+    fn test_convert_error() {
+        setup_convert_error_input()
+            .into_iter()
+            .zip(setup_convert_error_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (result, expect))| match result {
+                Err(kind) => assert_eq!(expect, kind.to_string(), "[{}] wrong error", i),
+                Ok(()) => panic!("[{}] conversion did not fail", i),
+            });
+    }
+
+    const FIBONACCI: &str = "
+        let fib = fn(n) { if (n < 2) { n } else { fib(n - 1) + fib(n - 2) } };
+        fib(15)
+    ";
+
+    fn setup_limits_input() -> Vec<(InterpreterBuilder, &'static str)> {
+        vec![
+            (Interpreter::builder().max_steps(100), FIBONACCI),
+            (Interpreter::builder().max_steps(100), "while (true) { }"),
+            (
+                Interpreter::builder().max_call_depth(50),
+                "let f = fn(x) { f(x + 1) }; f(0)",
+            ),
+            (Interpreter::builder().max_objects(1000), FIBONACCI),
+            (
+                Interpreter::builder().timeout(Duration::from_nanos(0)),
+                FIBONACCI,
+            ),
+            (
+                Interpreter::builder().max_call_depth(50),
+                "let f = fn(n) { if (n == 0) { 0 } else { n + f(n - 1) } }; f(100)",
+            ),
+        ]
+    }
+
+    fn setup_limits_expect() -> Vec<RuntimeErrorKind> {
+        vec![
+            RuntimeErrorKind::StepLimitExceeded(100),
+            RuntimeErrorKind::StepLimitExceeded(100),
+            RuntimeErrorKind::CallDepthExceeded(50),
+            RuntimeErrorKind::ObjectLimitExceeded(1000),
+            RuntimeErrorKind::DeadlineExceeded(Duration::from_nanos(0)),
+            RuntimeErrorKind::CallDepthExceeded(50),
+        ]
+    }
+
+    #[test]
+    fn test_limits() {
+        setup_limits_input()
+            .into_iter()
+            .zip(setup_limits_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, ((builder, input), expect))| {
+                let mut interpreter = builder.build();
+                match interpreter.eval_str(input) {
+                    Err(InterpreterError::Runtime(error)) => {
+                        assert_eq!(expect, error.kind, "[{}] wrong error kind", i)
+                    }
+                    other => panic!("[{}] result is not runtime error. got={:?}", i, other),
+                }
+            });
+    }
+
+    #[test]
+    fn test_no_limits_by_default() {
+        let mut interpreter = Interpreter::new();
+        let input = "let f = fn(n) { if (n == 0) { 0 } else { n + f(n - 1) } }; f(30)";
+        assert_eq!("465", interpreter.eval_str(input).unwrap().to_string());
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let mut interpreter = Interpreter::builder().max_depth(10).build();
+        match interpreter.eval_str("!!!!!!!!!!!!!!!!true") {
+            Err(InterpreterError::Parse(errors)) => assert_eq!(
+                errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+                vec!["1:11: nesting depth exceeded. max=10."]
+            ),
+            other => panic!("result is not parse error. got={:?}", other),
+        }
+        assert_eq!(
+            "true",
+            interpreter.eval_str("!!!!!!!!true").unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_limits_per_evaluation() {
+        let mut interpreter = Interpreter::builder()
+            .max_steps(100_000)
+            .max_call_depth(20)
+            .max_objects(100_000)
+            .timeout(Duration::from_secs(60))
+            .build();
+        assert_eq!(
+            "610",
+            interpreter.eval_str(FIBONACCI).unwrap().to_string(),
+            "fib(15) is within the limits"
+        );
+        // the budget is renewed for every call, so the same script fits again.
+        assert_eq!("610", interpreter.eval_str(FIBONACCI).unwrap().to_string());
+
+        match interpreter.eval_str("let f = fn(x) { f(x + 1) }; f(0)") {
+            Err(error) => assert!(
+                error
+                    .to_string()
+                    .starts_with("1:17: call depth exceeded. max=20.\n    at f (1:17)"),
+                "error has wrong message. got={}",
+                error
+            ),
+            other => panic!("result is not error. got={:?}", other),
+        }
+    }
+
+    // every scope below binds a function capturing it, which would leak the scope and
+    // the probe within it if the scope were not released once done with.
+    const SCOPES: &str = "
+        let f = fn(x) { let p = probe; let g = fn() { g }; x };
+        let m = fn(x) { match (x) { n => if (true) { let p = probe; let h = fn() { h }; n } } };
+        let i = 0;
+        while (i < 100) { f(i); m(i); i += 1; }
+        let keep = fn() { let p = probe; fn() { p } }();
+    ";
+
+    #[test]
+    fn test_scopes_are_released() {
+        let probe = Builtin::new("probe", |_| Ok(Object::Null));
+        let handle = Rc::clone(&probe.function);
+        let mut interpreter = Interpreter::new();
+        interpreter.set_global("probe", Object::Builtin(probe));
+        interpreter.eval_str(SCOPES).unwrap();
+
+        // the globals, the closure kept in them and the handle hold the probe.
+        assert_eq!(3, Rc::strong_count(&handle), "scopes were kept alive");
+        match interpreter.eval_str("keep()") {
+            Ok(Object::Builtin(builtin)) => assert_eq!("probe", builtin.name),
+            other => panic!("closure lost its scope. got={:?}", other),
+        }
+
+        drop(interpreter);
+        assert_eq!(1, Rc::strong_count(&handle), "globals were kept alive");
+    }
+
+    #[test]
+    fn test_macros() {
+        let mut interpreter = Interpreter::new();
+        interpreter
+            .eval_str(
+                "let unless = macro(c, a, b) { quote(if (!(unquote(c))) { unquote(a) } else { unquote(b) }) };",
+            )
+            .unwrap();
+
+        assert!(
+            interpreter.get_global("unless").is_none(),
+            "macro leaked into the globals"
+        );
+        assert_eq!(
+            "yes",
+            interpreter
+                .eval_str("unless(1 > 2, \"yes\", 1 / 0)")
+                .unwrap()
+                .to_string(),
+            "macro was not expanded"
+        );
+        match interpreter.eval_str("unless(true)") {
+            Err(InterpreterError::Runtime(error)) => assert_eq!(
+                RuntimeErrorKind::WrongNumberOfArguments(3, 1),
+                error.kind,
+                "wrong error kind"
+            ),
+            other => panic!("result is not runtime error. got={:?}", other),
+        }
+    }
+}
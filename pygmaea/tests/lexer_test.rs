@@ -10,7 +10,7 @@ mod tests {
         };
 
         let result = add(five, ten);
-        !-/*5;
+        !-/ *5;
         5 < 10 > 5;
 
         if (5 < 10) {
@@ -140,4 +140,404 @@ mod tests {
             );
         });
     }
+
+    fn setup_span_input() -> String {
+        "let x = 5;\n  x == 10 é;".to_string()
+    }
+
+    fn setup_span_expects() -> Vec<(TokenType, usize, usize, usize, usize)> {
+        use TokenType::*;
+        vec![
+            (Let, 0, 3, 1, 1),
+            (Ident, 4, 5, 1, 5),
+            (Assign, 6, 7, 1, 7),
+            (Int, 8, 9, 1, 9),
+            (Semicolon, 9, 10, 1, 10),
+            (Ident, 13, 14, 2, 3),
+            (Equal, 15, 17, 2, 5),
+            (Int, 18, 20, 2, 8),
+            (Illegal, 21, 23, 2, 11),
+            (Semicolon, 23, 24, 2, 12),
+            (EOF, 24, 24, 2, 13),
+        ]
+    }
+
+    #[test]
+    fn test_next_token_span() {
+        use pygmaea::lexer::Lexer;
+
+        let mut lexer = Lexer::new(setup_span_input());
+
+        setup_span_expects().into_iter().enumerate().for_each(
+            |(i, (token_type, start, end, line, column))| {
+                let token = lexer.next_token();
+                assert_eq!(
+                    token_type, token.token_type,
+                    "tests[{}] - tokentype wrong. expected={}, got={}",
+                    i, token_type, token.token_type
+                );
+                assert_eq!(
+                    (start, end),
+                    (token.span.start.offset, token.span.end.offset),
+                    "tests[{}] - offset wrong. expected={}..{}, got={:?}",
+                    i,
+                    start,
+                    end,
+                    token.span
+                );
+                assert_eq!(
+                    (line, column),
+                    (token.span.start.line, token.span.start.column),
+                    "tests[{}] - position wrong. expected={}:{}, got={}",
+                    i,
+                    line,
+                    column,
+                    token.span
+                );
+            },
+        );
+    }
+
+    fn setup_string_input() -> String {
+        r#""a${ {x} }b\n" "c${"d${e}"}" "#.to_string()
+    }
+
+    fn setup_string_expects() -> Vec<(TokenType, &'static str, usize, usize)> {
+        use TokenType::*;
+        vec![
+            (StringStart, "a", 0, 4),
+            (LBrace, "{", 5, 6),
+            (Ident, "x", 6, 7),
+            (RBrace, "}", 7, 8),
+            (StringEnd, "b\n", 9, 14),
+            (StringStart, "c", 15, 19),
+            (StringStart, "d", 19, 23),
+            (Ident, "e", 23, 24),
+            (StringEnd, "", 24, 26),
+            (StringEnd, "", 26, 28),
+            (EOF, "", 29, 29),
+        ]
+    }
+
+    #[test]
+    fn test_next_token_string() {
+        use pygmaea::lexer::Lexer;
+
+        let mut lexer = Lexer::new(setup_string_input());
+
+        setup_string_expects().into_iter().enumerate().for_each(
+            |(i, (token_type, literal, start, end))| {
+                let token = lexer.next_token();
+                assert_eq!(
+                    (token_type, literal),
+                    (token.token_type, token.literal.as_str()),
+                    "tests[{}] - token wrong",
+                    i
+                );
+                assert_eq!(
+                    (start, end),
+                    (token.span.start.offset, token.span.end.offset),
+                    "tests[{}] - offset wrong. expected={}..{}, got={:?}",
+                    i,
+                    start,
+                    end,
+                    token.span
+                );
+            },
+        );
+        assert!(lexer.take_errors().is_empty(), "lexer has errors");
+    }
+
+    fn setup_number_input() -> String {
+        "3.14 1e-9 2E5 0xFF 0o17 0b1010 1_000 1.e 0x1e+1 12abc 1..2".to_string()
+    }
+
+    fn setup_number_expects() -> Vec<(TokenType, &'static str)> {
+        use TokenType::*;
+        vec![
+            (Float, "3.14"),
+            (Float, "1e-9"),
+            (Float, "2E5"),
+            (Int, "0xFF"),
+            (Int, "0o17"),
+            (Int, "0b1010"),
+            (Int, "1_000"),
+            (Float, "1.e"),
+            (Int, "0x1e"),
+            (Plus, "+"),
+            (Int, "1"),
+            (Int, "12abc"),
+            (Int, "1"),
+            (DotDot, ".."),
+            (Int, "2"),
+            (EOF, ""),
+        ]
+    }
+
+    #[test]
+    fn test_next_token_number() {
+        use pygmaea::lexer::Lexer;
+
+        let mut lexer = Lexer::new(setup_number_input());
+
+        setup_number_expects()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (token_type, literal))| {
+                let token = lexer.next_token();
+                assert_eq!(
+                    (token_type, literal),
+                    (token.token_type, token.literal.as_str()),
+                    "tests[{}] - token wrong",
+                    i
+                );
+            });
+    }
+
+    fn setup_operator_input() -> String {
+        "a <= b >= c && d || e % f & g | h <".to_string()
+    }
+
+    fn setup_operator_expects() -> Vec<(TokenType, &'static str)> {
+        use TokenType::*;
+        vec![
+            (Ident, "a"),
+            (LessThanOrEqual, "<="),
+            (Ident, "b"),
+            (GreaterThanOrEqual, ">="),
+            (Ident, "c"),
+            (And, "&&"),
+            (Ident, "d"),
+            (Or, "||"),
+            (Ident, "e"),
+            (Percent, "%"),
+            (Ident, "f"),
+            (Illegal, "&"),
+            (Ident, "g"),
+            (Illegal, "|"),
+            (Ident, "h"),
+            (LessThan, "<"),
+            (EOF, ""),
+        ]
+    }
+
+    #[test]
+    fn test_next_token_operator() {
+        use pygmaea::lexer::Lexer;
+
+        let mut lexer = Lexer::new(setup_operator_input());
+
+        setup_operator_expects()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (token_type, literal))| {
+                let token = lexer.next_token();
+                assert_eq!(
+                    (token_type, literal),
+                    (token.token_type, token.literal.as_str()),
+                    "tests[{}] - token wrong",
+                    i
+                );
+            });
+    }
+
+    #[test]
+    fn test_next_token_assign() {
+        use pygmaea::lexer::Lexer;
+        use TokenType::*;
+
+        let mut lexer = Lexer::new("a += 1 -= *= /= = + / ==".to_string());
+        vec![
+            (Ident, "a"),
+            (PlusAssign, "+="),
+            (Int, "1"),
+            (MinusAssign, "-="),
+            (AsteriskAssign, "*="),
+            (SlashAssign, "/="),
+            (Assign, "="),
+            (Plus, "+"),
+            (Slash, "/"),
+            (Equal, "=="),
+            (EOF, ""),
+        ]
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, (token_type, literal))| {
+            let token = lexer.next_token();
+            assert_eq!(
+                (token_type, literal),
+                (token.token_type, token.literal.as_str()),
+                "tests[{}] - token wrong",
+                i
+            );
+        });
+    }
+
+    #[test]
+    fn test_next_token_pattern() {
+        use pygmaea::lexer::Lexer;
+        use TokenType::*;
+
+        let mut lexer = Lexer::new("match (x) { [a, ...b] => a, _ => 0..1 }".to_string());
+        vec![
+            (Match, "match"),
+            (LParen, "("),
+            (Ident, "x"),
+            (RParen, ")"),
+            (LBrace, "{"),
+            (LBracket, "["),
+            (Ident, "a"),
+            (Comma, ","),
+            (Ellipsis, "..."),
+            (Ident, "b"),
+            (RBracket, "]"),
+            (FatArrow, "=>"),
+            (Ident, "a"),
+            (Comma, ","),
+            (Ident, "_"),
+            (FatArrow, "=>"),
+            (Int, "0"),
+            (DotDot, ".."),
+            (Int, "1"),
+            (RBrace, "}"),
+            (EOF, ""),
+        ]
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, (token_type, literal))| {
+            let token = lexer.next_token();
+            assert_eq!(
+                (token_type, literal),
+                (token.token_type, token.literal.as_str()),
+                "tests[{}] - token wrong",
+                i
+            );
+        });
+    }
+
+    fn texts(trivia: &[pygmaea::token::Trivia]) -> Vec<&str> {
+        trivia.iter().map(|trivia| trivia.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_next_token_trivia() {
+        use pygmaea::lexer::Lexer;
+        use TokenType::*;
+
+        let mut lexer = Lexer::lossless("  let x\t= 1; \n\n  x \n".to_string());
+        vec![
+            (Let, vec!["  "], vec![" "]),
+            (Ident, vec![], vec!["\t"]),
+            (Assign, vec![], vec![" "]),
+            (Int, vec![], vec![]),
+            (Semicolon, vec![], vec![" "]),
+            (Ident, vec!["\n\n  "], vec![" "]),
+            (EOF, vec!["\n"], vec![]),
+        ]
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, (token_type, leading, trailing))| {
+            let token = lexer.next_token();
+            assert_eq!(
+                token_type, token.token_type,
+                "tests[{}] - tokentype wrong",
+                i
+            );
+            assert_eq!(
+                leading,
+                texts(&token.leading_trivia),
+                "tests[{}] - leading trivia wrong",
+                i
+            );
+            assert_eq!(
+                trailing,
+                texts(&token.trailing_trivia),
+                "tests[{}] - trailing trivia wrong",
+                i
+            );
+        });
+
+        let token = Lexer::new("  x ".to_string()).next_token();
+        assert!(token.leading_trivia.is_empty() && token.trailing_trivia.is_empty());
+    }
+
+    #[test]
+    fn test_next_token_comment() {
+        use pygmaea::lexer::Lexer;
+        use pygmaea::token::TriviaKind::*;
+
+        let input = "a // c\n b /* x /* y */ z */ c /// d\n//// e\nf / g /= h";
+        let mut lexer = Lexer::new(input.to_string());
+        vec![
+            (TokenType::Ident, "a", vec![]),
+            (TokenType::Ident, "b", vec![]),
+            (TokenType::Ident, "c", vec![]),
+            (TokenType::Ident, "f", vec![(DocComment, "/// d")]),
+            (TokenType::Slash, "/", vec![]),
+            (TokenType::Ident, "g", vec![]),
+            (TokenType::SlashAssign, "/=", vec![]),
+            (TokenType::Ident, "h", vec![]),
+            (TokenType::EOF, "", vec![]),
+        ]
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, (token_type, literal, leading))| {
+            let token = lexer.next_token();
+            assert_eq!(
+                (token_type, literal),
+                (token.token_type, token.literal.as_str()),
+                "tests[{}] - token wrong",
+                i
+            );
+            let trivia: Vec<_> = token
+                .leading_trivia
+                .iter()
+                .map(|trivia| (trivia.kind, trivia.text.as_str()))
+                .collect();
+            assert_eq!(trivia, leading, "tests[{}] - leading trivia wrong", i);
+        });
+        assert!(lexer.take_errors().is_empty(), "lexer has errors");
+
+        let mut lexer = Lexer::lossless(input.to_string());
+        let token = lexer.next_token();
+        assert_eq!(
+            token
+                .trailing_trivia
+                .iter()
+                .map(|trivia| (trivia.kind, trivia.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(Whitespace, " "), (LineComment, "// c")]
+        );
+        let token = lexer.next_token();
+        assert_eq!(vec!["\n "], texts(&token.leading_trivia));
+        assert_eq!(
+            vec![" ", "/* x /* y */ z */", " "],
+            texts(&token.trailing_trivia)
+        );
+        let token = lexer.next_token();
+        assert_eq!(vec![" "], texts(&token.trailing_trivia));
+        let token = lexer.next_token();
+        assert_eq!(
+            vec!["/// d", "\n", "//// e", "\n"],
+            texts(&token.leading_trivia)
+        );
+    }
+
+    #[test]
+    fn test_next_token_unterminated_comment() {
+        use pygmaea::error::LexError;
+        use pygmaea::lexer::Lexer;
+        use pygmaea::span::{Position, Span};
+
+        let mut lexer = Lexer::new("a /* b /* c */".to_string());
+        assert_eq!(TokenType::Ident, lexer.next_token().token_type);
+        assert_eq!(TokenType::EOF, lexer.next_token().token_type);
+        assert_eq!(
+            lexer.take_errors(),
+            vec![LexError::UnterminatedComment(Span::new(
+                Position::new(2, 1, 3),
+                Position::new(14, 1, 15)
+            ))]
+        );
+    }
 }
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use pygmaea::ast::*;
+    use pygmaea::environment::Environment;
+    use pygmaea::lexer::Lexer;
+    use pygmaea::limits::Limits;
+    use pygmaea::macro_expansion::{define_macros, expand_macros};
+    use pygmaea::object::Object;
+    use pygmaea::parser::Parser;
+
+    fn parse(input: &str) -> Program {
+        let mut parser = Parser::new(Lexer::new(input.to_string()));
+        let program = parser.parse_program();
+        assert!(
+            parser.errors.is_empty(),
+            "parser has errors. got={:?}",
+            parser.errors
+        );
+        program
+    }
+
+    #[test]
+    fn test_define_macros() {
+        let mut program = parse(
+            "
+            let number = 1;
+            let function = fn(x, y) { x + y };
+            let mymacro = macro(x, y) { x + y; };
+            ",
+        );
+        let mut env = Environment::new();
+        define_macros(&mut program, &mut env);
+
+        assert_eq!(2, program.len(), "wrong number of statements");
+        assert!(env.get("number").is_none(), "number should not be defined");
+        assert!(
+            env.get("function").is_none(),
+            "function should not be defined"
+        );
+        match env.get("mymacro") {
+            Some(Object::Macro(definition)) => {
+                assert_eq!(
+                    vec!["x", "y"],
+                    definition
+                        .parameters
+                        .iter()
+                        .map(|parameter| parameter.value.as_str())
+                        .collect::<Vec<_>>(),
+                    "parameters wrong"
+                );
+                assert_eq!("{ (x + y) }", definition.body.to_string(), "body wrong");
+            }
+            other => panic!("object is not Macro. got={:?}", other),
+        }
+    }
+
+    fn setup_expand_macros_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            (
+                "let infixExpression = macro() { quote(1 + 2); }; infixExpression();",
+                "(1 + 2)",
+            ),
+            (
+                "let reverse = macro(a, b) { quote(unquote(b) - unquote(a)); }; reverse(2 + 2, 10 - 5);",
+                "(10 - 5) - (2 + 2)",
+            ),
+            (
+                r#"
+                let unless = macro(condition, consequence, alternative) {
+                    quote(if (!(unquote(condition))) {
+                        unquote(consequence);
+                    } else {
+                        unquote(alternative);
+                    });
+                };
+                unless(10 > 5, puts("not greater"), puts("greater"));
+                "#,
+                r#"if (!(10 > 5)) { puts("not greater") } else { puts("greater") }"#,
+            ),
+            (
+                "let twice = macro(x) { quote(unquote(x) + unquote(x)) }; twice(twice(a));",
+                "(a + a) + (a + a)",
+            ),
+            (
+                "let code = macro(n) { let q = quote(n * 2); return q; }; fn() { code(1) }",
+                "fn() { n * 2 }",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_expand_macros() {
+        setup_expand_macros_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut program = parse(input);
+                let mut env = Environment::new();
+                define_macros(&mut program, &mut env);
+                let expanded = expand_macros(program, &env, Limits::default()).unwrap();
+
+                assert_eq!(
+                    string(&parse(expect)),
+                    string(&expanded),
+                    "[{}] expanded program wrong",
+                    i
+                );
+            });
+    }
+
+    fn setup_expand_macros_error_input() -> Vec<(&'static str, &'static str)> {
+        vec![
+            (
+                "let m = macro() { 1 };\nm();",
+                "2:1: macro must return a quote, got Integer.",
+            ),
+            (
+                "let m = macro(a) { quote(a) };\nm(1, 2);",
+                "2:1: wrong number of arguments. want=1, got=2.",
+            ),
+            (
+                "let m = macro() { quote(unquote(fn() { 1 })) };\nm();",
+                "1:25: unable to unquote Function.\n    at m (2:1)",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_expand_macros_error() {
+        setup_expand_macros_error_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut program = parse(input);
+                let mut env = Environment::new();
+                define_macros(&mut program, &mut env);
+                match expand_macros(program, &env, Limits::default()) {
+                    Err(error) => assert_eq!(expect, error.to_string(), "[{}] error wrong", i),
+                    Ok(program) => {
+                        panic!("[{}] expansion did not fail. got={}", i, string(&program))
+                    }
+                }
+            });
+    }
+}
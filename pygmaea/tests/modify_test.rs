@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use pygmaea::ast::*;
+    use pygmaea::lexer::Lexer;
+    use pygmaea::modify::modify_program;
+    use pygmaea::parser::Parser;
+
+    fn parse(input: &str) -> Program {
+        let mut parser = Parser::new(Lexer::new(input.to_string()));
+        let program = parser.parse_program();
+        assert!(
+            parser.errors.is_empty(),
+            "parser has errors. got={:?}",
+            parser.errors
+        );
+        program
+    }
+
+    fn turn_one_into_two(expression: Expression) -> Result<Expression, ()> {
+        match expression {
+            Expression::Integer(mut integer) if integer.value == 1 => {
+                integer.value = 2;
+                Ok(Expression::Integer(integer))
+            }
+            expression => Ok(expression),
+        }
+    }
+
+    fn setup_modify_input() -> Vec<&'static str> {
+        vec![
+            "1",
+            "1 + 1; -1",
+            "let x = 1; return 1;",
+            "x = 1; a[1] += 1;",
+            "while (1) { 1 }",
+            "for (x, y in 1..1) { 1 }",
+            "if (1) { 1 } else { 1 }",
+            "fn(x) { 1 }; macro(x) { 1 }",
+            "f(1)(1)",
+            r#""a${1}b""#,
+            "[1, [1]][1]",
+            "{1: 1, \"k\": {1: 1}}",
+            "match (1) { 1 if 1 => 1, [1, ...r] => 1, {1: [1]} => 1, _ => 1 }",
+        ]
+    }
+
+    #[test]
+    fn test_modify() {
+        setup_modify_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, input)| {
+                let modified = modify_program(parse(input), &mut turn_one_into_two).unwrap();
+                let expect = parse(&input.replace('1', "2"));
+                assert_eq!(
+                    string(&expect),
+                    string(&modified),
+                    "[{}] modified program wrong",
+                    i
+                );
+            });
+    }
+
+    #[test]
+    fn test_modify_order() {
+        let mut visited = vec![];
+        modify_program(parse("f(a + b, c)"), &mut |expression| {
+            visited.push(expression.to_string());
+            Ok::<_, ()>(expression)
+        })
+        .unwrap();
+
+        assert_eq!(
+            vec!["f", "a", "b", "(a + b)", "c", "f((a + b), c)"],
+            visited,
+            "children are not modified before their parent"
+        );
+    }
+
+    #[test]
+    fn test_modify_error() {
+        let mut visited = 0;
+        let result = modify_program(parse("a; boom; c"), &mut |expression| {
+            visited += 1;
+            match expression {
+                Expression::Identifier(ref identifier) if identifier.value == "boom" => {
+                    Err(identifier.value.clone())
+                }
+                expression => Ok(expression),
+            }
+        });
+
+        assert_eq!(Some("boom".to_string()), result.err(), "error wrong");
+        assert_eq!(2, visited, "modification did not stop at the error");
+    }
+}
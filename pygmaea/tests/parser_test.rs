@@ -2,7 +2,7 @@
 mod tests {
     use pygmaea::ast::*;
     use pygmaea::lexer::Lexer;
-    use pygmaea::parser::Parser;
+    use pygmaea::parser::{Parser, DEFAULT_MAX_DEPTH};
 
     fn setup_let_statement_input() -> Vec<String> {
         vec![
@@ -416,6 +416,33 @@ mod tests {
             "false",
             "3 > 5 == false",
             "3 < 5 == true",
+            "a + add(b * c) + d",
+            "add(a, b, 1, 2 * 3, 4 + 5, add(6, 7 * 8))",
+            "add(a + b + c * d / f + g)",
+            "fn(x) { x }(5)",
+            "add(a, b(c))",
+            "1 + (2 + 3) + 4",
+            "(5 + 5) * 2",
+            "2 / (5 + 5)",
+            "-(5 + 5)",
+            "!(true == true)",
+            "-(a + b)",
+            "((((a))))",
+            "(a * (b + (c - d))) / e",
+            "add((a + b), (c))",
+            "a <= b == c >= d",
+            "a % b * c + d % e",
+            "a || b && c",
+            "a && b || c && d",
+            "a == b && c != d || !e",
+            "a < b || a >= c + 1",
+            "a * [1, 2, 3, 4][b * c] * d",
+            "add(a * b[2], b[1], 2 * [1, 2][1])",
+            "-a[0]",
+            "f(x)[0][1]",
+            "0..n + 1",
+            "a..b < c..d",
+            "-a..len(b) * 2",
         ]
         .into_iter()
         .map(str::to_string)
@@ -440,6 +467,33 @@ mod tests {
             "false",
             "((3 > 5) == false)",
             "((3 < 5) == true)",
+            "((a + add((b * c))) + d)",
+            "add(a, b, 1, (2 * 3), (4 + 5), add(6, (7 * 8)))",
+            "add((((a + b) + ((c * d) / f)) + g))",
+            "fn(x) { x }(5)",
+            "add(a, b(c))",
+            "((1 + (2 + 3)) + 4)",
+            "((5 + 5) * 2)",
+            "(2 / (5 + 5))",
+            "(-(5 + 5))",
+            "(!(true == true))",
+            "(-(a + b))",
+            "a",
+            "((a * (b + (c - d))) / e)",
+            "add((a + b), c)",
+            "((a <= b) == (c >= d))",
+            "(((a % b) * c) + (d % e))",
+            "(a || (b && c))",
+            "((a && b) || (c && d))",
+            "(((a == b) && (c != d)) || (!e))",
+            "((a < b) || (a >= (c + 1)))",
+            "((a * ([1, 2, 3, 4][(b * c)])) * d)",
+            "add((a * (b[2])), (b[1]), (2 * ([1, 2][1])))",
+            "(-(a[0]))",
+            "((f(x)[0])[1])",
+            "(0..(n + 1))",
+            "((a..b) < (c..d))",
+            "((-a)..(len(b) * 2))",
         ]
         .into_iter()
         .map(str::to_string)
@@ -524,117 +578,1575 @@ mod tests {
             });
     }
 
-    fn assert_boolean_expression(expression: &Expression, expect: bool, i: usize) {
-        let boolean_expression = match expression {
-            Expression::Boolean(expression) => expression,
-            _ => panic!("[{}] expression is not Boolean. got={}", i, expression),
-        };
+    fn setup_if_expression_input() -> Vec<String> {
+        vec!["if (x < y) { x }", "if (x < y) { x } else { y }"]
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn setup_if_expression_expect() -> Vec<Option<&'static str>> {
+        vec![None, Some("y")]
+    }
+
+    #[test]
+    fn test_if_expression() {
+        let inputs = setup_if_expression_input();
+        let expects = setup_if_expression_expect();
+
+        inputs
+            .into_iter()
+            .zip(expects.into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                check_parser_errors(&parser, i);
+
+                assert_eq!(
+                    1,
+                    program.len(),
+                    "[{}] program statements does not contain 1 statements. got={}",
+                    i,
+                    program.len()
+                );
+
+                let expression_statement = match program.get(0) {
+                    Some(Statement::Expression(statement)) => statement,
+                    _ => panic!("[{}] program statement is not ExpressionStatement.", i),
+                };
+
+                let if_expression = match *expression_statement.expression {
+                    Expression::If(ref expression) => expression,
+                    _ => panic!(
+                        "[{}] expression is not IfExpression. got={}",
+                        i, expression_statement.expression
+                    ),
+                };
+
+                assert_infix_expression(
+                    &if_expression.condition,
+                    Concrete::String("x".to_string()),
+                    "<".to_string(),
+                    Concrete::String("y".to_string()),
+                    i,
+                );
+                assert_block_statement(&if_expression.consequence, "x", i);
+
+                match (expect, &if_expression.alternative) {
+                    (None, None) => {}
+                    (Some(expect), Some(alternative)) => {
+                        assert_block_statement(alternative, expect, i)
+                    }
+                    (_, alternative) => panic!(
+                        "[{}] alternative is not {:?}. got={:?}",
+                        i, expect, alternative
+                    ),
+                }
+            });
+    }
+
+    fn assert_block_statement(block: &BlockStatement, expect: &str, i: usize) {
         assert_eq!(
-            expect, boolean_expression.value,
-            "[{}] boolean value not {}. got={}",
-            i, expect, boolean_expression.value
+            1,
+            block.statements.len(),
+            "[{}] block statements does not contain 1 statements. got={}",
+            i,
+            block.statements.len()
         );
+        match block.statements.get(0) {
+            Some(Statement::Expression(statement)) => {
+                assert_identifier(&statement.expression, expect.to_string(), i)
+            }
+            other => panic!(
+                "[{}] block statement is not ExpressionStatement. got={:?}",
+                i, other
+            ),
+        }
+    }
+
+    fn setup_if_expression_string_input() -> Vec<String> {
+        vec![
+            "if (x < y) { x } else { y }",
+            "if (x < y) { if (!x) { let z = y; z } } else { y }",
+            "if (x) { return x; }",
+            "if (a) { } else { if (b) { c } else { d } }",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    #[test]
+    fn test_if_expression_string() {
+        let inputs = setup_if_expression_string_input();
+        inputs.into_iter().enumerate().for_each(|(i, input)| {
+            let mut parser = Parser::new(Lexer::new(input.clone()));
+            let program = parser.parse_program();
+            check_parser_errors(&parser, i);
+            assert!(parser.errors.is_empty(), "[{}] parser has errors", i);
+
+            assert_eq!(
+                input,
+                string(&program),
+                "[{}] expected={}, got={}",
+                i,
+                input,
+                string(&program)
+            );
+
+            let mut parser = Parser::new(Lexer::new(string(&program)));
+            let reparsed = parser.parse_program();
+            check_parser_errors(&parser, i);
+            assert_eq!(
+                string(&program),
+                string(&reparsed),
+                "[{}] round trip mismatch",
+                i
+            );
+        });
+    }
+
+    #[test]
+    fn test_function_literal_parsing() {
+        let inputs = vec!["fn(x, y) { x + y; }".to_string()];
+        inputs.into_iter().enumerate().for_each(|(i, input)| {
+            let mut parser = Parser::new(Lexer::new(input));
+            let program = parser.parse_program();
+            check_parser_errors(&parser, i);
+
+            assert_eq!(
+                1,
+                program.len(),
+                "[{}] program statements does not contain 1 statements. got={}",
+                i,
+                program.len()
+            );
+
+            let expression_statement = match program.get(0) {
+                Some(Statement::Expression(statement)) => statement,
+                _ => panic!("[{}] program statement is not ExpressionStatement.", i),
+            };
+
+            let function = match *expression_statement.expression {
+                Expression::Function(ref function) => function,
+                _ => panic!(
+                    "[{}] expression is not FunctionLiteral. got={}",
+                    i, expression_statement.expression
+                ),
+            };
+
+            assert_eq!(
+                2,
+                function.parameters.len(),
+                "[{}] function literal parameters wrong. want 2, got={}",
+                i,
+                function.parameters.len()
+            );
+            assert_eq!("x", function.parameters[0].value);
+            assert_eq!("y", function.parameters[1].value);
+
+            assert_eq!(
+                1,
+                function.body.statements.len(),
+                "[{}] function.body.statements has not 1 statements. got={}",
+                i,
+                function.body.statements.len()
+            );
+            match function.body.statements.get(0) {
+                Some(Statement::Expression(statement)) => assert_infix_expression(
+                    &statement.expression,
+                    Concrete::String("x".to_string()),
+                    "+".to_string(),
+                    Concrete::String("y".to_string()),
+                    i,
+                ),
+                other => panic!(
+                    "[{}] function body statement is not ExpressionStatement. got={:?}",
+                    i, other
+                ),
+            }
+        });
+    }
+
+    fn setup_function_parameter_parsing_input() -> Vec<String> {
+        vec!["fn() {};", "fn(x) {};", "fn(x, y, z) {};"]
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn setup_function_parameter_parsing_expect() -> Vec<Vec<&'static str>> {
+        vec![vec![], vec!["x"], vec!["x", "y", "z"]]
+    }
+
+    #[test]
+    fn test_function_parameter_parsing() {
+        let inputs = setup_function_parameter_parsing_input();
+        let expects = setup_function_parameter_parsing_expect();
+
+        inputs
+            .into_iter()
+            .zip(expects.into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                check_parser_errors(&parser, i);
+
+                let function = match program.get(0) {
+                    Some(Statement::Expression(statement)) => match *statement.expression {
+                        Expression::Function(ref function) => function,
+                        _ => panic!(
+                            "[{}] expression is not FunctionLiteral. got={}",
+                            i, statement.expression
+                        ),
+                    },
+                    _ => panic!("[{}] program statement is not ExpressionStatement.", i),
+                };
+
+                assert_eq!(
+                    expect.len(),
+                    function.parameters.len(),
+                    "[{}] length parameters wrong. want {}, got={}",
+                    i,
+                    expect.len(),
+                    function.parameters.len()
+                );
+                expect
+                    .iter()
+                    .zip(function.parameters.iter())
+                    .for_each(|(expect, parameter)| assert_eq!(*expect, parameter.value));
+            });
+    }
+
+    #[test]
+    fn test_call_expression_parsing() {
+        let inputs = vec!["add(1, 2 * 3, 4 + 5);".to_string()];
+        inputs.into_iter().enumerate().for_each(|(i, input)| {
+            let mut parser = Parser::new(Lexer::new(input));
+            let program = parser.parse_program();
+            check_parser_errors(&parser, i);
+
+            assert_eq!(
+                1,
+                program.len(),
+                "[{}] program statements does not contain 1 statements. got={}",
+                i,
+                program.len()
+            );
+
+            let expression_statement = match program.get(0) {
+                Some(Statement::Expression(statement)) => statement,
+                _ => panic!("[{}] program statement is not ExpressionStatement.", i),
+            };
+
+            let call = match *expression_statement.expression {
+                Expression::Call(ref call) => call,
+                _ => panic!(
+                    "[{}] expression is not CallExpression. got={}",
+                    i, expression_statement.expression
+                ),
+            };
+
+            assert_identifier(&call.function, "add".to_string(), i);
+            assert_eq!(
+                3,
+                call.arguments.len(),
+                "[{}] wrong length of arguments. got={}",
+                i,
+                call.arguments.len()
+            );
+            Concrete::Integer(1).assert_literal_expression(&call.arguments[0], i);
+            assert_infix_expression(
+                &call.arguments[1],
+                Concrete::Integer(2),
+                "*".to_string(),
+                Concrete::Integer(3),
+                i,
+            );
+            assert_infix_expression(
+                &call.arguments[2],
+                Concrete::Integer(4),
+                "+".to_string(),
+                Concrete::Integer(5),
+                i,
+            );
+        });
+    }
+
+    fn setup_unclosed_paren_input() -> Vec<String> {
+        vec!["(1 + 2", "((a)", "add(1, 2", "if (x { y }", "fn(x, y { x }"]
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn setup_unclosed_paren_expect() -> Vec<usize> {
+        vec![0, 0, 3, 3, 2]
+    }
+
+    #[test]
+    fn test_unclosed_paren() {
+        use pygmaea::error::{ParseError, ParseExpressionError};
+
+        let inputs = setup_unclosed_paren_input();
+        let expects = setup_unclosed_paren_expect();
+        inputs
+            .into_iter()
+            .zip(expects.into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                parser.parse_program();
+
+                assert!(
+                    parser.errors.iter().any(|e| match e {
+                        ParseError::Expression(ParseExpressionError::UnclosedParen(paren, _)) => {
+                            paren.literal == "(" && paren.span.start.offset == expect
+                        }
+                        _ => false,
+                    }),
+                    "[{}] parser errors does not contain UnclosedParen. got={:?}",
+                    i,
+                    parser.errors
+                );
+            });
+    }
+
+    fn setup_parse_error_span_input() -> Vec<String> {
+        vec!["let = 5;", "let x 5;", "5 + ;", "\n  )"]
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn setup_parse_error_span_expect() -> Vec<&'static str> {
+        vec![
+            "1:5: expected next token to be Ident, got Assign instead.",
+            "1:7: expected next token to be Assign, got Int instead.",
+            "1:5: no prefix parse for Semicolon.",
+            "2:3: no prefix parse for RParen.",
+        ]
+    }
+
+    #[test]
+    fn test_parse_error_span() {
+        let inputs = setup_parse_error_span_input();
+        let expects = setup_parse_error_span_expect();
+        inputs
+            .into_iter()
+            .zip(expects.into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                parser.parse_program();
+
+                let error = parser.errors.first();
+                assert!(error.is_some(), "[{}] parser has no errors", i);
+                assert_eq!(
+                    expect,
+                    error.unwrap().to_string(),
+                    "[{}] error message wrong",
+                    i
+                );
+            });
+    }
+
+    fn setup_parse_error_recovery_input() -> Vec<String> {
+        vec![
+            "let = 5; let x = 10;",
+            "let x 5; let y = 1;",
+            "5 + ; 6 + ;",
+            "let x = 5 +\nlet y = 6;",
+            "if (x { y } let z = 1;",
+            "if (x) { let = 5; y } else { return ; }",
+            "fn(x, 1) { x }; let ok = 1;",
+            "add(1, 2; let ok = 1;",
+            "} let ok = 1;",
+            "let a = 1;; let b = 2;",
+            "if (x) { let y = ",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_parse_error_recovery_expect() -> Vec<(&'static str, Vec<&'static str>)> {
+        vec![
+            (
+                "let x = 10;",
+                vec!["1:5: expected next token to be Ident, got Assign instead."],
+            ),
+            (
+                "let y = 1;",
+                vec!["1:7: expected next token to be Assign, got Int instead."],
+            ),
+            (
+                "",
+                vec![
+                    "1:5: no prefix parse for Semicolon.",
+                    "1:11: no prefix parse for Semicolon.",
+                ],
+            ),
+            ("let y = 6;", vec!["2:1: no prefix parse for Let."]),
+            (
+                "let z = 1;",
+                vec!["1:7: unclosed LParen opened at 1:4. expected RParen, got LBrace instead."],
+            ),
+            (
+                "if (x) { y } else { }",
+                vec![
+                    "1:14: expected next token to be Ident, got Assign instead.",
+                    "1:37: no prefix parse for Semicolon.",
+                ],
+            ),
+            (
+                "let ok = 1;",
+                vec!["1:7: expected next token to be Ident, got Int instead."],
+            ),
+            (
+                "let ok = 1;",
+                vec!["1:9: unclosed LParen opened at 1:4. expected RParen, got Semicolon instead."],
+            ),
+            ("let ok = 1;", vec!["1:1: no prefix parse for RBrace."]),
+            (
+                "let a = 1;let b = 2;",
+                vec!["1:11: no prefix parse for Semicolon."],
+            ),
+            ("", vec!["1:18: no prefix parse for EOF."]),
+        ]
+    }
+
+    #[test]
+    fn test_parse_error_recovery() {
+        let inputs = setup_parse_error_recovery_input();
+        let expects = setup_parse_error_recovery_expect();
         assert_eq!(
-            expect.to_string(),
-            boolean_expression.token_literal(),
-            "[{}] literal token_literal not {}. got={}",
-            i,
-            expect.to_string(),
-            boolean_expression.token_literal()
+            inputs.len(),
+            expects.len(),
+            "inputs.len and expects.len is mismatch"
         );
+
+        inputs
+            .into_iter()
+            .zip(expects.into_iter())
+            .enumerate()
+            .for_each(|(i, (input, (expect_program, expect_errors)))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+
+                assert_eq!(
+                    expect_program,
+                    string(&program),
+                    "[{}] recovered program wrong",
+                    i
+                );
+                assert_eq!(
+                    expect_errors,
+                    parser
+                        .errors
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>(),
+                    "[{}] parser errors wrong",
+                    i
+                );
+            });
+    }
+
+    fn assert_boolean_expression(expression: &Expression, expect: bool, i: usize) {
+        let boolean_expression = match expression {
+            Expression::Boolean(expression) => expression,
+            _ => panic!("[{}] expression is not Boolean. got={}", i, expression),
+        };
+        assert_eq!(
+            expect, boolean_expression.value,
+            "[{}] boolean value not {}. got={}",
+            i, expect, boolean_expression.value
+        );
+        assert_eq!(
+            expect.to_string(),
+            boolean_expression.token_literal(),
+            "[{}] literal token_literal not {}. got={}",
+            i,
+            expect.to_string(),
+            boolean_expression.token_literal()
+        );
+    }
+
+    fn assert_integer_literal(expression: &Expression, value: i64, i: usize) {
+        let integer_literal = match expression {
+            Expression::Integer(literal) => literal,
+            _ => panic!("[{}] expression not IntegerLiteral. got={}", i, expression),
+        };
+
+        assert_eq!(
+            value, integer_literal.value,
+            "[{}] literal value not {}. got={}",
+            i, value, integer_literal.value
+        );
+        assert_eq!(
+            value.to_string(),
+            integer_literal.token_literal(),
+            "[{}] literal token_literal not {}. got={}",
+            i,
+            value.to_string(),
+            integer_literal.token_literal()
+        );
+    }
+
+    fn assert_identifier(expression: &Expression, value: String, i: usize) {
+        let identifier = match expression {
+            Expression::Identifier(identifier) => identifier,
+            _ => panic!("[{}] expression not Identifier. got={}", i, expression),
+        };
+        assert_eq!(
+            identifier.value, value,
+            "[{}] identifier.value not {}. got={}",
+            i, value, identifier.value
+        );
+        assert_eq!(
+            identifier.token_literal(),
+            value,
+            "[{}] identifier.token_literal() not {}. got={}",
+            i,
+            value,
+            identifier.token_literal()
+        );
+    }
+
+    fn assert_boolean_literal(expression: &Expression, value: bool, i: usize) {
+        let boolean = match expression {
+            Expression::Boolean(boolean) => boolean,
+            _ => panic!("[{}] expression not boolean. got={}", i, expression),
+        };
+        assert_eq!(
+            value, boolean.value,
+            "[{}] expression.value not {}. got={}",
+            i, value, boolean.value
+        );
+        assert_eq!(
+            value.to_string(),
+            boolean.token_literal(),
+            "[{}] expression.token_literal() not {}. got={}",
+            i,
+            value.to_string(),
+            boolean.token_literal()
+        );
+    }
+
+    fn check_parser_errors(parser: &Parser, i: usize) {
+        if parser.errors.is_empty() {
+            return;
+        }
+
+        eprintln!("[{}] parser has {} errors", i, parser.errors.len());
+        parser
+            .errors
+            .iter()
+            .enumerate()
+            .for_each(|(i, err)| eprintln!("[{}] parser error: {}", i, err));
+    }
+
+    trait Wrapped {
+        fn assert_literal_expression(self, expression: &Expression, i: usize);
+    }
+
+    enum Concrete {
+        Integer(i64),
+        String(String),
+        Boolean(bool),
+    }
+
+    impl Wrapped for Concrete {
+        fn assert_literal_expression(self, expression: &Expression, i: usize) {
+            match self {
+                Concrete::Integer(v) => assert_integer_literal(expression, v, i),
+                Concrete::String(v) => assert_identifier(expression, v, i),
+                Concrete::Boolean(v) => assert_boolean_literal(expression, v, i),
+            }
+        }
+    }
+
+    fn setup_string_literal_expression_input() -> Vec<String> {
+        vec![
+            r#""hello world";"#,
+            r#""""#,
+            r#""a\tb\n\"c\" \\ \u{48}\u{1F600} \$x""#,
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_string_literal_expression_expect() -> Vec<&'static str> {
+        vec!["hello world", "", "a\tb\n\"c\" \\ H\u{1F600} $x"]
+    }
+
+    #[test]
+    fn test_string_literal_expression() {
+        setup_string_literal_expression_input()
+            .into_iter()
+            .zip(setup_string_literal_expression_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                check_parser_errors(&parser, i);
+
+                assert_eq!(1, program.len(), "[{}] program has wrong length", i);
+                match program[0] {
+                    Statement::Expression(ref statement) => match *statement.expression {
+                        Expression::String(ref string) => {
+                            assert_eq!(expect, string.value, "[{}] string has wrong value", i)
+                        }
+                        ref other => panic!("[{}] expression is not String. got={}", i, other),
+                    },
+                    ref other => panic!("[{}] statement is not Expression. got={}", i, other),
+                }
+            });
+    }
+
+    fn setup_string_interpolation_input() -> Vec<String> {
+        vec![
+            r#""a ${x} b""#,
+            r#""${x + 1}""#,
+            r#""${a}${b}""#,
+            r#""outer ${"inner ${x}"} ${fn(y) { y }(1)}""#,
+            r#""line\n ${"\"q\""} \${literal}""#,
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_string_interpolation_expect() -> Vec<&'static str> {
+        vec![
+            r#""a ${x} b""#,
+            r#""${(x + 1)}""#,
+            r#""${a}${b}""#,
+            r#""outer ${"inner ${x}"} ${fn(y) { y }(1)}""#,
+            r#""line\n ${"\"q\""} \${literal}""#,
+        ]
+    }
+
+    #[test]
+    fn test_string_interpolation() {
+        setup_string_interpolation_input()
+            .into_iter()
+            .zip(setup_string_interpolation_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                check_parser_errors(&parser, i);
+
+                assert_eq!(expect, string(&program), "[{}] program string wrong", i);
+            });
+    }
+
+    fn setup_string_error_input() -> Vec<String> {
+        vec![
+            r#"let s = "abc"#,
+            r#"let s = "a\qb"; let ok = 1;"#,
+            r#""\u{110000}" + "\u{zz}""#,
+            r#""${x"#,
+            r#""${}""#,
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_string_error_expect() -> Vec<(&'static str, Vec<&'static str>)> {
+        vec![
+            (r#"let s = "abc";"#, vec!["1:9: unterminated string."]),
+            (
+                r#"let s = "ab";let ok = 1;"#,
+                vec![r"1:11: invalid escape sequence \q."],
+            ),
+            (
+                r#"("" + "zz}")"#,
+                vec![
+                    r"1:2: invalid escape sequence \u{110000}.",
+                    r"1:17: invalid escape sequence \u{.",
+                ],
+            ),
+            (
+                "",
+                vec!["1:5: expected next token to be StringEnd, got EOF instead."],
+            ),
+            ("", vec!["1:4: no prefix parse for StringEnd."]),
+        ]
+    }
+
+    #[test]
+    fn test_string_error() {
+        setup_string_error_input()
+            .into_iter()
+            .zip(setup_string_error_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, (expect_program, expect_errors)))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+
+                assert_eq!(
+                    expect_program,
+                    string(&program),
+                    "[{}] recovered program wrong",
+                    i
+                );
+                assert_eq!(
+                    expect_errors,
+                    parser
+                        .errors
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>(),
+                    "[{}] parser errors wrong",
+                    i
+                );
+            });
+    }
+
+    fn setup_number_literal_input() -> Vec<String> {
+        vec![
+            "0xff + 0o17 + 0b1010 + 1_000",
+            "3.14 * 1e-9",
+            "2.5e3 - 1_0.0_1",
+            "9223372036854775807",
+            "0x7fff_ffff_ffff_ffff",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_number_literal_expect() -> Vec<&'static str> {
+        vec![
+            "(((255 + 15) + 10) + 1000)",
+            "(3.14 * 1e-9)",
+            "(2500.0 - 10.01)",
+            "9223372036854775807",
+            "9223372036854775807",
+        ]
+    }
+
+    #[test]
+    fn test_number_literal() {
+        setup_number_literal_input()
+            .into_iter()
+            .zip(setup_number_literal_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                check_parser_errors(&parser, i);
+
+                assert_eq!(expect, string(&program), "[{}] program string wrong", i);
+            });
+    }
+
+    fn setup_number_error_input() -> Vec<String> {
+        vec![
+            "9223372036854775808",
+            "0x",
+            "0b102",
+            "1__0 + 1_",
+            "0x_ff",
+            "12abc",
+            "1e",
+            "3.",
+            "1.e5",
+            "1e400",
+            "let x = 0o8; let ok = 1;",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_number_error_expect() -> Vec<Vec<&'static str>> {
+        vec![
+            vec!["1:1: number literal 9223372036854775808 is out of range."],
+            vec!["1:1: malformed number literal 0x."],
+            vec!["1:1: malformed number literal 0b102."],
+            vec!["1:8: malformed number literal 1_."],
+            vec!["1:1: malformed number literal 0x_ff."],
+            vec!["1:1: malformed number literal 12abc."],
+            vec!["1:1: malformed number literal 1e."],
+            vec!["1:1: malformed number literal 3.."],
+            vec!["1:1: malformed number literal 1.e5."],
+            vec!["1:1: number literal 1e400 is out of range."],
+            vec!["1:9: malformed number literal 0o8."],
+        ]
+    }
+
+    #[test]
+    fn test_number_error() {
+        setup_number_error_input()
+            .into_iter()
+            .zip(setup_number_error_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                parser.parse_program();
+
+                assert_eq!(
+                    expect,
+                    parser
+                        .errors
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>(),
+                    "[{}] parser errors wrong",
+                    i
+                );
+            });
+    }
+
+    #[test]
+    fn test_array_literal() {
+        let input = "[1, 2 * 2, f(x)]; []".to_string();
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+        check_parser_errors(&parser, 0);
+
+        assert_eq!(2, program.len(), "program has wrong length");
+        match program[0] {
+            Statement::Expression(ref statement) => match *statement.expression {
+                Expression::Array(ref array) => {
+                    assert_eq!(3, array.elements.len(), "array has wrong length");
+                    assert_eq!("[1, (2 * 2), f(x)]", array.to_string());
+                    assert_eq!(
+                        (0, 16),
+                        (array.span().start.offset, array.span().end.offset),
+                        "array has wrong span"
+                    );
+                }
+                ref other => panic!("expression is not Array. got={}", other),
+            },
+            ref other => panic!("statement is not Expression. got={}", other),
+        }
+        assert_eq!("[]", program[1].to_string());
+    }
+
+    #[test]
+    fn test_index_expression() {
+        let input = "array[1 + 1]".to_string();
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+        check_parser_errors(&parser, 0);
+
+        match program[0] {
+            Statement::Expression(ref statement) => match *statement.expression {
+                Expression::Index(ref index) => {
+                    assert_eq!("array", index.left.to_string());
+                    assert_eq!("(1 + 1)", index.index.to_string());
+                    assert_eq!(
+                        (0, 12),
+                        (index.span().start.offset, index.span().end.offset),
+                        "index has wrong span"
+                    );
+                }
+                ref other => panic!("expression is not Index. got={}", other),
+            },
+            ref other => panic!("statement is not Expression. got={}", other),
+        }
+    }
+
+    fn setup_unclosed_bracket_input() -> Vec<String> {
+        vec!["[1, 2", "a[1; let ok = 1;", "[1, 2)"]
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn setup_unclosed_bracket_expect() -> Vec<&'static str> {
+        vec![
+            "1:6: unclosed LBracket opened at 1:1. expected RBracket, got EOF instead.",
+            "1:4: unclosed LBracket opened at 1:2. expected RBracket, got Semicolon instead.",
+            "1:6: unclosed LBracket opened at 1:1. expected RBracket, got RParen instead.",
+        ]
+    }
+
+    #[test]
+    fn test_unclosed_bracket() {
+        setup_unclosed_bracket_input()
+            .into_iter()
+            .zip(setup_unclosed_bracket_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                parser.parse_program();
+
+                assert_eq!(
+                    vec![expect],
+                    parser
+                        .errors
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>(),
+                    "[{}] parser errors wrong",
+                    i
+                );
+            });
+    }
+
+    fn setup_hash_literal_input() -> Vec<String> {
+        vec![
+            r#"{"one": 1, "two": 2, "three": 3}"#,
+            "{}",
+            r#"{"one": 0 + 1, 2: 10 - 8, true: 15 / 5,}"#,
+            "let h = {a: {b: [1]}}; h[a][b]",
+            "if (x) { {1: 2} }",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_hash_literal_expect() -> Vec<&'static str> {
+        vec![
+            r#"{"one": 1, "two": 2, "three": 3}"#,
+            "{}",
+            r#"{"one": (0 + 1), 2: (10 - 8), true: (15 / 5)}"#,
+            "let h = {a: {b: [1]}};((h[a])[b])",
+            "if (x) { {1: 2} }",
+        ]
+    }
+
+    #[test]
+    fn test_hash_literal() {
+        setup_hash_literal_input()
+            .into_iter()
+            .zip(setup_hash_literal_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                check_parser_errors(&parser, i);
+
+                assert_eq!(expect, string(&program), "[{}] program string wrong", i);
+            });
+    }
+
+    fn setup_hash_literal_error_input() -> Vec<String> {
+        vec![r#"{"a" 1}"#, r#"{"a": 1 "b": 2}"#, r#"{"a": 1"#]
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn setup_hash_literal_error_expect() -> Vec<&'static str> {
+        vec![
+            "1:6: expected next token to be Colon, got Int instead.",
+            "1:9: expected next token to be Comma, got String instead.",
+            "1:8: expected next token to be Comma, got EOF instead.",
+        ]
+    }
+
+    #[test]
+    fn test_hash_literal_error() {
+        setup_hash_literal_error_input()
+            .into_iter()
+            .zip(setup_hash_literal_error_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                parser.parse_program();
+
+                let error = parser.errors.first();
+                assert!(error.is_some(), "[{}] parser has no errors", i);
+                assert_eq!(
+                    expect,
+                    error.unwrap().to_string(),
+                    "[{}] error message wrong",
+                    i
+                );
+            });
+    }
+
+    fn setup_assign_statement_input() -> Vec<String> {
+        vec![
+            "x = 5;",
+            "x = x + 1",
+            "total += a * b;",
+            "x -= 1; x *= 2; x /= 3;",
+            "arr[0] = 1;",
+            r#"h["k"][i + 1] = v"#,
+            "if (a) { b = 1 } else { c += 1 }",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_assign_statement_expect() -> Vec<&'static str> {
+        vec![
+            "x = 5;",
+            "x = (x + 1);",
+            "total += (a * b);",
+            "x -= 1;x *= 2;x /= 3;",
+            "arr[0] = 1;",
+            r#"(h["k"])[(i + 1)] = v;"#,
+            "if (a) { b = 1; } else { c += 1; }",
+        ]
+    }
+
+    #[test]
+    fn test_assign_statement() {
+        setup_assign_statement_input()
+            .into_iter()
+            .zip(setup_assign_statement_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                check_parser_errors(&parser, i);
+
+                assert_eq!(expect, string(&program), "[{}] program string wrong", i);
+                program.iter().for_each(|statement| match statement {
+                    Statement::Assign(_) | Statement::Expression(_) => {}
+                    other => panic!("[{}] statement is not Assign. got={}", i, other),
+                });
+            });
+
+        let mut parser = Parser::new(Lexer::new("x = 1".to_string()));
+        let program = parser.parse_program();
+        match program[0] {
+            Statement::Assign(ref statement) => {
+                assert_eq!("=", statement.operator);
+                assert_eq!(
+                    (0, 5),
+                    (statement.span().start.offset, statement.span().end.offset),
+                    "statement has wrong span"
+                );
+            }
+            ref other => panic!("statement is not Assign. got={}", other),
+        }
+    }
+
+    fn setup_assign_error_input() -> Vec<String> {
+        vec![
+            "1 = 2; let ok = 1;",
+            "f(x) = 2;",
+            "f(x)[0] = 2;",
+            "x + y += 1;",
+            "x = ;",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_assign_error_expect() -> Vec<(&'static str, Vec<&'static str>)> {
+        vec![
+            ("let ok = 1;", vec!["1:1: invalid assignment target."]),
+            ("", vec!["1:1: invalid assignment target."]),
+            ("", vec!["1:1: invalid assignment target."]),
+            ("", vec!["1:1: invalid assignment target."]),
+            ("", vec!["1:5: no prefix parse for Semicolon."]),
+        ]
+    }
+
+    #[test]
+    fn test_assign_error() {
+        setup_assign_error_input()
+            .into_iter()
+            .zip(setup_assign_error_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, (expect_program, expect_errors)))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+
+                assert_eq!(
+                    expect_program,
+                    string(&program),
+                    "[{}] recovered program wrong",
+                    i
+                );
+                assert_eq!(
+                    expect_errors,
+                    parser
+                        .errors
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>(),
+                    "[{}] parser errors wrong",
+                    i
+                );
+            });
+    }
+
+    fn setup_while_statement_input() -> Vec<String> {
+        vec![
+            "while (x < 10) { x += 1; }",
+            "while (true) { if (x) { break; } else { continue; } }",
+            "while (x) { while (y) { break } continue }",
+            "while (x) { let f = fn() { while (y) { continue; } }; }",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
     }
 
-    fn assert_integer_literal(expression: &Expression, value: i64, i: usize) {
-        let integer_literal = match expression {
-            Expression::Integer(literal) => literal,
-            _ => panic!("[{}] expression not IntegerLiteral. got={}", i, expression),
-        };
+    fn setup_while_statement_expect() -> Vec<&'static str> {
+        vec![
+            "while (x < 10) { x += 1; }",
+            "while (true) { if (x) { break; } else { continue; } }",
+            "while (x) { while (y) { break; } continue; }",
+            "while (x) { let f = fn() { while (y) { continue; } }; }",
+        ]
+    }
 
-        assert_eq!(
-            value, integer_literal.value,
-            "[{}] literal value not {}. got={}",
-            i, value, integer_literal.value
-        );
-        assert_eq!(
-            value.to_string(),
-            integer_literal.token_literal(),
-            "[{}] literal token_literal not {}. got={}",
-            i,
-            value.to_string(),
-            integer_literal.token_literal()
-        );
+    #[test]
+    fn test_while_statement() {
+        setup_while_statement_input()
+            .into_iter()
+            .zip(setup_while_statement_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                check_parser_errors(&parser, i);
+
+                assert_eq!(expect, string(&program), "[{}] program string wrong", i);
+                match program[0] {
+                    Statement::While(_) => {}
+                    ref other => panic!("[{}] statement is not While. got={}", i, other),
+                }
+            });
     }
 
-    fn assert_identifier(expression: &Expression, value: String, i: usize) {
-        let identifier = match expression {
-            Expression::Identifier(identifier) => identifier,
-            _ => panic!("[{}] expression not Identifier. got={}", i, expression),
-        };
-        assert_eq!(
-            identifier.value, value,
-            "[{}] identifier.value not {}. got={}",
-            i, value, identifier.value
-        );
-        assert_eq!(
-            identifier.token_literal(),
-            value,
-            "[{}] identifier.token_literal() not {}. got={}",
-            i,
-            value,
-            identifier.token_literal()
-        );
+    fn setup_loop_control_error_input() -> Vec<String> {
+        vec![
+            "break; let ok = 1;",
+            "continue",
+            "if (x) { break; }",
+            "while (x) { fn() { continue; }; }",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
     }
 
-    fn assert_boolean_literal(expression: &Expression, value: bool, i: usize) {
-        let boolean = match expression {
-            Expression::Boolean(boolean) => boolean,
-            _ => panic!("[{}] expression not boolean. got={}", i, expression),
+    fn setup_loop_control_error_expect() -> Vec<(&'static str, Vec<&'static str>)> {
+        vec![
+            ("let ok = 1;", vec!["1:1: break outside of a loop."]),
+            ("", vec!["1:1: continue outside of a loop."]),
+            ("if (x) { }", vec!["1:10: break outside of a loop."]),
+            (
+                "while (x) { fn() { } }",
+                vec!["1:20: continue outside of a loop."],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_loop_control_error() {
+        setup_loop_control_error_input()
+            .into_iter()
+            .zip(setup_loop_control_error_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, (expect_program, expect_errors)))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+
+                assert_eq!(
+                    expect_program,
+                    string(&program),
+                    "[{}] recovered program wrong",
+                    i
+                );
+                assert_eq!(
+                    expect_errors,
+                    parser
+                        .errors
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>(),
+                    "[{}] parser errors wrong",
+                    i
+                );
+            });
+    }
+
+    fn setup_for_statement_input() -> Vec<String> {
+        vec![
+            "for (x in xs) { puts(x); }",
+            "for (k, v in {\"a\": 1}) { puts(k, v) };",
+            "for (i in 0..10) { if (i == 5) { break; } continue; }",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_for_statement_expect() -> Vec<(&'static str, Option<&'static str>, &'static str)> {
+        vec![
+            ("x", None, "for (x in xs) { puts(x) }"),
+            ("k", Some("v"), "for (k, v in {\"a\": 1}) { puts(k, v) }"),
+            (
+                "i",
+                None,
+                "for (i in (0..10)) { if (i == 5) { break; } continue; }",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_for_statement() {
+        setup_for_statement_input()
+            .into_iter()
+            .zip(setup_for_statement_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, (key, value, expect)))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                check_parser_errors(&parser, i);
+
+                assert_eq!(1, program.len(), "[{}] program has wrong length", i);
+                assert_eq!(expect, string(&program), "[{}] program string wrong", i);
+                match program[0] {
+                    Statement::For(ref statement) => {
+                        assert_eq!(key, statement.key.value, "[{}] key wrong", i);
+                        assert_eq!(
+                            value,
+                            statement.value.as_ref().map(|value| value.value.as_str()),
+                            "[{}] value wrong",
+                            i
+                        );
+                    }
+                    ref other => panic!("[{}] statement is not For. got={}", i, other),
+                }
+            });
+    }
+
+    fn setup_for_statement_error_input() -> Vec<String> {
+        vec![
+            "for (x xs) { } let ok = 1;",
+            "for (1 in xs) { }",
+            "for (x in xs) { fn() { break; } }",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_for_statement_error_expect() -> Vec<(&'static str, Vec<&'static str>)> {
+        vec![
+            (
+                "let ok = 1;",
+                vec!["1:8: expected next token to be In, got Ident instead."],
+            ),
+            (
+                "",
+                vec!["1:6: expected next token to be Ident, got Int instead."],
+            ),
+            (
+                "for (x in xs) { fn() { } }",
+                vec!["1:24: break outside of a loop."],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_for_statement_error() {
+        setup_for_statement_error_input()
+            .into_iter()
+            .zip(setup_for_statement_error_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, (expect_program, expect_errors)))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+
+                assert_eq!(
+                    expect_program,
+                    string(&program),
+                    "[{}] recovered program wrong",
+                    i
+                );
+                assert_eq!(
+                    expect_errors,
+                    parser
+                        .errors
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>(),
+                    "[{}] parser errors wrong",
+                    i
+                );
+            });
+    }
+
+    fn setup_match_expression_input() -> Vec<String> {
+        vec![
+            "match (x) { 0 => \"zero\", -1 => \"minus one\", _ => \"other\" }",
+            "match (xs) { [] => 0, [x, ...rest] => x + sum(rest), }",
+            "match (h) { {\"k\": v, 1: [_, y]} => v, n if n > 1 => n }",
+            "let y = match (a + b) { true => 1.5, false => x };",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_match_expression_expect() -> Vec<(&'static str, usize)> {
+        vec![
+            (
+                "match (x) { 0 => \"zero\", (-1) => \"minus one\", _ => \"other\" }",
+                3,
+            ),
+            ("match (xs) { [] => 0, [x, ...rest] => (x + sum(rest)) }", 2),
+            (
+                "match (h) { {\"k\": v, 1: [_, y]} => v, n if (n > 1) => n }",
+                2,
+            ),
+            ("let y = match (a + b) { true => 1.5, false => x };", 2),
+        ]
+    }
+
+    #[test]
+    fn test_match_expression() {
+        setup_match_expression_input()
+            .into_iter()
+            .zip(setup_match_expression_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, (expect, arms)))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                let program = parser.parse_program();
+                check_parser_errors(&parser, i);
+
+                assert_eq!(expect, string(&program), "[{}] program string wrong", i);
+                let expression = match program[0] {
+                    Statement::Expression(ref statement) => &statement.expression,
+                    Statement::Let(ref statement) => &statement.expression,
+                    ref other => panic!("[{}] unexpected statement. got={}", i, other),
+                };
+                match **expression {
+                    Expression::Match(ref match_expression) => {
+                        assert_eq!(arms, match_expression.arms.len(), "[{}] wrong arms", i)
+                    }
+                    ref other => panic!("[{}] expression is not Match. got={}", i, other),
+                }
+            });
+    }
+
+    fn setup_match_expression_error_input() -> Vec<String> {
+        vec![
+            "match (x) { f(1) => 1 }",
+            "match (x) { -a => 1 }",
+            "match (x) { {k: v} => 1 }",
+            "match (x) { [...a, b] => 1 }",
+            "match (x) { 1 -> 2 }",
+            "match (x) { 1 => 2 3 => 4 }",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    }
+
+    fn setup_match_expression_error_expect() -> Vec<&'static str> {
+        vec![
+            "1:14: expected next token to be FatArrow, got LParen instead.",
+            "1:13: no pattern parse for Minus.",
+            "1:14: no pattern parse for Ident.",
+            "1:18: unclosed LBracket opened at 1:13. expected RBracket, got Comma instead.",
+            "1:15: expected next token to be FatArrow, got Minus instead.",
+            "1:20: expected next token to be Comma, got Int instead.",
+        ]
+    }
+
+    #[test]
+    fn test_match_expression_error() {
+        setup_match_expression_error_input()
+            .into_iter()
+            .zip(setup_match_expression_error_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                parser.parse_program();
+
+                let error = parser.errors.first();
+                assert!(error.is_some(), "[{}] parser has no errors", i);
+                assert_eq!(
+                    expect,
+                    error.unwrap().to_string(),
+                    "[{}] error message wrong",
+                    i
+                );
+            });
+    }
+
+    #[test]
+    fn test_macro_literal_parsing() {
+        let mut parser = Parser::new(Lexer::new("macro(x, y) { x + y; }".to_string()));
+        let program = parser.parse_program();
+        check_parser_errors(&parser, 0);
+
+        assert_eq!("macro(x, y) { (x + y) }", string(&program), "program wrong");
+        let expression = match program.get(0) {
+            Some(Statement::Expression(statement)) => &statement.expression,
+            other => panic!(
+                "program statement is not ExpressionStatement. got={:?}",
+                other
+            ),
         };
+        match **expression {
+            Expression::Macro(ref macro_literal) => {
+                assert_eq!(2, macro_literal.parameters.len(), "parameters wrong");
+                assert_eq!(1, macro_literal.body.statements.len(), "body wrong");
+            }
+            ref other => panic!("expression is not MacroLiteral. got={}", other),
+        }
+    }
+
+    // DocCollector gathers the documentation of every let statement, nested ones too.
+    #[derive(Default)]
+    struct DocCollector {
+        docs: Vec<(String, Option<String>)>,
+    }
+
+    impl pygmaea::visit::Visitor for DocCollector {
+        fn visit_let_statement(&mut self, statement: &LetStatement) {
+            self.docs
+                .push((statement.identifier.value.clone(), statement.doc.clone()));
+            pygmaea::visit::walk_let_statement(self, statement);
+        }
+    }
+
+    #[test]
+    fn test_doc_comment() {
+        use pygmaea::visit::Visitor;
+
+        let input = "
+            /// Adds two numbers.
+            ///
+            ///   Returns a + b.
+            let add = fn(a, b) {
+                /// the sum.
+                let sum = a + b; sum
+            };
+            // not a doc comment.
+            let x = 1; /// documents y.
+            let y = 2;
+            //// not a doc comment either.
+            /* nor /* this */ */ let z = 3;
+            /// a doc comment on an expression is dropped.
+            add(x, y)
+        ";
+        let mut parser = Parser::new(Lexer::new(input.to_string()));
+        let program = parser.parse_program();
+        check_parser_errors(&parser, 0);
+
+        let mut collector = DocCollector::default();
+        collector.visit_program(&program);
+        let docs: Vec<_> = collector
+            .docs
+            .iter()
+            .map(|(name, doc)| (name.as_str(), doc.as_deref()))
+            .collect();
         assert_eq!(
-            value, boolean.value,
-            "[{}] expression.value not {}. got={}",
-            i, value, boolean.value
+            docs,
+            vec![
+                ("add", Some("Adds two numbers.\n\n  Returns a + b.")),
+                ("sum", Some("the sum.")),
+                ("x", None),
+                ("y", Some("documents y.")),
+                ("z", None),
+            ]
         );
         assert_eq!(
-            value.to_string(),
-            boolean.token_literal(),
-            "[{}] expression.token_literal() not {}. got={}",
-            i,
-            value.to_string(),
-            boolean.token_literal()
+            "let add = fn(a, b) { let sum = (a + b); sum };let x = 1;let y = 2;let z = 3;add(x, y)",
+            string(&program),
+            "program wrong"
         );
     }
 
-    fn check_parser_errors(parser: &Parser, i: usize) {
-        if parser.errors.is_empty() {
-            return;
-        }
+    #[test]
+    fn test_unterminated_comment() {
+        let mut parser = Parser::new(Lexer::new("let x = 1; /* a /* b */".to_string()));
+        let program = parser.parse_program();
 
-        eprintln!("[{}] parser has {} errors", i, parser.errors.len());
-        parser
-            .errors
-            .iter()
-            .enumerate()
-            .for_each(|(i, err)| eprintln!("[{}] parser error: {}", i, err));
+        assert_eq!("let x = 1;", string(&program), "program wrong");
+        let errors: Vec<_> = parser.errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(errors, vec!["1:12: unterminated block comment."]);
     }
 
-    trait Wrapped {
-        fn assert_literal_expression(self, expression: &Expression, i: usize);
+    fn setup_nesting_depth_input() -> Vec<String> {
+        let depth = 20_000;
+        vec![
+            "(".repeat(depth) + "1" + &")".repeat(depth),
+            "-".repeat(depth) + "1",
+            "[".repeat(depth) + &"]".repeat(depth),
+            "{1: ".repeat(depth) + &"}".repeat(depth),
+            "if (x) { ".repeat(depth) + &"}".repeat(depth),
+            "while (x) { ".repeat(depth) + &"}".repeat(depth),
+            "match (x) { ".to_string() + &"[".repeat(depth) + &"]".repeat(depth) + " => 1 }",
+        ]
     }
 
-    enum Concrete {
-        Integer(i64),
-        String(String),
-        Boolean(bool),
+    fn setup_nesting_depth_expect() -> Vec<usize> {
+        vec![
+            DEFAULT_MAX_DEPTH + 1,
+            DEFAULT_MAX_DEPTH + 1,
+            DEFAULT_MAX_DEPTH + 1,
+            4 * DEFAULT_MAX_DEPTH - 2,
+            9 * DEFAULT_MAX_DEPTH / 2 + 1,
+            12 * DEFAULT_MAX_DEPTH + 8,
+            DEFAULT_MAX_DEPTH + 12,
+        ]
     }
 
-    impl Wrapped for Concrete {
-        fn assert_literal_expression(self, expression: &Expression, i: usize) {
-            match self {
-                Concrete::Integer(v) => assert_integer_literal(expression, v, i),
-                Concrete::String(v) => assert_identifier(expression, v, i),
-                Concrete::Boolean(v) => assert_boolean_literal(expression, v, i),
-            }
+    #[test]
+    fn test_nesting_depth() {
+        setup_nesting_depth_input()
+            .into_iter()
+            .zip(setup_nesting_depth_expect().into_iter())
+            .enumerate()
+            .for_each(|(i, (input, column))| {
+                let mut parser = Parser::new(Lexer::new(input));
+                parser.parse_program();
+
+                let errors: Vec<_> = parser.errors.iter().map(|e| e.to_string()).collect();
+                let expect = format!(
+                    "1:{}: nesting depth exceeded. max={}.",
+                    column, DEFAULT_MAX_DEPTH
+                );
+                assert_eq!(errors, vec![expect], "[{}] wrong errors", i);
+            });
+    }
+
+    // else_if_chain spells out else if the only way the language has, one block deeper
+    // for every branch.
+    fn else_if_chain(branches: usize) -> String {
+        let mut input = "let name = fn(n) { ".to_string();
+        for i in 0..branches {
+            input += &format!("if (n == {}) {{ \"{}\" }} else {{ ", i, i);
         }
+        input += "\"many\"";
+        input += &" }".repeat(branches);
+        input + " };"
+    }
+
+    #[test]
+    fn test_else_if_chain() {
+        let mut parser = Parser::new(Lexer::new(else_if_chain(100)));
+        let program = parser.parse_program();
+
+        assert!(parser.errors.is_empty(), "errors: {:?}", parser.errors);
+        assert_eq!(program.len(), 1, "program wrong");
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let mut parser = Parser::new(Lexer::new(else_if_chain(100)));
+        parser.set_max_depth(64);
+        parser.parse_program();
+
+        let errors: Vec<_> = parser.errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(errors, vec!["1:879: nesting depth exceeded. max=64."]);
     }
 }
@@ -0,0 +1,236 @@
+#[cfg(test)]
+mod tests {
+    use pygmaea::ast::*;
+    use pygmaea::interpreter::Interpreter;
+    use pygmaea::json;
+    use pygmaea::lexer::Lexer;
+    use pygmaea::parser::Parser;
+    use pygmaea::serialize::{from_json, from_sexp, to_json, to_sexp, Value, MAX_DEPTH};
+    use pygmaea::sexp;
+
+    fn parse(input: &str) -> Program {
+        let mut parser = Parser::new(Lexer::new(input.to_string()));
+        let program = parser.parse_program();
+        assert!(
+            parser.errors.is_empty(),
+            "parser has errors. got={:?}",
+            parser.errors
+        );
+        program
+    }
+
+    fn string(program: &[Statement]) -> String {
+        program
+            .iter()
+            .map(|statement| statement.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            to_json(&parse("x;")),
+            concat!(
+                r#"[{"kind":"ExpressionStatement","expression":{"kind":"Identifier","#,
+                r#""token":{"kind":"Token","type":"Ident","literal":"x","span":[[0,1,1],[1,1,2]]},"#,
+                r#""value":"x"}}]"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_sexp() {
+        assert_eq!(
+            to_sexp(&parse("-1.5;")),
+            concat!(
+                "[(ExpressionStatement (PrefixExpression ",
+                r#"(Token Minus "-" [[0 1 1] [1 1 2]]) "-" "#,
+                r#"(FloatLiteral (Token Float "1.5" [[1 1 2] [4 1 5]]) 1.5)))]"#
+            )
+        );
+    }
+
+    fn setup_roundtrip_input() -> Vec<&'static str> {
+        vec![
+            "/// one.\nlet x = 1; return x;",
+            "x = 2.5; a[0] += -1;",
+            "while (true) { break; continue; }",
+            "for (k, v in 0..10) { k }; for (x in [1, 2]) { x }",
+            "if (a < b) { a } else { b }; if (c) { d }",
+            "fn(a, b) { a + b }(1, 2)",
+            r#"let s = "a\"b\n${x + 1}c"; {"k": [1, false]}"#,
+            "match (x) { [a, ...rest] if a > 0 => rest, {\"k\": v} => v, -1 => 0, _ => x }",
+            "let m = macro(a) { quote(unquote(a) * 2) };",
+        ]
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        setup_roundtrip_input()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, input)| {
+                let program = parse(input);
+                let dumped = to_json(&program);
+                let loaded = from_json(&dumped)
+                    .unwrap_or_else(|e| panic!("tests[{}] - json error: {}", i, e));
+                assert_eq!(to_json(&loaded), dumped, "tests[{}] - json wrong", i);
+                assert_eq!(string(&loaded), string(&program), "tests[{}] - json", i);
+
+                let dumped = to_sexp(&program);
+                let loaded = from_sexp(&dumped)
+                    .unwrap_or_else(|e| panic!("tests[{}] - sexp error: {}", i, e));
+                assert_eq!(to_sexp(&loaded), dumped, "tests[{}] - sexp wrong", i);
+                assert_eq!(string(&loaded), string(&program), "tests[{}] - sexp", i);
+            });
+    }
+
+    #[test]
+    fn test_roundtrip_eval() {
+        let input = "
+            let unless = macro(c, x, y) { quote(if (!(unquote(c))) { unquote(x) } else { unquote(y) }) };
+            let sum = fn(xs) { let total = 0; for (x in xs) { total += x; }; total };
+            match ([sum(1..5), 2]) { [10, y] => unless(y > 2, \"${y}\", \"no\"), _ => \"no\" }
+        ";
+        let expect = Interpreter::new().eval_str(input).unwrap().to_string();
+        assert_eq!(expect, "2");
+
+        let program = parse(input);
+        let from_json = from_json(&to_json(&program)).unwrap();
+        let result = Interpreter::new().eval_program(from_json).unwrap();
+        assert_eq!(result.to_string(), expect, "json result wrong");
+
+        let from_sexp = from_sexp(&to_sexp(&program)).unwrap();
+        let result = Interpreter::new().eval_program(from_sexp).unwrap();
+        assert_eq!(result.to_string(), expect, "sexp result wrong");
+    }
+
+    #[test]
+    fn test_value() {
+        let value = Value::node(
+            "Node",
+            vec![
+                ("a", Value::List(vec![Value::Null, Value::Boolean(true)])),
+                ("b", Value::Float(1.0)),
+                ("c", Value::Float(f64::INFINITY)),
+                ("d", Value::String("\"\\\n\u{1}é".to_string())),
+                ("e", Value::Integer(-3)),
+            ],
+        );
+        let expect_json =
+            r#"{"kind":"Node","a":[null,true],"b":1.0,"c":"inf","d":"\"\\\n\u0001é","e":-3}"#;
+        assert_eq!(json::to_string(&value), expect_json);
+        match json::from_str(expect_json).unwrap() {
+            Value::Node(_, fields) => {
+                // infinity only survives as a string until it is read as a float.
+                assert_eq!(fields[2].1, Value::String("inf".to_string()));
+                assert_eq!(fields[3].1, Value::String("\"\\\n\u{1}é".to_string()));
+            }
+            other => panic!("value is not a node. got={:?}", other),
+        }
+
+        let expect_sexp = r#"(Node [nil true] 1.0 "inf" "\"\\\n\u0001é" -3)"#;
+        assert_eq!(sexp::to_string(&value), expect_sexp);
+        match sexp::from_str(expect_sexp).unwrap() {
+            Value::Node(kind, fields) => {
+                assert_eq!(kind, "Node");
+                let values: Vec<_> = fields.into_iter().map(|(_, value)| value).collect();
+                assert_eq!(
+                    values,
+                    vec![
+                        Value::List(vec![Value::Null, Value::Boolean(true)]),
+                        Value::Float(1.0),
+                        Value::String("inf".to_string()),
+                        Value::String("\"\\\n\u{1}é".to_string()),
+                        Value::Integer(-3),
+                    ]
+                );
+            }
+            other => panic!("value is not a node. got={:?}", other),
+        }
+        assert_eq!(
+            json::from_str(r#" [ "😀", 1e3 ] "#).unwrap(),
+            Value::List(vec![
+                Value::String("\u{1F600}".to_string()),
+                Value::Float(1000.0)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_deserialize_error() {
+        vec![
+            (from_json("[1"), "1:3: unexpected end of input."),
+            (from_json("[1 2]"), "1:4: unexpected character '2'."),
+            (from_json("[1] x"), "1:5: unexpected character 'x'."),
+            (
+                from_json(r#"["\q"]"#),
+                r#"1:3: invalid escape sequence \q."#,
+            ),
+            (
+                from_json(r#"["\ud800\u0041"]"#),
+                r#"1:3: invalid escape sequence \ud800\u0041."#,
+            ),
+            (from_json("[1-]"), "1:2: invalid number 1-."),
+            (from_json("{}"), "missing field kind in object at 1:1."),
+            (from_json(r#"{"kind":"X"}"#), "expected List, got X."),
+            (from_json("[1]"), "expected Statement, got Integer."),
+            (
+                from_json(r#"[{"kind":"Nope"}]"#),
+                "unknown node kind: Nope.",
+            ),
+            (
+                from_json(r#"[{"kind":"BreakStatement"}]"#),
+                "missing field token in BreakStatement.",
+            ),
+            (
+                from_sexp(r#"[(BreakStatement (Token Brake "break" [[0 1 1] [5 1 6]]))]"#),
+                "unknown token type: Brake.",
+            ),
+            (
+                from_sexp(r#"[(ExpressionStatement (Identifier 1 "x"))]"#),
+                "expected Token, got Integer.",
+            ),
+            (from_sexp("[(]"), "1:3: unexpected character ']'."),
+        ]
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, (result, expect))| match result {
+            Ok(program) => panic!("tests[{}] - no error. got={}", i, string(&program)),
+            Err(error) => assert_eq!(error.to_string(), expect, "tests[{}] - error wrong", i),
+        });
+    }
+
+    #[test]
+    fn test_nesting_depth() {
+        let depth = 200_000;
+        let json = "[".repeat(depth) + &"]".repeat(depth);
+        let sexp = "(A ".repeat(depth) + &")".repeat(depth);
+        vec![
+            (from_json(&json), MAX_DEPTH + 1),
+            (from_sexp(&sexp), 3 * MAX_DEPTH + 1),
+        ]
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, (result, column))| match result {
+            Ok(program) => panic!("tests[{}] - no error. got={}", i, string(&program)),
+            Err(error) => assert_eq!(
+                error.to_string(),
+                format!("1:{}: nesting depth exceeded. max={}.", column, MAX_DEPTH),
+                "tests[{}] - error wrong",
+                i
+            ),
+        });
+
+        // a long else if chain still loads.
+        let mut input = String::new();
+        for i in 0..100 {
+            input += &format!("if (n == {}) {{ {} }} else {{ ", i, i);
+        }
+        let program = parse(&(input + "-1" + &" }".repeat(100)));
+        let loaded = from_json(&to_json(&program)).unwrap();
+        assert_eq!(string(&loaded), string(&program), "json wrong");
+        let loaded = from_sexp(&to_sexp(&program)).unwrap();
+        assert_eq!(string(&loaded), string(&program), "sexp wrong");
+    }
+}
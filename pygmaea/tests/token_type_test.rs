@@ -3,23 +3,38 @@ mod tests {
     use pygmaea::token_type::TokenType::*;
     use pygmaea::token_type::*;
 
-    const TOKEN_TYPES: [TokenType; 27] = [
+    const TOKEN_TYPES: [TokenType; 54] = [
         Plus,
         Minus,
         Asterisk,
         Slash,
+        Percent,
         Assign,
+        PlusAssign,
+        MinusAssign,
+        AsteriskAssign,
+        SlashAssign,
         Bang,
         LessThan,
         GreaterThan,
+        LessThanOrEqual,
+        GreaterThanOrEqual,
         Equal,
         NotEqual,
+        And,
+        Or,
         Comma,
         Semicolon,
+        Colon,
+        DotDot,
+        Ellipsis,
+        FatArrow,
         LParen,
         RParen,
         LBrace,
         RBrace,
+        LBracket,
+        RBracket,
         True,
         False,
         Let,
@@ -27,14 +42,28 @@ mod tests {
         If,
         Else,
         Return,
+        While,
+        Break,
+        Continue,
+        For,
+        In,
+        Match,
+        Macro,
         Int,
+        Float,
+        String,
+        StringStart,
+        StringMiddle,
+        StringEnd,
         Ident,
         EOF,
         Illegal,
     ];
 
-    const KEYWORD_TOKEN_TYPES: [TokenType; 8] =
-        [True, False, Let, Function, If, Else, Return, Ident];
+    const KEYWORD_TOKEN_TYPES: [TokenType; 15] = [
+        True, False, Let, Function, If, Else, Return, While, Break, Continue, For, In, Match,
+        Macro, Ident,
+    ];
 
     #[test]
     fn test_is_keyword() {
@@ -53,6 +82,16 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_is_number() {
+        TOKEN_TYPES.iter().for_each(|token_type| {
+            assert_eq!(
+                token_type == &Int || token_type == &Float,
+                token_type.is_number()
+            );
+        })
+    }
+
     #[test]
     fn test_is_eof() {
         TOKEN_TYPES.iter().for_each(|token_type| {
@@ -80,18 +119,33 @@ mod tests {
             Minus => assert_eq!("Minus", format!("{}", token_type)),
             Asterisk => assert_eq!("Asterisk", format!("{}", token_type)),
             Slash => assert_eq!("Slash", format!("{}", token_type)),
+            Percent => assert_eq!("Percent", format!("{}", token_type)),
             Assign => assert_eq!("Assign", format!("{}", token_type)),
+            PlusAssign => assert_eq!("PlusAssign", format!("{}", token_type)),
+            MinusAssign => assert_eq!("MinusAssign", format!("{}", token_type)),
+            AsteriskAssign => assert_eq!("AsteriskAssign", format!("{}", token_type)),
+            SlashAssign => assert_eq!("SlashAssign", format!("{}", token_type)),
             Bang => assert_eq!("Bang", format!("{}", token_type)),
             LessThan => assert_eq!("LessThan", format!("{}", token_type)),
             GreaterThan => assert_eq!("GreaterThan", format!("{}", token_type)),
+            LessThanOrEqual => assert_eq!("LessThanOrEqual", format!("{}", token_type)),
+            GreaterThanOrEqual => assert_eq!("GreaterThanOrEqual", format!("{}", token_type)),
             Equal => assert_eq!("Equal", format!("{}", token_type)),
             NotEqual => assert_eq!("NotEqual", format!("{}", token_type)),
+            And => assert_eq!("And", format!("{}", token_type)),
+            Or => assert_eq!("Or", format!("{}", token_type)),
             LParen => assert_eq!("LParen", format!("{}", token_type)),
             RParen => assert_eq!("RParen", format!("{}", token_type)),
             LBrace => assert_eq!("LBrace", format!("{}", token_type)),
             RBrace => assert_eq!("RBrace", format!("{}", token_type)),
+            LBracket => assert_eq!("LBracket", format!("{}", token_type)),
+            RBracket => assert_eq!("RBracket", format!("{}", token_type)),
             Comma => assert_eq!("Comma", format!("{}", token_type)),
             Semicolon => assert_eq!("Semicolon", format!("{}", token_type)),
+            Colon => assert_eq!("Colon", format!("{}", token_type)),
+            DotDot => assert_eq!("DotDot", format!("{}", token_type)),
+            Ellipsis => assert_eq!("Ellipsis", format!("{}", token_type)),
+            FatArrow => assert_eq!("FatArrow", format!("{}", token_type)),
             True => assert_eq!("True", format!("{}", token_type)),
             False => assert_eq!("False", format!("{}", token_type)),
             Let => assert_eq!("Let", format!("{}", token_type)),
@@ -99,7 +153,19 @@ mod tests {
             If => assert_eq!("If", format!("{}", token_type)),
             Else => assert_eq!("Else", format!("{}", token_type)),
             Return => assert_eq!("Return", format!("{}", token_type)),
+            While => assert_eq!("While", format!("{}", token_type)),
+            Break => assert_eq!("Break", format!("{}", token_type)),
+            Continue => assert_eq!("Continue", format!("{}", token_type)),
+            For => assert_eq!("For", format!("{}", token_type)),
+            In => assert_eq!("In", format!("{}", token_type)),
+            Match => assert_eq!("Match", format!("{}", token_type)),
+            Macro => assert_eq!("Macro", format!("{}", token_type)),
             Int => assert_eq!("Int", format!("{}", token_type)),
+            Float => assert_eq!("Float", format!("{}", token_type)),
+            String => assert_eq!("String", format!("{}", token_type)),
+            StringStart => assert_eq!("StringStart", format!("{}", token_type)),
+            StringMiddle => assert_eq!("StringMiddle", format!("{}", token_type)),
+            StringEnd => assert_eq!("StringEnd", format!("{}", token_type)),
             Ident => assert_eq!("Ident", format!("{}", token_type)),
             EOF => assert_eq!("EOF", format!("{}", token_type)),
             Illegal => assert_eq!("Illegal", format!("{}", token_type)),
@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod tests {
+    use pygmaea::ast::*;
+    use pygmaea::fold::{self, Folder};
+    use pygmaea::lexer::Lexer;
+    use pygmaea::parser::Parser;
+    use pygmaea::visit::{self, Visitor};
+    use pygmaea::visit_mut::VisitorMut;
+
+    fn parse(input: &str) -> Program {
+        let mut parser = Parser::new(Lexer::new(input.to_string()));
+        let program = parser.parse_program();
+        assert!(
+            parser.errors.is_empty(),
+            "parser has errors. got={:?}",
+            parser.errors
+        );
+        program
+    }
+
+    fn string(program: &[Statement]) -> String {
+        program
+            .iter()
+            .map(|statement| statement.to_string())
+            .collect()
+    }
+
+    #[derive(Default)]
+    struct IdentifierCounter {
+        identifiers: Vec<String>,
+    }
+
+    impl Visitor for IdentifierCounter {
+        fn visit_identifier(&mut self, identifier: &Identifier) {
+            self.identifiers.push(identifier.value.clone());
+        }
+    }
+
+    fn setup_visit_input() -> Vec<&'static str> {
+        vec![
+            "let a = b + c;",
+            "a = [b, c][d]; e[f] += g;",
+            "while (a) { break; } for (b, c in d..e) { continue; }",
+            "fn(a, b) { a(b) }; macro(c) { quote(c) }",
+            r#"if (a) { b } else { {c: "${d}"} }"#,
+            r#"match (a) { [b, ...c] if d => e, {"f": g} => h, 1 => i, _ => j }"#,
+        ]
+    }
+
+    fn setup_visit_expect() -> Vec<Vec<&'static str>> {
+        vec![
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c", "d", "e", "f", "g"],
+            vec!["a", "b", "c", "d", "e"],
+            vec!["a", "b", "a", "b", "c", "quote", "c"],
+            vec!["a", "b", "c", "d"],
+            vec!["a", "b", "c", "d", "e", "g", "h", "i", "j"],
+        ]
+    }
+
+    #[test]
+    fn test_visitor() {
+        setup_visit_input()
+            .into_iter()
+            .zip(setup_visit_expect())
+            .enumerate()
+            .for_each(|(i, (input, expect))| {
+                let mut counter = IdentifierCounter::default();
+                counter.visit_program(&parse(input));
+                assert_eq!(
+                    counter.identifiers, expect,
+                    "tests[{}] - identifiers wrong",
+                    i
+                );
+            });
+    }
+
+    struct LetCounter {
+        lets: usize,
+        expressions: usize,
+    }
+
+    impl Visitor for LetCounter {
+        fn visit_let_statement(&mut self, statement: &LetStatement) {
+            self.lets += 1;
+            visit::walk_let_statement(self, statement);
+        }
+
+        fn visit_expression(&mut self, expression: &Expression) {
+            self.expressions += 1;
+            visit::walk_expression(self, expression);
+        }
+    }
+
+    #[test]
+    fn test_visitor_walk() {
+        let mut counter = LetCounter {
+            lets: 0,
+            expressions: 0,
+        };
+        counter.visit_program(&parse("let a = fn() { let b = 1 + 2; b }; a();"));
+        assert_eq!((counter.lets, counter.expressions), (2, 7));
+    }
+
+    struct Renamer;
+
+    impl VisitorMut for Renamer {
+        fn visit_identifier(&mut self, identifier: &mut Identifier) {
+            identifier.value = identifier.value.to_uppercase();
+        }
+
+        fn visit_function_literal(&mut self, function: &mut FunctionLiteral) {
+            // leave the parameters alone but keep renaming inside the body.
+            self.visit_block_statement(&mut function.body);
+        }
+    }
+
+    #[test]
+    fn test_visitor_mut() {
+        vec![
+            ("let a = b;", "let A = B;"),
+            ("a[b] = c;", "A[B] = C;"),
+            ("for (k, v in h) { k }", "for (K, V in H) { K }"),
+            ("fn(x) { x + y }", "fn(x) { (X + Y) }"),
+            (
+                "match (a) { [b, ...c] => c }",
+                "match (A) { [B, ...C] => C }",
+            ),
+        ]
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, (input, expect))| {
+            let mut program = parse(input);
+            Renamer.visit_program(&mut program);
+            assert_eq!(string(&program), expect, "tests[{}] - program wrong", i);
+        });
+    }
+
+    struct ConstantFolder;
+
+    impl Folder for ConstantFolder {
+        fn fold_expression(&mut self, expression: Expression) -> Expression {
+            match fold::walk_expression(self, expression) {
+                Expression::Infix(infix) => match (*infix.left, *infix.right) {
+                    (Expression::Integer(mut left), Expression::Integer(right))
+                        if infix.operator == "+" =>
+                    {
+                        left.value += right.value;
+                        Expression::Integer(left)
+                    }
+                    (left, right) => Expression::Infix(InfixExpression {
+                        left: Box::new(left),
+                        right: Box::new(right),
+                        ..infix
+                    }),
+                },
+                expression => expression,
+            }
+        }
+    }
+
+    #[test]
+    fn test_folder() {
+        vec![
+            ("1 + 2", "3"),
+            ("1 + 2 + 3", "6"),
+            ("a + 1 + 2", "((a + 1) + 2)"),
+            ("let a = [1 + 2, b + (3 + 4)];", "let a = [3, (b + 7)];"),
+            ("if (1 + 1) { f(2 + 2) }", "if (2) { f(4) }"),
+            (
+                "match (1 + 1) { x => x + (1 + 1) }",
+                "match (2) { x => (x + 2) }",
+            ),
+        ]
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, (input, expect))| {
+            let program = ConstantFolder.fold_program(parse(input));
+            assert_eq!(string(&program), expect, "tests[{}] - program wrong", i);
+        });
+    }
+}
@@ -1,10 +1,12 @@
-use pygmaea::lexer::Lexer;
+use pygmaea::error::InterpreterError;
+use pygmaea::interpreter::Interpreter;
 use std::io::Write;
 
 const PROMPT: &str = ">>";
 const EXIT_COMMAND: [&str; 3] = [":exit", ":quit", ":q"];
 
 pub fn start() {
+    let mut interpreter = Interpreter::new();
     loop {
         let mut command = String::new();
         print!("{} ", PROMPT);
@@ -20,11 +22,12 @@ pub fn start() {
             break;
         }
 
-        let mut lexer = Lexer::new(command);
-        let mut token = lexer.next_token();
-        while !token.token_type.is_eof() {
-            println!("{}", token);
-            token = lexer.next_token();
+        match interpreter.eval_str(&command) {
+            Ok(object) => println!("{}", object),
+            Err(InterpreterError::Parse(errors)) => {
+                errors.iter().for_each(|error| eprintln!("\t{}", error))
+            }
+            Err(InterpreterError::Runtime(error)) => eprintln!("runtime error: {}", error),
         }
     }
 }